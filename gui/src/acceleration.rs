@@ -0,0 +1,56 @@
+//! Make fast drags cover more range than slow ones.
+
+/// How a drag's speed scales its movement.
+///
+/// Below `threshold`, in normals per second, movement is 1:1. Above it the
+/// movement is multiplied by `1 + gain * excess^exponent`, where `excess` is
+/// how far past the threshold the drag is going.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Acceleration {
+    pub threshold: f32,
+    pub gain: f32,
+    pub exponent: f32,
+}
+
+impl Acceleration {
+    /// The factor a movement at `speed` normals per second is scaled by.
+    pub fn scale(&self, speed: f32) -> f32 {
+        let excess = (speed - self.threshold).max(0.0);
+
+        1.0 + self.gain * excess.powf(self.exponent)
+    }
+
+    /// `delta` scaled for having happened over `seconds`. A delta with no
+    /// time to measure, like the first of a drag, is left alone.
+    pub fn apply(&self, delta: f32, seconds: f32) -> f32 {
+        if seconds <= 0.0 {
+            return delta;
+        }
+
+        delta * self.scale(delta.abs() / seconds)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const CURVE: Acceleration = Acceleration {
+        threshold: 1.0,
+        gain: 0.5,
+        exponent: 2.0,
+    };
+
+    #[test]
+    fn slow_drags_are_one_to_one() {
+        assert_eq!(CURVE.apply(0.01, 0.1), 0.01);
+        assert_eq!(CURVE.apply(-0.01, 0.1), -0.01);
+    }
+
+    #[test]
+    fn fast_drags_go_further() {
+        // 3 normals a second is 2 past the threshold: 1 + 0.5 * 4.
+        assert!((CURVE.apply(0.3, 0.1) - 0.9).abs() < 1e-6);
+        assert!((CURVE.apply(-0.3, 0.1) + 0.9).abs() < 1e-6);
+    }
+}
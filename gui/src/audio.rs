@@ -0,0 +1,525 @@
+//! A sine oscillator played through the default output device.
+
+use cpal::traits::{DeviceTrait, HostTrait, StreamTrait};
+use cpal::{FromSample, SampleFormat, SizedSample};
+
+use std::f32::consts::{FRAC_1_SQRT_2, FRAC_PI_4, TAU};
+use std::fmt;
+use std::sync::atomic::{AtomicBool, AtomicU32, Ordering};
+use std::sync::Arc;
+
+// The level of the sine at 0 dB of gain, leaving some headroom.
+const LEVEL: f32 = 0.25;
+
+// A click's peak level and how long it takes to decay away.
+const CLICK_LEVEL: f32 = 0.2;
+const CLICK_SECONDS: f32 = 0.003;
+
+// The limiter passes samples below the knee untouched and bends everything
+// above it smoothly towards, but never past, full scale. The makeup is about
+// 1 dB, won back from the headroom the limiter leaves.
+const LIMITER_KNEE: f32 = 0.5;
+const LIMITER_MAKEUP: f32 = 1.12;
+
+/// Values written by the UI and read by the audio callback.
+///
+/// Each f32 is stored bit-cast in an atomic so the callback never locks.
+#[derive(Debug)]
+pub struct Shared {
+    frequency: AtomicU32,
+    gain: AtomicU32,
+    // From -1 for hard left to 1 for hard right.
+    pan: AtomicU32,
+    // Whether the sine's sign is flipped.
+    invert: AtomicBool,
+    // The time constant the frequency and gain follow the UI with.
+    smoothing: AtomicU32,
+    // Set by the UI, cleared by the callback once the click starts.
+    click: AtomicBool,
+    // The highest output sample since the UI last read it.
+    peak: AtomicU32,
+    // Set by the stream's error callback, cleared when a stream starts.
+    device_lost: AtomicBool,
+    // Whether the limiter is in, and whether it has bent the output since
+    // the UI last checked.
+    limiter: AtomicBool,
+    limiting: AtomicBool,
+}
+
+impl Shared {
+    pub fn new(frequency: f32, gain: f32, pan: f32, smoothing: f32) -> Self {
+        Shared {
+            frequency: AtomicU32::new(frequency.to_bits()),
+            gain: AtomicU32::new(gain.to_bits()),
+            pan: AtomicU32::new(pan.to_bits()),
+            invert: AtomicBool::new(false),
+            smoothing: AtomicU32::new(smoothing.to_bits()),
+            click: AtomicBool::new(false),
+            peak: AtomicU32::new(0),
+            device_lost: AtomicBool::new(false),
+            limiter: AtomicBool::new(true),
+            limiting: AtomicBool::new(false),
+        }
+    }
+
+    pub fn set_frequency(&self, frequency: f32) {
+        self.frequency.store(frequency.to_bits(), Ordering::Relaxed);
+    }
+
+    pub fn frequency(&self) -> f32 {
+        f32::from_bits(self.frequency.load(Ordering::Relaxed))
+    }
+
+    /// Sets the gain as a linear amplitude factor.
+    pub fn set_gain(&self, gain: f32) {
+        self.gain.store(gain.to_bits(), Ordering::Relaxed);
+    }
+
+    pub fn gain(&self) -> f32 {
+        f32::from_bits(self.gain.load(Ordering::Relaxed))
+    }
+
+    /// Sets the stereo position, from -1 for hard left to 1 for hard right.
+    pub fn set_pan(&self, pan: f32) {
+        self.pan.store(pan.to_bits(), Ordering::Relaxed);
+    }
+
+    pub fn pan(&self) -> f32 {
+        f32::from_bits(self.pan.load(Ordering::Relaxed))
+    }
+
+    /// Flips the sine's phase, or puts it back. The click isn't flipped.
+    pub fn set_invert(&self, invert: bool) {
+        self.invert.store(invert, Ordering::Relaxed);
+    }
+
+    pub fn invert(&self) -> bool {
+        self.invert.load(Ordering::Relaxed)
+    }
+
+    /// Sets how long, in seconds, the frequency and gain take to catch up
+    /// with a change. Zero makes them jump.
+    pub fn set_smoothing(&self, seconds: f32) {
+        self.smoothing.store(seconds.to_bits(), Ordering::Relaxed);
+    }
+
+    pub fn smoothing(&self) -> f32 {
+        f32::from_bits(self.smoothing.load(Ordering::Relaxed))
+    }
+
+    /// Plays a short click over the sine, independent of the gain.
+    pub fn click(&self) {
+        self.click.store(true, Ordering::Relaxed);
+    }
+
+    fn take_click(&self) -> bool {
+        self.click.swap(false, Ordering::Relaxed)
+    }
+
+    /// The peak output level since the last call, as a linear amplitude.
+    pub fn take_peak(&self) -> f32 {
+        f32::from_bits(self.peak.swap(0, Ordering::Relaxed))
+    }
+
+    /// Whether the stream has failed since it started, most likely because
+    /// its device was unplugged.
+    pub fn device_lost(&self) -> bool {
+        self.device_lost.load(Ordering::Relaxed)
+    }
+
+    pub fn lose_device(&self) {
+        self.device_lost.store(true, Ordering::Relaxed);
+    }
+
+    /// Puts the output limiter in or bypasses it.
+    pub fn set_limiter(&self, on: bool) {
+        self.limiter.store(on, Ordering::Relaxed);
+    }
+
+    fn limiter(&self) -> bool {
+        self.limiter.load(Ordering::Relaxed)
+    }
+
+    /// Whether the limiter has reduced the output since the last call.
+    pub fn take_limiting(&self) -> bool {
+        self.limiting.swap(false, Ordering::Relaxed)
+    }
+
+    fn raise_peak(&self, peak: f32) {
+        // Non-negative floats order the same as their bits.
+        self.peak.fetch_max(peak.to_bits(), Ordering::Relaxed);
+    }
+}
+
+/// Converts decibels to a linear amplitude factor.
+pub fn db_to_gain(db: f32) -> f32 {
+    10.0_f32.powf(db / 20.0)
+}
+
+/// The inverse of [`db_to_gain`]. Silence is negative infinity.
+pub fn gain_to_db(gain: f32) -> f32 {
+    20.0 * gain.log10()
+}
+
+/// The left and right gains for `pan`, from -1 for hard left to 1 for hard
+/// right, by the constant-power law: their squares always sum to 1, so the
+/// loudness holds as the sound moves across. The center and both ends are
+/// exact rather than whatever the trigonometry rounds to.
+pub fn pan_gains(pan: f32) -> (f32, f32) {
+    if pan <= -1.0 {
+        return (1.0, 0.0);
+    }
+    if pan >= 1.0 {
+        return (0.0, 1.0);
+    }
+    if pan == 0.0 {
+        return (FRAC_1_SQRT_2, FRAC_1_SQRT_2);
+    }
+
+    let angle = (pan + 1.0) * FRAC_PI_4;
+    (angle.cos(), angle.sin())
+}
+
+/// `pan` as a mixing desk shows it: "C" at the center, otherwise the side
+/// and how far over it is out of 100, like "L63" or "R100".
+pub fn pan_label(pan: f32) -> String {
+    let amount = (pan.abs().min(1.0) * 100.0).round();
+
+    if amount == 0.0 {
+        String::from("C")
+    } else if pan < 0.0 {
+        format!("L{amount}")
+    } else {
+        format!("R{amount}")
+    }
+}
+
+/// A running output stream. Dropping it stops the sound.
+pub struct Audio {
+    stream: cpal::Stream,
+    sample_rate: u32,
+}
+
+impl Audio {
+    /// Opens the default output device and starts playing.
+    ///
+    /// The oscillator runs at whatever sample rate the device reports rather
+    /// than assuming one, so it stays in tune on any device. Starting again
+    /// after the device is lost picks up the same frequency and gain from
+    /// `shared` on whichever device is now the default.
+    pub fn start(shared: Arc<Shared>) -> Result<Audio, AudioError> {
+        shared.device_lost.store(false, Ordering::Relaxed);
+
+        let device = cpal::default_host()
+            .default_output_device()
+            .ok_or(AudioError::NoDevice)?;
+
+        let supported = device.default_output_config()?;
+        let sample_format = supported.sample_format();
+        let config = supported.config();
+        let sample_rate = config.sample_rate.0;
+
+        let stream = match sample_format {
+            SampleFormat::F32 => build_stream::<f32>(&device, &config, shared)?,
+            SampleFormat::I16 => build_stream::<i16>(&device, &config, shared)?,
+            SampleFormat::U16 => build_stream::<u16>(&device, &config, shared)?,
+            sample_format => return Err(AudioError::UnsupportedFormat(sample_format)),
+        };
+
+        stream.play()?;
+
+        Ok(Audio {
+            stream,
+            sample_rate,
+        })
+    }
+
+    pub fn sample_rate(&self) -> u32 {
+        self.sample_rate
+    }
+
+    /// Pauses the stream before closing it, so it doesn't end on a click.
+    pub fn stop(self) {
+        let _ = self.stream.pause();
+    }
+}
+
+fn build_stream<T>(
+    device: &cpal::Device,
+    config: &cpal::StreamConfig,
+    shared: Arc<Shared>,
+) -> Result<cpal::Stream, cpal::BuildStreamError>
+where
+    T: SizedSample + FromSample<f32>,
+{
+    let channels = usize::from(config.channels);
+    let sample_rate = config.sample_rate.0 as f32;
+    let mut oscillator = Oscillator::new(sample_rate);
+    let mut click = Click::new(sample_rate);
+    // Frequency is smoothed in octaves, so a sweep moves evenly in pitch.
+    let mut pitch = Smoothed::new(shared.frequency().log2());
+    let mut gain = Smoothed::new(shared.gain());
+    let mut pan = Smoothed::new(shared.pan());
+    let errors = Arc::clone(&shared);
+
+    device.build_output_stream(
+        config,
+        move |data: &mut [T], _| {
+            let target_pitch = shared.frequency().log2();
+            let target_gain = shared.gain();
+            let target_pan = shared.pan();
+            let polarity = if shared.invert() { -1.0 } else { 1.0 };
+
+            let coefficient = smoothing_coefficient(shared.smoothing(), sample_rate);
+
+            if shared.take_click() {
+                click.start();
+            }
+
+            let limiter = shared.limiter();
+            let mut peak = 0.0_f32;
+            let mut limiting = false;
+
+            for frame in data.chunks_mut(channels) {
+                let frequency = pitch.next(target_pitch, coefficient).exp2();
+                let sine = oscillator.next(frequency)
+                    * polarity
+                    * gain.next(target_gain, coefficient)
+                    * LEVEL;
+                let mut sample = sine + click.next();
+                if limiter {
+                    sample *= LIMITER_MAKEUP;
+                    limiting |= sample.abs() > LIMITER_KNEE;
+                    sample = soft_clip(sample);
+                }
+
+                // A mono device gets the sound as it is. Any channels past
+                // the first two are left silent.
+                match frame {
+                    [mono] => {
+                        *mono = T::from_sample(sample);
+                        peak = peak.max(sample.abs());
+                    }
+                    [left, right, rest @ ..] => {
+                        let (left_gain, right_gain) = pan_gains(pan.next(target_pan, coefficient));
+                        *left = T::from_sample(sample * left_gain);
+                        *right = T::from_sample(sample * right_gain);
+                        rest.fill(T::from_sample(0.0));
+                        peak = peak.max(sample.abs() * left_gain.max(right_gain));
+                    }
+                    [] => {}
+                }
+            }
+
+            shared.raise_peak(peak);
+            if limiting {
+                shared.limiting.store(true, Ordering::Relaxed);
+            }
+        },
+        // Any error stops the stream for good, whether or not the backend
+        // reports it as the device going away, so the UI offers to restart.
+        move |error| {
+            eprintln!("audio stream error: {error}");
+            errors.lose_device();
+        },
+        None,
+    )
+}
+
+struct Oscillator {
+    // The position in the current cycle, in turns.
+    phase: f32,
+    sample_rate: f32,
+}
+
+impl Oscillator {
+    fn new(sample_rate: f32) -> Self {
+        Oscillator {
+            phase: 0.0,
+            sample_rate,
+        }
+    }
+
+    fn next(&mut self, frequency: f32) -> f32 {
+        let sample = (self.phase * TAU).sin();
+        self.phase = (self.phase + frequency / self.sample_rate).fract();
+        sample
+    }
+}
+
+// A value that follows its target with a one-pole filter rather than
+// jumping to it, so a change of gain doesn't click and a change of frequency
+// glides.
+struct Smoothed {
+    value: f32,
+}
+
+impl Smoothed {
+    fn new(value: f32) -> Self {
+        Smoothed { value }
+    }
+
+    // `coefficient` is the fraction of the remaining distance covered each
+    // sample, from `smoothing_coefficient`.
+    fn next(&mut self, target: f32, coefficient: f32) -> f32 {
+        self.value += (target - self.value) * coefficient;
+        self.value
+    }
+}
+
+// The one-pole coefficient for a time constant of `seconds`: after that long
+// a value has covered all but 1/e of the way to its target.
+fn smoothing_coefficient(seconds: f32, sample_rate: f32) -> f32 {
+    if seconds <= 0.0 {
+        return 1.0;
+    }
+
+    1.0 - (-1.0 / (sample_rate * seconds)).exp()
+}
+
+// Leaves `sample` alone up to the knee, then bends it along a tanh curve
+// that approaches full scale without reaching it. Continuous in value and
+// slope at the knee, and never decreasing, so it can't fold the waveform.
+fn soft_clip(sample: f32) -> f32 {
+    let level = sample.abs();
+    if level <= LIMITER_KNEE {
+        return sample;
+    }
+
+    let room = 1.0 - LIMITER_KNEE;
+    let bent = LIMITER_KNEE + room * ((level - LIMITER_KNEE) / room).tanh();
+    bent.copysign(sample)
+}
+
+// A decaying impulse, the sound of a detent.
+struct Click {
+    // Samples left until the click has decayed away.
+    remaining: usize,
+    len: usize,
+}
+
+impl Click {
+    fn new(sample_rate: f32) -> Self {
+        Click {
+            remaining: 0,
+            len: (sample_rate * CLICK_SECONDS) as usize,
+        }
+    }
+
+    fn start(&mut self) {
+        self.remaining = self.len;
+    }
+
+    fn next(&mut self) -> f32 {
+        if self.remaining == 0 {
+            return 0.0;
+        }
+
+        let envelope = self.remaining as f32 / self.len as f32;
+        self.remaining -= 1;
+        CLICK_LEVEL * envelope * envelope
+    }
+}
+
+#[derive(Debug)]
+pub enum AudioError {
+    NoDevice,
+    UnsupportedFormat(SampleFormat),
+    Config(cpal::DefaultStreamConfigError),
+    Build(cpal::BuildStreamError),
+    Play(cpal::PlayStreamError),
+}
+
+impl fmt::Display for AudioError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            AudioError::NoDevice => write!(f, "no output device found"),
+            AudioError::UnsupportedFormat(format) => {
+                write!(f, "unsupported sample format {format}")
+            }
+            AudioError::Config(error) => write!(f, "{error}"),
+            AudioError::Build(error) => write!(f, "{error}"),
+            AudioError::Play(error) => write!(f, "{error}"),
+        }
+    }
+}
+
+impl std::error::Error for AudioError {}
+
+impl From<cpal::DefaultStreamConfigError> for AudioError {
+    fn from(error: cpal::DefaultStreamConfigError) -> Self {
+        AudioError::Config(error)
+    }
+}
+
+impl From<cpal::BuildStreamError> for AudioError {
+    fn from(error: cpal::BuildStreamError) -> Self {
+        AudioError::Build(error)
+    }
+}
+
+impl From<cpal::PlayStreamError> for AudioError {
+    fn from(error: cpal::PlayStreamError) -> Self {
+        AudioError::Play(error)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn smoothing_settles_at_time_constant() {
+        let sample_rate = 1000.0;
+        let coefficient = smoothing_coefficient(0.01, sample_rate);
+        let mut value = Smoothed::new(0.0);
+
+        // Ten samples is one time constant.
+        let reached = (0..10)
+            .map(|_| value.next(1.0, coefficient))
+            .last()
+            .unwrap();
+        assert!((reached - (1.0 - (-1.0_f32).exp())).abs() < 1e-3);
+    }
+
+    #[test]
+    fn pan_keeps_constant_power() {
+        assert_eq!(pan_gains(0.0), (FRAC_1_SQRT_2, FRAC_1_SQRT_2));
+        assert_eq!(pan_gains(-1.0), (1.0, 0.0));
+        assert_eq!(pan_gains(1.0), (0.0, 1.0));
+
+        for i in -10..=10 {
+            let (left, right) = pan_gains(i as f32 / 10.0);
+            assert!((left * left + right * right - 1.0).abs() < 1e-6);
+        }
+        let (left, right) = pan_gains(-0.5);
+        assert!(left > right);
+    }
+
+    #[test]
+    fn pan_labels() {
+        assert_eq!(pan_label(0.0), "C");
+        assert_eq!(pan_label(0.004), "C");
+        assert_eq!(pan_label(-0.63), "L63");
+        assert_eq!(pan_label(1.0), "R100");
+    }
+
+    #[test]
+    fn soft_clip_is_monotonic_and_below_full_scale() {
+        let samples: Vec<_> = (-4000..=4000)
+            .map(|i| soft_clip(i as f32 / 1000.0))
+            .collect();
+
+        assert!(samples.windows(2).all(|pair| pair[0] <= pair[1]));
+        assert!(samples.iter().all(|sample| sample.abs() < 1.0));
+        assert_eq!(soft_clip(0.25), 0.25);
+        assert_eq!(soft_clip(-2.0), -soft_clip(2.0));
+    }
+
+    #[test]
+    fn no_smoothing_jumps() {
+        let mut value = Smoothed::new(0.0);
+
+        assert_eq!(value.next(1.0, smoothing_coefficient(0.0, 48000.0)), 1.0);
+    }
+}
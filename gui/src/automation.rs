@@ -0,0 +1,197 @@
+//! Record a parameter's movement over time and loop it back.
+
+use iced_audio::Normal;
+
+use std::fmt::Write;
+use std::time::{Duration, Instant};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum State {
+    Idle,
+    Recording(Instant),
+    Playing(Instant),
+}
+
+/// One parameter's recorded movement.
+///
+/// Recording keeps every value the parameter is set to, stamped with the
+/// time since recording started. Playback loops over them, interpolating
+/// between points.
+#[derive(Debug)]
+pub struct Lane {
+    points: Vec<(Duration, Normal)>,
+    state: State,
+}
+
+impl Default for Lane {
+    fn default() -> Self {
+        Lane {
+            points: Vec::new(),
+            state: State::Idle,
+        }
+    }
+}
+
+impl Lane {
+    pub fn state(&self) -> State {
+        self.state
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.points.is_empty()
+    }
+
+    /// Starts a new recording, replacing the last one. `start` is the
+    /// parameter's value as it begins.
+    pub fn record(&mut self, now: Instant, start: Normal) {
+        self.points = vec![(Duration::ZERO, start)];
+        self.state = State::Recording(now);
+    }
+
+    /// Starts looping the recording, if there is one.
+    pub fn play(&mut self, now: Instant) {
+        if !self.points.is_empty() {
+            self.state = State::Playing(now);
+        }
+    }
+
+    /// Stops recording or playing. A recording stopped here loops from this
+    /// moment, so a pause at the end is kept.
+    pub fn stop(&mut self, now: Instant) {
+        if let (State::Recording(started), Some(&(_, end))) = (self.state, self.points.last()) {
+            self.points.push((now - started, end));
+        }
+
+        self.state = State::Idle;
+    }
+
+    pub fn clear(&mut self) {
+        self.points.clear();
+        self.state = State::Idle;
+    }
+
+    /// Adds a point while recording. Does nothing otherwise.
+    pub fn capture(&mut self, now: Instant, normal: Normal) {
+        if let State::Recording(started) = self.state {
+            self.points.push((now - started, normal));
+        }
+    }
+
+    /// The value to write back while playing, `None` otherwise.
+    pub fn playback(&self, now: Instant) -> Option<Normal> {
+        match self.state {
+            State::Playing(started) => self.sample(now - started),
+            _ => None,
+        }
+    }
+
+    /// The recording as CSV: a header row, then each point's time since
+    /// recording started as `hh:mm:ss.mmm`, its normal, and the value
+    /// `value` maps that to.
+    pub fn to_csv(&self, value: impl Fn(Normal) -> f32) -> String {
+        let mut csv = String::from("time,normal,value\n");
+
+        for &(time, normal) in &self.points {
+            let _ = writeln!(
+                csv,
+                "{},{},{}",
+                timestamp(time),
+                normal.as_f32(),
+                value(normal)
+            );
+        }
+
+        csv
+    }
+
+    // The recording's value `elapsed` into playback, looping around.
+    fn sample(&self, elapsed: Duration) -> Option<Normal> {
+        let &(length, last) = self.points.last()?;
+
+        if length.is_zero() {
+            return Some(last);
+        }
+
+        let at = Duration::from_secs_f64(elapsed.as_secs_f64() % length.as_secs_f64());
+        // The first point after `at`, and the one before it.
+        let next = self.points.partition_point(|&(time, _)| time <= at);
+        let (before_time, before) = self.points[next.saturating_sub(1)];
+        let Some(&(after_time, after)) = self.points.get(next) else {
+            return Some(last);
+        };
+
+        let span = (after_time - before_time).as_secs_f32();
+        let t = (at - before_time).as_secs_f32() / span;
+
+        Some(Normal::from_clipped(
+            before.as_f32() + (after.as_f32() - before.as_f32()) * t,
+        ))
+    }
+}
+
+// `time` as hours, minutes, seconds and milliseconds, like 00:01:02.500.
+fn timestamp(time: Duration) -> String {
+    let seconds = time.as_secs();
+
+    format!(
+        "{:02}:{:02}:{:02}.{:03}",
+        seconds / 3600,
+        seconds / 60 % 60,
+        seconds % 60,
+        time.subsec_millis()
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn lane(points: &[(u64, f32)]) -> Lane {
+        Lane {
+            points: points
+                .iter()
+                .map(|&(millis, normal)| {
+                    (Duration::from_millis(millis), Normal::from_clipped(normal))
+                })
+                .collect(),
+            state: State::Idle,
+        }
+    }
+
+    #[test]
+    fn interpolates_between_points() {
+        let lane = lane(&[(0, 0.0), (1000, 1.0)]);
+        let value = lane.sample(Duration::from_millis(250)).unwrap();
+
+        assert!((value.as_f32() - 0.25).abs() < 1e-4);
+    }
+
+    #[test]
+    fn loops_past_the_end() {
+        let lane = lane(&[(0, 0.0), (1000, 1.0)]);
+        let value = lane.sample(Duration::from_millis(1500)).unwrap();
+
+        assert!((value.as_f32() - 0.5).abs() < 1e-4);
+    }
+
+    #[test]
+    fn csv_has_a_header_and_mapped_values() {
+        let lane = lane(&[(0, 0.0), (62_500, 0.5)]);
+        let csv = lane.to_csv(|normal| normal.as_f32() * 100.0);
+
+        assert_eq!(
+            csv,
+            "time,normal,value\n00:00:00.000,0,0\n00:01:02.500,0.5,50\n"
+        );
+    }
+
+    #[test]
+    fn plays_nothing_when_empty() {
+        let mut lane = Lane::default();
+        let now = Instant::now();
+        lane.play(now);
+
+        assert_eq!(lane.state(), State::Idle);
+        assert_eq!(lane.playback(now), None);
+    }
+}
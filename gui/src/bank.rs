@@ -0,0 +1,170 @@
+//! Banks of presets: directories of preset files, stepped through in order.
+
+use crate::preset::{self, Preset};
+
+use std::fs;
+use std::io;
+use std::path::Path;
+
+/// The presets in one directory, sorted by file name, and the one last
+/// applied.
+#[derive(Debug)]
+pub struct PresetBank {
+    name: String,
+    presets: Vec<(String, Preset)>,
+    index: usize,
+    // How many files looked like presets but couldn't be loaded.
+    skipped: usize,
+}
+
+impl PresetBank {
+    pub fn new(name: String, presets: Vec<(String, Preset)>) -> Self {
+        PresetBank {
+            name,
+            presets,
+            index: 0,
+            skipped: 0,
+        }
+    }
+
+    /// Loads every `.json` and `.bin` preset in `dir`, named after their
+    /// files. One that fails to load is left out rather than failing the
+    /// bank, and counted by [`PresetBank::skipped`].
+    pub fn load(dir: &Path) -> io::Result<PresetBank> {
+        let mut paths: Vec<_> = fs::read_dir(dir)?
+            .filter_map(|entry| entry.ok().map(|entry| entry.path()))
+            .filter(|path| path.is_file() && preset::is_preset(path))
+            .collect();
+        paths.sort();
+
+        let mut skipped = 0;
+        let presets = paths
+            .iter()
+            .filter_map(|path| match Preset::load(path) {
+                Ok(preset) => Some((file_stem(path), preset)),
+                Err(_) => {
+                    skipped += 1;
+                    None
+                }
+            })
+            .collect();
+
+        Ok(PresetBank {
+            skipped,
+            ..PresetBank::new(file_stem(dir), presets)
+        })
+    }
+
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    pub fn skipped(&self) -> usize {
+        self.skipped
+    }
+
+    pub fn len(&self) -> usize {
+        self.presets.len()
+    }
+
+    /// The current preset and its name, `None` if the bank is empty.
+    pub fn current(&self) -> Option<(&str, &Preset)> {
+        self.presets
+            .get(self.index)
+            .map(|(name, preset)| (name.as_str(), preset))
+    }
+
+    /// The position of the current preset, counting from 1, for display.
+    pub fn position(&self) -> usize {
+        self.index + 1
+    }
+
+    /// Moves to the next preset, wrapping to the first after the last.
+    pub fn next(&mut self) -> Option<(&str, &Preset)> {
+        self.step(1)
+    }
+
+    /// Moves to the previous preset, wrapping to the last before the first.
+    pub fn prev(&mut self) -> Option<(&str, &Preset)> {
+        self.step(-1)
+    }
+
+    fn step(&mut self, by: isize) -> Option<(&str, &Preset)> {
+        if self.presets.is_empty() {
+            return None;
+        }
+
+        let len = self.presets.len() as isize;
+        self.index = (self.index as isize + by).rem_euclid(len) as usize;
+        self.current()
+    }
+}
+
+/// The names of the banks under `root`: each subdirectory is one. Sorted,
+/// and empty if `root` doesn't exist.
+pub fn list(root: &Path) -> Vec<String> {
+    let Ok(entries) = fs::read_dir(root) else {
+        return Vec::new();
+    };
+
+    let mut names: Vec<_> = entries
+        .filter_map(|entry| entry.ok().map(|entry| entry.path()))
+        .filter(|path| path.is_dir())
+        .map(|path| file_stem(&path))
+        .collect();
+    names.sort();
+
+    names
+}
+
+fn file_stem(path: &Path) -> String {
+    path.file_stem()
+        .unwrap_or_default()
+        .to_string_lossy()
+        .into_owned()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tests::{temp_dir, test_preset};
+
+    fn preset(knob: f32) -> Preset {
+        Preset {
+            knob,
+            ..test_preset()
+        }
+    }
+
+    #[test]
+    fn steps_wrap_around() {
+        let mut bank = PresetBank::new(
+            "bank".into(),
+            vec![("a".into(), preset(0.1)), ("b".into(), preset(0.2))],
+        );
+
+        assert_eq!(bank.prev().unwrap().0, "b");
+        assert_eq!(bank.next().unwrap().0, "a");
+        assert_eq!(bank.next().unwrap().0, "b");
+        assert_eq!(bank.next().unwrap().0, "a");
+
+        let mut empty = PresetBank::new("empty".into(), Vec::new());
+        assert!(empty.next().is_none());
+    }
+
+    #[test]
+    fn loads_a_directory_in_name_order() {
+        let dir = temp_dir("bank");
+
+        preset(0.2).save(&dir.join("b.bin")).unwrap();
+        preset(0.1).save(&dir.join("a.json")).unwrap();
+        fs::write(dir.join("broken.json"), "{").unwrap();
+        fs::write(dir.join("notes.txt"), "not a preset").unwrap();
+
+        let bank = PresetBank::load(&dir).unwrap();
+
+        assert_eq!(bank.len(), 2);
+        assert_eq!(bank.skipped(), 1);
+        assert_eq!(bank.current().unwrap(), ("a", &preset(0.1)));
+    }
+}
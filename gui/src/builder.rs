@@ -0,0 +1,243 @@
+//! Configures the app before it's run, for the binary or anything embedding
+//! it.
+
+use iced::{window, Application, Settings};
+
+use std::collections::HashMap;
+use std::fmt;
+use std::path::{Path, PathBuf};
+use std::sync::mpsc::Receiver;
+
+use crate::config::{Config, ConfigError, ParamConfig};
+use crate::param::ParamId;
+use crate::preset::{Preset, PresetError};
+use crate::render::{self, RenderError};
+use crate::style::TickStyle;
+use crate::{App, OnChange};
+
+/// The app's ranges, defaults and preset path, set up before [`run`].
+///
+/// Anything left unset is as the binary has it: parameters from
+/// `defaults.toml` if there's a valid one there, and presets saved to
+/// `preset.json`.
+///
+/// ```no_run
+/// use paris_green_gui::{App, ParamConfig, ParamId};
+///
+/// App::builder()
+///     .param(ParamId::KnobFreq, ParamConfig::new(20.0, 2000.0, 220.0))
+///     .preset_path("bass.bin")
+///     .run()
+///     .unwrap();
+/// ```
+///
+/// [`run`]: AppBuilder::run
+#[derive(Default)]
+pub struct AppBuilder {
+    pub(crate) config: Option<Config>,
+    pub(crate) preset_path: Option<PathBuf>,
+    pub(crate) templates: HashMap<ParamId, &'static str>,
+    pub(crate) on_change: Option<OnChange>,
+    pub(crate) external: Option<Receiver<(ParamId, f32)>>,
+    pub(crate) tick_style: Option<TickStyle>,
+    pub(crate) preset: Option<(PathBuf, Preset)>,
+}
+
+impl fmt::Debug for AppBuilder {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("AppBuilder")
+            .field("config", &self.config)
+            .field("preset_path", &self.preset_path)
+            .field("templates", &self.templates)
+            .field("on_change", &self.on_change.as_ref().map(|_| ".."))
+            .field("external", &self.external.as_ref().map(|_| ".."))
+            .field("tick_style", &self.tick_style)
+            .field("preset", &self.preset.as_ref().map(|(path, _)| path))
+            .finish()
+    }
+}
+
+impl AppBuilder {
+    /// Uses `config` for every parameter instead of reading
+    /// `defaults.toml`. An invalid one falls back to the factory defaults,
+    /// as the file does.
+    pub fn config(mut self, config: Config) -> Self {
+        self.config = Some(config);
+        self
+    }
+
+    /// Sets one parameter's range and default. Parameters not set this way
+    /// or by [`AppBuilder::config`] keep the factory defaults, and
+    /// `defaults.toml` is no longer read.
+    ///
+    /// The octave knob follows the frequency knob and has no range of its
+    /// own, so setting it does nothing.
+    pub fn param(mut self, id: ParamId, param: ParamConfig) -> Self {
+        let config = self.config.get_or_insert_with(Config::default);
+        if let Some(section) = config.section_mut(id) {
+            *section = param;
+        }
+        self
+    }
+
+    /// Words one parameter's label with `template` in place of the default
+    /// `"{name}: {value} {unit}"`, for a localized or branded build. Each
+    /// `{name}`, `{value}` and `{unit}` is filled in, and anything else is
+    /// shown as written.
+    ///
+    /// ```no_run
+    /// use paris_green_gui::{App, ParamId};
+    ///
+    /// App::builder()
+    ///     .template(ParamId::KnobFreq, "Fréquence : {value} {unit}")
+    ///     .run()
+    ///     .unwrap();
+    /// ```
+    pub fn template(mut self, id: ParamId, template: &'static str) -> Self {
+        self.templates.insert(id, template);
+        self
+    }
+
+    /// Calls `on_change` with the parameter and its new value whenever one
+    /// changes, however it was changed, so a host can follow the UI without
+    /// polling it. Linked parameters, like the octave knob following the
+    /// frequency knob, report their own changes too.
+    ///
+    /// ```no_run
+    /// use paris_green_gui::App;
+    ///
+    /// App::builder()
+    ///     .on_change(|id, value| println!("{id:?} = {value}"))
+    ///     .run()
+    ///     .unwrap();
+    /// ```
+    pub fn on_change(mut self, on_change: impl Fn(ParamId, f32) + 'static) -> Self {
+        self.on_change = Some(Box::new(on_change));
+        self
+    }
+
+    /// Sets parameters from the values sent on `source`, each a parameter
+    /// and its new value, mapped as [`on_change`](AppBuilder::on_change)
+    /// reports it. This is how a host thread, like one running the DSP,
+    /// drives the UI.
+    ///
+    /// The channel is polled once a frame without blocking, and only the
+    /// latest value for each parameter in a frame is applied. Locked and
+    /// disabled parameters ignore it, as they do OSC.
+    ///
+    /// ```no_run
+    /// use paris_green_gui::{App, ParamId};
+    /// use std::sync::mpsc;
+    /// use std::thread;
+    ///
+    /// let (sender, receiver) = mpsc::channel();
+    /// thread::spawn(move || sender.send((ParamId::KnobFreq, 220.0)));
+    ///
+    /// App::builder().external_source(receiver).run().unwrap();
+    /// ```
+    pub fn external_source(mut self, source: Receiver<(ParamId, f32)>) -> Self {
+        self.external = Some(source);
+        self
+    }
+
+    /// Draws the tick marks on every slider and knob in `ticks` instead of
+    /// the theme's palette, whichever theme is chosen.
+    ///
+    /// ```no_run
+    /// use iced::{Color, Theme};
+    /// use paris_green_gui::{App, TickStyle};
+    ///
+    /// let mut ticks = TickStyle::for_theme(&Theme::Light);
+    /// ticks.tier_2.color = Color::from_rgb(0.8, 0.2, 0.2);
+    ///
+    /// App::builder().tick_style(ticks).run().unwrap();
+    /// ```
+    pub fn tick_style(mut self, ticks: TickStyle) -> Self {
+        self.tick_style = Some(ticks);
+        self
+    }
+
+    /// Where the Save and Load Preset buttons write and read. A path ending
+    /// in `.bin` saves the binary format.
+    pub fn preset_path(mut self, path: impl Into<PathBuf>) -> Self {
+        self.preset_path = Some(path.into());
+        self
+    }
+
+    /// Reads the preset at `path` now, to start the app from once it runs,
+    /// with what's wrong with it if it can't be loaded. The window opens
+    /// with the preset already applied rather than gliding to it.
+    ///
+    /// ```no_run
+    /// use paris_green_gui::App;
+    ///
+    /// match App::builder().load_preset("demo.json") {
+    ///     Ok(builder) => builder.run().unwrap(),
+    ///     Err(error) => eprintln!("Couldn't load demo.json: {error}"),
+    /// }
+    /// ```
+    pub fn load_preset(mut self, path: impl Into<PathBuf>) -> Result<Self, PresetError> {
+        let path = path.into();
+        let preset = Preset::load(&path)?;
+        self.preset = Some((path, preset));
+        Ok(self)
+    }
+
+    /// Checks the parameters set so far make usable ranges, with what's
+    /// wrong with the first that doesn't. [`run`](AppBuilder::run) falls
+    /// back to the factory defaults on an invalid config, so this is the
+    /// way to find out before then.
+    ///
+    /// ```
+    /// use paris_green_gui::{App, ConfigError, ParamConfig, ParamId};
+    ///
+    /// let builder = App::builder().param(ParamId::RampCurve, ParamConfig::new(1.0, 0.0, 0.5));
+    /// assert!(matches!(
+    ///     builder.validate(),
+    ///     Err(ConfigError::Invalid("ramp", _))
+    /// ));
+    /// ```
+    pub fn validate(&self) -> Result<(), ConfigError> {
+        match &self.config {
+            Some(config) => config.validate(),
+            None => Ok(()),
+        }
+    }
+
+    /// Draws the app as it would first open, in a window `width` by
+    /// `height`, to a PNG at `path` instead of running it. Nothing's
+    /// hovered or focused, and no audio or OSC is started.
+    ///
+    /// ```no_run
+    /// use paris_green_gui::App;
+    ///
+    /// App::builder()
+    ///     .load_preset("demo.json")
+    ///     .unwrap()
+    ///     .render(1024, 768, "demo.png")
+    ///     .unwrap();
+    /// ```
+    pub fn render(
+        self,
+        width: u32,
+        height: u32,
+        path: impl AsRef<Path>,
+    ) -> Result<(), RenderError> {
+        render::render(&mut App::from_builder(self), width, height, path.as_ref())
+    }
+
+    /// Opens the window and runs the app until it's closed.
+    pub fn run(self) -> iced::Result {
+        App::run(Settings {
+            window: window::Settings {
+                // Narrower than this and the slider's step labels run
+                // together.
+                min_size: Some((320, 240)),
+                ..window::Settings::default()
+            },
+            // Let the app stop audio before the window goes away.
+            exit_on_close_request: false,
+            ..Settings::with_flags(self)
+        })
+    }
+}
@@ -0,0 +1,505 @@
+//! Factory defaults for the parameters, read from a TOML file at startup.
+//!
+//! Every section is optional, and one left out keeps the built-in bounds and
+//! default:
+//!
+//! ```toml
+//! [knob]
+//! min = 20.0
+//! max = 20480.0
+//! default = 440.0
+//! # Optional: keep the Randomize button within 100 Hz to 2 kHz.
+//! random = [100.0, 2000.0]
+//! # Optional: show whole Hz rather than switching to kHz.
+//! decimals = 0
+//! ```
+
+use serde::Deserialize;
+
+use std::fmt;
+use std::fs;
+use std::io;
+use std::ops::RangeInclusive;
+use std::path::Path;
+
+use crate::param::ParamId;
+
+// More places than an f32 holds are just noise.
+const MAX_DECIMALS: usize = 6;
+
+// The frequencies a frequency range can span. iced_audio's `FreqRange`
+// quietly moves bounds outside them in, which can leave min above max.
+const FREQUENCIES: RangeInclusive<f32> = 20.0..=20480.0;
+
+/// A range's bounds and where its parameter starts, the part of the range
+/// randomizing may pick from, the whole of it if left out, and the decimal
+/// places its value is shown with, the range's own if left out.
+#[derive(Debug, Clone, Copy, PartialEq, Deserialize)]
+pub struct ParamConfig {
+    pub min: f32,
+    pub max: f32,
+    pub default: f32,
+    #[serde(default)]
+    pub random: Option<(f32, f32)>,
+    #[serde(default)]
+    pub decimals: Option<usize>,
+}
+
+impl ParamConfig {
+    pub const fn new(min: f32, max: f32, default: f32) -> Self {
+        ParamConfig {
+            min,
+            max,
+            default,
+            random: None,
+            decimals: None,
+        }
+    }
+
+    /// The Rust expression that builds this, as `Config::default` writes
+    /// them.
+    pub fn to_code(&self) -> String {
+        let new = format!(
+            "ParamConfig::new({:?}, {:?}, {:?})",
+            self.min, self.max, self.default
+        );
+        if self.random.is_none() && self.decimals.is_none() {
+            return new;
+        }
+
+        let mut fields = Vec::new();
+        if let Some((low, high)) = self.random {
+            fields.push(format!("random: Some(({low:?}, {high:?}))"));
+        }
+        if let Some(decimals) = self.decimals {
+            fields.push(format!("decimals: Some({decimals})"));
+        }
+        format!("ParamConfig {{ {}, ..{new} }}", fields.join(", "))
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct Config {
+    pub h_slider: ParamConfig,
+    pub v_slider: ParamConfig,
+    pub knob: ParamConfig,
+    pub knob_mod: ParamConfig,
+    pub xy_pad_x: ParamConfig,
+    pub xy_pad_y: ParamConfig,
+    pub ramp: ParamConfig,
+    pub smoothing: ParamConfig,
+    pub band_low: ParamConfig,
+    pub band_high: ParamConfig,
+}
+
+impl Default for Config {
+    fn default() -> Self {
+        Config {
+            h_slider: ParamConfig::new(0.0, 10.0, 5.0),
+            v_slider: ParamConfig::new(-12.0, 12.0, 0.0),
+            knob: ParamConfig::new(20.0, 20480.0, 1000.0),
+            knob_mod: ParamConfig::new(-1.0, 1.0, 0.0),
+            xy_pad_x: ParamConfig::new(-1.0, 1.0, 0.0),
+            xy_pad_y: ParamConfig::new(-1.0, 1.0, 0.0),
+            // A linear ramp.
+            ramp: ParamConfig::new(0.0, 1.0, 0.5),
+            // Milliseconds.
+            smoothing: ParamConfig::new(0.0, 50.0, 5.0),
+            // Hz, over the same range as the knob.
+            band_low: ParamConfig::new(20.0, 20480.0, 200.0),
+            band_high: ParamConfig::new(20.0, 20480.0, 2000.0),
+        }
+    }
+}
+
+impl Config {
+    /// Reads and validates the config. The caller falls back to
+    /// `Config::default()` on an error.
+    pub fn load(path: &Path) -> Result<Config, ConfigError> {
+        let text = fs::read_to_string(path)?;
+        let config: Config = toml::from_str(&text)?;
+        config.validate()?;
+
+        Ok(config)
+    }
+
+    /// The section configuring `id`. The octave knob follows the frequency
+    /// knob, so it has none.
+    pub fn section_mut(&mut self, id: ParamId) -> Option<&mut ParamConfig> {
+        match id {
+            ParamId::HSliderInt => Some(&mut self.h_slider),
+            ParamId::VSliderDB => Some(&mut self.v_slider),
+            ParamId::KnobFreq => Some(&mut self.knob),
+            ParamId::KnobOctave => None,
+            ParamId::KnobMod => Some(&mut self.knob_mod),
+            ParamId::XYPadX => Some(&mut self.xy_pad_x),
+            ParamId::XYPadY => Some(&mut self.xy_pad_y),
+            ParamId::RampCurve => Some(&mut self.ramp),
+            ParamId::Smoothing => Some(&mut self.smoothing),
+            ParamId::BandLow => Some(&mut self.band_low),
+            ParamId::BandHigh => Some(&mut self.band_high),
+        }
+    }
+
+    /// Checks every section makes a usable range. The ranges would panic on
+    /// some of these, so they're caught here.
+    pub fn validate(&self) -> Result<(), ConfigError> {
+        for (name, param) in self.sections() {
+            let invalid = |reason: String| ConfigError::Invalid(name, reason);
+            let ParamConfig {
+                min,
+                max,
+                default,
+                random,
+                decimals,
+            } = param;
+
+            if !(min.is_finite() && max.is_finite() && default.is_finite()) {
+                return Err(invalid("bounds and default must be finite".into()));
+            }
+            if min >= max {
+                return Err(invalid(format!("min {min} must be below max {max}")));
+            }
+            // Wider than an f32 spans, every normal would come out NaN.
+            if !(max - min).is_finite() {
+                return Err(invalid(format!("{min} to {max} is too wide a range")));
+            }
+            if !(min..=max).contains(&default) {
+                return Err(invalid(format!(
+                    "default {default} is outside {min} to {max}"
+                )));
+            }
+            if let Some((low, high)) = random {
+                if !(min <= low && low < high && high <= max) {
+                    return Err(invalid(format!(
+                        "random range {low} to {high} must be an ascending part of {min} to {max}"
+                    )));
+                }
+            }
+            if decimals.is_some_and(|decimals| decimals > MAX_DECIMALS) {
+                return Err(invalid(format!(
+                    "decimals can't be more than {MAX_DECIMALS}"
+                )));
+            }
+        }
+
+        if self.h_slider.min.fract() != 0.0 || self.h_slider.max.fract() != 0.0 {
+            return Err(ConfigError::Invalid(
+                "h_slider",
+                "bounds must be whole numbers".into(),
+            ));
+        }
+        // `i32::MAX` rounds up to 2^31 as an f32, just out of reach.
+        let steps = i32::MIN as f32..i32::MAX as f32;
+        if !steps.contains(&self.h_slider.min) || !steps.contains(&self.h_slider.max) {
+            return Err(ConfigError::Invalid(
+                "h_slider",
+                "bounds must fit in a 32-bit integer".into(),
+            ));
+        }
+        if self.v_slider.min > 0.0 || self.v_slider.max < 0.0 {
+            return Err(ConfigError::Invalid(
+                "v_slider",
+                "the range must include 0 dB".into(),
+            ));
+        }
+        if self.smoothing.min < 0.0 {
+            return Err(ConfigError::Invalid(
+                "smoothing",
+                "min can't be below 0 ms".into(),
+            ));
+        }
+        for (name, param) in [
+            ("knob", self.knob),
+            ("band_low", self.band_low),
+            ("band_high", self.band_high),
+        ] {
+            if !FREQUENCIES.contains(&param.min) || !FREQUENCIES.contains(&param.max) {
+                return Err(ConfigError::Invalid(
+                    name,
+                    format!(
+                        "bounds must be within {} to {} Hz",
+                        FREQUENCIES.start(),
+                        FREQUENCIES.end()
+                    ),
+                ));
+            }
+        }
+        // Both handles share one track.
+        if (self.band_low.min, self.band_low.max) != (self.band_high.min, self.band_high.max) {
+            return Err(ConfigError::Invalid(
+                "band_high",
+                "bounds must match band_low's".into(),
+            ));
+        }
+        if self.band_low.default > self.band_high.default {
+            return Err(ConfigError::Invalid(
+                "band_high",
+                "default can't be below band_low's".into(),
+            ));
+        }
+
+        Ok(())
+    }
+
+    /// The Rust expression that builds this config, one section to a line,
+    /// for pasting in as new defaults.
+    pub fn to_code(&self) -> String {
+        let lines: Vec<_> = self
+            .sections()
+            .iter()
+            .map(|(name, param)| format!("    {name}: {},\n", param.to_code()))
+            .collect();
+
+        format!("Config {{\n{}}}", lines.concat())
+    }
+
+    // Every section, named as in the file.
+    fn sections(&self) -> [(&'static str, ParamConfig); 10] {
+        [
+            ("h_slider", self.h_slider),
+            ("v_slider", self.v_slider),
+            ("knob", self.knob),
+            ("knob_mod", self.knob_mod),
+            ("xy_pad_x", self.xy_pad_x),
+            ("xy_pad_y", self.xy_pad_y),
+            ("ramp", self.ramp),
+            ("smoothing", self.smoothing),
+            ("band_low", self.band_low),
+            ("band_high", self.band_high),
+        ]
+    }
+}
+
+#[derive(Debug)]
+pub enum ConfigError {
+    Io(io::Error),
+    Toml(toml::de::Error),
+    // The section and what's wrong with it.
+    Invalid(&'static str, String),
+}
+
+impl ConfigError {
+    /// There's no config to load, which isn't worth reporting.
+    pub fn is_missing(&self) -> bool {
+        matches!(self, ConfigError::Io(error) if error.kind() == io::ErrorKind::NotFound)
+    }
+}
+
+impl fmt::Display for ConfigError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ConfigError::Io(error) => write!(f, "{error}"),
+            ConfigError::Toml(error) => write!(f, "invalid config: {error}"),
+            ConfigError::Invalid(section, reason) => write!(f, "invalid [{section}]: {reason}"),
+        }
+    }
+}
+
+impl std::error::Error for ConfigError {}
+
+impl From<io::Error> for ConfigError {
+    fn from(error: io::Error) -> Self {
+        ConfigError::Io(error)
+    }
+}
+
+impl From<toml::de::Error> for ConfigError {
+    fn from(error: toml::de::Error) -> Self {
+        ConfigError::Toml(error)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn missing_sections_keep_defaults() {
+        let config: Config =
+            toml::from_str("[knob]\nmin = 20.0\nmax = 20480.0\ndefault = 440.0\n").unwrap();
+
+        assert_eq!(config.knob.default, 440.0);
+        assert_eq!(config.v_slider, Config::default().v_slider);
+        assert!(config.validate().is_ok());
+    }
+
+    #[test]
+    fn random_range_must_fit() {
+        let config: Config =
+            toml::from_str("[ramp]\nmin = 0.0\nmax = 1.0\ndefault = 0.5\nrandom = [0.2, 0.8]\n")
+                .unwrap();
+        assert_eq!(config.ramp.random, Some((0.2, 0.8)));
+        assert!(config.validate().is_ok());
+
+        let config = Config {
+            ramp: ParamConfig {
+                random: Some((0.5, 2.0)),
+                ..config.ramp
+            },
+            ..config
+        };
+        assert!(matches!(
+            config.validate(),
+            Err(ConfigError::Invalid("ramp", _))
+        ));
+    }
+
+    #[test]
+    fn decimals_are_optional_and_bounded() {
+        let config: Config =
+            toml::from_str("[knob]\nmin = 20.0\nmax = 20480.0\ndefault = 440.0\ndecimals = 0\n")
+                .unwrap();
+        assert_eq!(config.knob.decimals, Some(0));
+        assert_eq!(config.v_slider.decimals, None);
+        assert!(config.validate().is_ok());
+
+        let config = Config {
+            knob: ParamConfig {
+                decimals: Some(12),
+                ..config.knob
+            },
+            ..config
+        };
+        assert!(matches!(
+            config.validate(),
+            Err(ConfigError::Invalid("knob", _))
+        ));
+    }
+
+    #[test]
+    fn band_ends_share_a_range_in_order() {
+        let config = Config {
+            band_low: ParamConfig::new(20.0, 20480.0, 3000.0),
+            ..Config::default()
+        };
+        assert!(matches!(
+            config.validate(),
+            Err(ConfigError::Invalid("band_high", _))
+        ));
+
+        let config = Config {
+            band_high: ParamConfig::new(20.0, 10000.0, 2000.0),
+            ..Config::default()
+        };
+        assert!(matches!(
+            config.validate(),
+            Err(ConfigError::Invalid("band_high", _))
+        ));
+    }
+
+    #[test]
+    fn degenerate_ranges_are_rejected() {
+        let invalid = |section: &'static str, config: Config| {
+            assert!(
+                matches!(config.validate(), Err(ConfigError::Invalid(name, _)) if name == section),
+                "{section} was accepted: {config:?}"
+            );
+        };
+
+        invalid(
+            "ramp",
+            Config {
+                ramp: ParamConfig::new(0.5, 0.5, 0.5),
+                ..Config::default()
+            },
+        );
+        invalid(
+            "xy_pad_x",
+            Config {
+                xy_pad_x: ParamConfig::new(1.0, -1.0, 0.0),
+                ..Config::default()
+            },
+        );
+        invalid(
+            "knob_mod",
+            Config {
+                knob_mod: ParamConfig::new(f32::NAN, 1.0, 0.0),
+                ..Config::default()
+            },
+        );
+        invalid(
+            "smoothing",
+            Config {
+                smoothing: ParamConfig::new(0.0, f32::INFINITY, 5.0),
+                ..Config::default()
+            },
+        );
+        invalid(
+            "xy_pad_y",
+            Config {
+                xy_pad_y: ParamConfig::new(f32::MIN, f32::MAX, 0.0),
+                ..Config::default()
+            },
+        );
+        invalid(
+            "v_slider",
+            Config {
+                v_slider: ParamConfig::new(6.0, 12.0, 6.0),
+                ..Config::default()
+            },
+        );
+        invalid(
+            "knob",
+            Config {
+                knob: ParamConfig::new(0.0, 20480.0, 440.0),
+                ..Config::default()
+            },
+        );
+        invalid(
+            "knob",
+            Config {
+                knob: ParamConfig::new(25000.0, 30000.0, 25000.0),
+                ..Config::default()
+            },
+        );
+        invalid(
+            "band_low",
+            Config {
+                band_low: ParamConfig::new(5.0, 10.0, 5.0),
+                band_high: ParamConfig::new(5.0, 10.0, 10.0),
+                ..Config::default()
+            },
+        );
+        invalid(
+            "h_slider",
+            Config {
+                h_slider: ParamConfig::new(0.0, 1e10, 5.0),
+                ..Config::default()
+            },
+        );
+    }
+
+    #[test]
+    fn code_builds_each_section() {
+        let config = Config {
+            knob: ParamConfig {
+                random: Some((100.0, 2000.0)),
+                ..ParamConfig::new(20.0, 20480.0, 440.0)
+            },
+            ..Config::default()
+        };
+        let code = config.to_code();
+
+        assert!(code.starts_with("Config {\n    h_slider: ParamConfig::new(0.0, 10.0, 5.0),\n"));
+        assert!(code.contains(
+            "\n    knob: ParamConfig { random: Some((100.0, 2000.0)), \
+             ..ParamConfig::new(20.0, 20480.0, 440.0) },\n"
+        ));
+        assert!(code.ends_with("band_high: ParamConfig::new(20.0, 20480.0, 2000.0),\n}"));
+    }
+
+    #[test]
+    fn default_outside_range_is_rejected() {
+        let config = Config {
+            knob: ParamConfig::new(20.0, 20480.0, 30000.0),
+            ..Config::default()
+        };
+
+        assert!(matches!(
+            config.validate(),
+            Err(ConfigError::Invalid("knob", _))
+        ));
+    }
+}
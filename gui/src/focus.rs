@@ -0,0 +1,113 @@
+//! Keyboard focus across the parameter widgets.
+
+use crate::param::ParamId;
+
+/// The parameter widgets, in the order Tab moves focus through them.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Focus {
+    HSlider,
+    VSlider,
+    Knob,
+    ModRange,
+    XYPad,
+    Band,
+    Ramp,
+}
+
+impl Focus {
+    pub const ALL: [Focus; 7] = [
+        Focus::HSlider,
+        Focus::VSlider,
+        Focus::Knob,
+        Focus::ModRange,
+        Focus::XYPad,
+        Focus::Band,
+        Focus::Ramp,
+    ];
+
+    /// The first widget after `focus` in Tab order that `enabled` accepts,
+    /// wrapping from the last back to the first. With nothing focused yet,
+    /// the search starts from the first. `None` if every widget is disabled.
+    pub fn next(focus: Option<Focus>, enabled: impl Fn(Focus) -> bool) -> Option<Focus> {
+        Focus::step(focus, 1, enabled)
+    }
+
+    /// The widget before `focus`, the inverse of [`Focus::next`].
+    pub fn previous(focus: Option<Focus>, enabled: impl Fn(Focus) -> bool) -> Option<Focus> {
+        Focus::step(focus, -1, enabled)
+    }
+
+    fn step(focus: Option<Focus>, offset: isize, enabled: impl Fn(Focus) -> bool) -> Option<Focus> {
+        let len = Focus::ALL.len() as isize;
+        let index = match focus {
+            Some(focus) => Focus::ALL.iter().position(|&other| other == focus).unwrap() as isize,
+            None if offset > 0 => -1,
+            None => len,
+        };
+
+        (1..=len)
+            .map(|steps| Focus::ALL[(index + offset * steps).rem_euclid(len) as usize])
+            .find(|&focus| enabled(focus))
+    }
+
+    /// The parameter the arrow keys nudge. The XY pad's vertical axis is a
+    /// separate parameter, and the band's top is nudged with Up and Down.
+    pub fn param(self, vertical: bool) -> ParamId {
+        match self {
+            Focus::HSlider => ParamId::HSliderInt,
+            Focus::VSlider => ParamId::VSliderDB,
+            Focus::Knob => ParamId::KnobFreq,
+            Focus::ModRange => ParamId::KnobMod,
+            Focus::XYPad if vertical => ParamId::XYPadY,
+            Focus::XYPad => ParamId::XYPadX,
+            Focus::Band if vertical => ParamId::BandHigh,
+            Focus::Band => ParamId::BandLow,
+            Focus::Ramp => ParamId::RampCurve,
+        }
+    }
+
+    /// Every parameter the widget controls.
+    pub fn params(self) -> &'static [ParamId] {
+        match self {
+            Focus::HSlider => &[ParamId::HSliderInt],
+            Focus::VSlider => &[ParamId::VSliderDB],
+            Focus::Knob => &[ParamId::KnobFreq],
+            Focus::ModRange => &[ParamId::KnobMod],
+            Focus::XYPad => &[ParamId::XYPadX, ParamId::XYPadY],
+            Focus::Band => &[ParamId::BandLow, ParamId::BandHigh],
+            Focus::Ramp => &[ParamId::RampCurve],
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn all(_: Focus) -> bool {
+        true
+    }
+
+    #[test]
+    fn tab_wraps_around() {
+        assert_eq!(Focus::next(None, all), Some(Focus::HSlider));
+        assert_eq!(Focus::next(Some(Focus::Ramp), all), Some(Focus::HSlider));
+        assert_eq!(Focus::previous(None, all), Some(Focus::Ramp));
+        assert_eq!(
+            Focus::previous(Some(Focus::HSlider), all),
+            Some(Focus::Ramp)
+        );
+    }
+
+    #[test]
+    fn tab_skips_disabled_widgets() {
+        let enabled = |focus| focus != Focus::ModRange;
+
+        assert_eq!(Focus::next(Some(Focus::Knob), enabled), Some(Focus::XYPad));
+        assert_eq!(
+            Focus::previous(Some(Focus::XYPad), enabled),
+            Some(Focus::Knob)
+        );
+        assert_eq!(Focus::next(None, |_| false), None);
+    }
+}
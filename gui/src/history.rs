@@ -0,0 +1,130 @@
+//! Undo and redo for parameter changes.
+
+use iced_audio::Normal;
+
+use crate::param::ParamId;
+
+// Older entries are dropped past this many.
+const MAX_ENTRIES: usize = 100;
+
+// The values some parameters had before a change, restored together.
+type Entry = Vec<(ParamId, Normal)>;
+
+/// The undo and redo stacks.
+///
+/// Changes made between [`History::begin`] and [`History::end`], such as a
+/// whole drag of a widget, form a single entry. Any other change is an entry
+/// of its own.
+#[derive(Debug, Default)]
+pub struct History {
+    undo: Vec<Entry>,
+    redo: Vec<Entry>,
+    // The entry for the gesture in progress.
+    open: Option<Entry>,
+}
+
+impl History {
+    pub fn begin(&mut self) {
+        self.end();
+        self.open = Some(Entry::new());
+    }
+
+    pub fn end(&mut self) {
+        if let Some(entry) = self.open.take() {
+            self.push_undo(entry);
+        }
+    }
+
+    pub fn is_open(&self) -> bool {
+        self.open.is_some()
+    }
+
+    /// Records the value `id` had before a change. A new change clears the
+    /// redo stack.
+    pub fn record(&mut self, id: ParamId, before: Normal) {
+        self.redo.clear();
+
+        match &mut self.open {
+            // Only the value from before the gesture started matters.
+            Some(entry) => {
+                if !entry.iter().any(|&(other, _)| other == id) {
+                    entry.push((id, before));
+                }
+            }
+            None => self.push_undo(vec![(id, before)]),
+        }
+    }
+
+    /// Pops the last entry for the caller to restore. The caller pushes the
+    /// values it replaces with [`History::push_redo`], so it can be redone.
+    pub fn undo(&mut self) -> Option<Entry> {
+        self.end();
+        self.undo.pop()
+    }
+
+    /// The inverse of [`History::undo`], paired with [`History::push_undo`].
+    pub fn redo(&mut self) -> Option<Entry> {
+        self.end();
+        self.redo.pop()
+    }
+
+    pub fn push_redo(&mut self, entry: Entry) {
+        self.redo.push(entry);
+    }
+
+    /// Pushes an entry without clearing the redo stack.
+    pub fn push_undo(&mut self, entry: Entry) {
+        if entry.is_empty() {
+            return;
+        }
+
+        self.undo.push(entry);
+
+        if self.undo.len() > MAX_ENTRIES {
+            self.undo.remove(0);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn gesture_is_one_entry() {
+        let mut history = History::default();
+
+        history.begin();
+        history.record(ParamId::KnobFreq, Normal::from_clipped(0.1));
+        history.record(ParamId::KnobFreq, Normal::from_clipped(0.2));
+        history.end();
+
+        let entry = history.undo().unwrap();
+        assert_eq!(entry, vec![(ParamId::KnobFreq, Normal::from_clipped(0.1))]);
+        assert!(history.undo().is_none());
+    }
+
+    #[test]
+    fn undo_then_redo() {
+        let mut history = History::default();
+
+        history.record(ParamId::KnobFreq, Normal::from_clipped(0.1));
+        history.undo().unwrap();
+        history.push_redo(vec![(ParamId::KnobFreq, Normal::from_clipped(0.3))]);
+
+        let entry = history.redo().unwrap();
+        assert_eq!(entry, vec![(ParamId::KnobFreq, Normal::from_clipped(0.3))]);
+    }
+
+    #[test]
+    fn new_change_clears_redo() {
+        let mut history = History::default();
+
+        history.record(ParamId::HSliderInt, Normal::MIN);
+        history.undo();
+        history.push_redo(vec![(ParamId::HSliderInt, Normal::MAX)]);
+        history.record(ParamId::VSliderDB, Normal::MIN);
+
+        assert!(history.redo().is_none());
+    }
+}
@@ -0,0 +1,215 @@
+//! A low-frequency oscillator that parameters can be modulated by.
+
+use iced_audio::Normal;
+use serde::{Deserialize, Serialize};
+
+use std::f32::consts::TAU;
+use std::fmt;
+use std::time::Instant;
+
+/// The rate a new LFO runs at, in Hz, and how far it swings a parameter
+/// either side, as a normal.
+pub const DEFAULT_RATE: f32 = 1.0;
+pub const DEFAULT_DEPTH: f32 = 0.1;
+
+/// The rates the rate knob spans.
+pub const RATES: RateRange = RateRange::new(0.05, 20.0);
+
+/// The LFO's waveform. Every shape starts at 0 on its way up, and they're
+/// all read off the same phase, so switching mid-cycle carries on from the
+/// same point of it.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub enum Shape {
+    #[default]
+    Sine,
+    Triangle,
+    Square,
+    Saw,
+}
+
+impl Shape {
+    pub const ALL: [Shape; 4] = [Shape::Sine, Shape::Triangle, Shape::Square, Shape::Saw];
+
+    // The wave at `phase`, from 0 up to 1 through a cycle.
+    fn at(self, phase: f32) -> f32 {
+        match self {
+            Shape::Sine => (phase * TAU).sin(),
+            Shape::Triangle => 1.0 - 4.0 * ((phase + 0.25).fract() - 0.5).abs(),
+            Shape::Square if phase < 0.5 => 1.0,
+            Shape::Square => -1.0,
+            Shape::Saw => (phase + 0.5).fract() * 2.0 - 1.0,
+        }
+    }
+}
+
+impl fmt::Display for Shape {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            Shape::Sine => "Sine",
+            Shape::Triangle => "Triangle",
+            Shape::Square => "Square",
+            Shape::Saw => "Saw",
+        })
+    }
+}
+
+/// Rates from `min` to `max` Hz, spread evenly by ratio across the knob as
+/// frequencies are, but reaching well below the audible ones a
+/// [`FreqRange`](iced_audio::FreqRange) stops at.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct RateRange {
+    min: f32,
+    max: f32,
+}
+
+impl RateRange {
+    pub const fn new(min: f32, max: f32) -> Self {
+        RateRange { min, max }
+    }
+
+    pub fn min(&self) -> f32 {
+        self.min
+    }
+
+    pub fn max(&self) -> f32 {
+        self.max
+    }
+
+    pub fn unmap_to_value(&self, normal: Normal) -> f32 {
+        self.min * (self.max / self.min).powf(normal.as_f32())
+    }
+
+    /// The inverse of `unmap_to_value`. Rates outside the range are clamped.
+    pub fn map_to_normal(&self, rate: f32) -> Normal {
+        Normal::from_clipped((rate / self.min).ln() / (self.max / self.min).ln())
+    }
+
+    /// `rate` brought inside the range.
+    pub fn clamp(&self, rate: f32) -> f32 {
+        rate.clamp(self.min, self.max)
+    }
+}
+
+/// A wave from -1 to 1 at `rate` cycles a second, stepped along by the time
+/// between ticks so changing the rate or shape never jumps its phase.
+#[derive(Debug)]
+pub struct Lfo {
+    rate: f32,
+    shape: Shape,
+    // How far through a cycle it is, from 0 up to 1.
+    phase: f32,
+    // When it was last stepped, or `None` until it first is.
+    stepped: Option<Instant>,
+}
+
+impl Lfo {
+    pub fn new(rate: f32) -> Self {
+        Lfo {
+            rate,
+            shape: Shape::default(),
+            phase: 0.0,
+            stepped: None,
+        }
+    }
+
+    pub fn rate(&self) -> f32 {
+        self.rate
+    }
+
+    pub fn set_rate(&mut self, rate: f32) {
+        self.rate = rate;
+    }
+
+    pub fn shape(&self) -> Shape {
+        self.shape
+    }
+
+    pub fn set_shape(&mut self, shape: Shape) {
+        self.shape = shape;
+    }
+
+    /// Moves the wave on to `now`. The first step only starts the clock.
+    pub fn step(&mut self, now: Instant) {
+        if let Some(stepped) = self.stepped {
+            let elapsed = now.saturating_duration_since(stepped).as_secs_f32();
+            self.phase = (self.phase + elapsed * self.rate).fract();
+        }
+        self.stepped = Some(now);
+    }
+
+    /// Forgets when it was last stepped, so the time it's been stopped for
+    /// isn't counted when it next is.
+    pub fn pause(&mut self) {
+        self.stepped = None;
+    }
+
+    /// Where the wave is, from -1 to 1.
+    pub fn value(&self) -> f32 {
+        self.shape.at(self.phase)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use std::time::Duration;
+
+    fn assert_close(a: f32, b: f32) {
+        assert!((a - b).abs() < 1e-5, "{a} isn't {b}");
+    }
+
+    #[test]
+    fn cycles_at_its_rate() {
+        let mut lfo = Lfo::new(2.0);
+        let start = Instant::now();
+
+        lfo.step(start);
+        assert_close(lfo.value(), 0.0);
+        // A quarter of a cycle at 2 Hz is an eighth of a second.
+        lfo.step(start + Duration::from_millis(125));
+        assert_close(lfo.value(), 1.0);
+        lfo.step(start + Duration::from_millis(375));
+        assert_close(lfo.value(), -1.0);
+
+        // Paused, the time away doesn't count.
+        lfo.pause();
+        lfo.step(start + Duration::from_secs(10));
+        assert_close(lfo.value(), -1.0);
+    }
+
+    #[test]
+    fn shapes_share_the_phase() {
+        for (phase, values) in [
+            (0.0, [0.0, 0.0, 1.0, 0.0]),
+            (0.25, [1.0, 1.0, 1.0, 0.5]),
+            (0.5, [0.0, 0.0, -1.0, -1.0]),
+            (0.75, [-1.0, -1.0, -1.0, -0.5]),
+        ] {
+            for (shape, value) in Shape::ALL.into_iter().zip(values) {
+                assert_close(shape.at(phase), value);
+            }
+        }
+
+        // Switching shape mid-cycle picks the new one up at the same point.
+        let mut lfo = Lfo::new(1.0);
+        let start = Instant::now();
+        lfo.step(start);
+        lfo.step(start + Duration::from_millis(125));
+        lfo.set_shape(Shape::Triangle);
+        assert_close(lfo.value(), 0.5);
+        lfo.step(start + Duration::from_millis(250));
+        assert_close(lfo.value(), 1.0);
+    }
+
+    #[test]
+    fn rates_spread_by_ratio() {
+        let range = RateRange::new(0.1, 10.0);
+
+        assert_close(range.unmap_to_value(Normal::MIN), 0.1);
+        assert_close(range.unmap_to_value(Normal::CENTER), 1.0);
+        assert_close(range.unmap_to_value(Normal::MAX), 10.0);
+        assert_close(range.map_to_normal(1.0).as_f32(), 0.5);
+        assert_eq!(range.map_to_normal(100.0), Normal::MAX);
+    }
+}
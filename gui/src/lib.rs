@@ -0,0 +1,4660 @@
+//! A demo of iced_audio's parameter widgets, driving a test tone.
+//!
+//! [`App::builder`] sets the app up and runs it. `main.rs` does only that,
+//! so another binary or a plugin host can embed the same app.
+
+// Import iced modules.
+use iced::widget::tooltip::Position;
+use iced::widget::{
+    button, column, container, pick_list, row, scrollable, slider, text, text_input, tooltip,
+    Column, Row,
+};
+use iced::{
+    clipboard, event, executor, keyboard, subscription, theme, window, Alignment, Application,
+    Command, Element, Length, Point, Rectangle, Subscription, Theme, Vector,
+};
+// Import iced_audio modules.
+use iced_audio::{ramp::RampDirection, HSlider, Knob, ModRangeInput, Ramp, VSlider, XYPad};
+use iced_audio::{
+    style::xy_pad, text_marks, tick_marks, FloatRange, FreqRange, IntRange, LogDBRange,
+    ModulationRange, Normal, NormalParam,
+};
+
+use rand::{Rng, SeedableRng};
+use rand_chacha::ChaCha8Rng;
+
+use std::collections::HashMap;
+use std::fs;
+use std::ops::RangeInclusive;
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
+
+mod acceleration;
+mod audio;
+mod automation;
+mod bank;
+mod builder;
+mod config;
+mod focus;
+mod history;
+mod lfo;
+mod log;
+mod marks;
+mod midi;
+mod morph;
+mod osc;
+mod param;
+mod pending;
+mod prefs;
+mod preset;
+mod render;
+mod shortcuts;
+mod source;
+mod stats;
+mod style;
+#[cfg(test)]
+mod tests;
+mod throttle;
+mod tuning;
+mod widget;
+
+use acceleration::Acceleration;
+use audio::Audio;
+use automation::Lane;
+use bank::PresetBank;
+use focus::Focus;
+use history::History;
+use lfo::{Lfo, Shape, RATES};
+use log::Log;
+use midi::{Binding, CcMode};
+use morph::Morph;
+use param::{
+    center_detent, clamp_magnitude, deadzone, format_amount, format_value, nearest_detent,
+    smooth_toward, snap_to_grid, track_click, AmplitudeRange, FreqSpan, ModSource, Param, Range,
+};
+use pending::Pending;
+use prefs::Prefs;
+use preset::{Preset, PRESET_VERSION};
+use stats::Stats;
+use throttle::Throttle;
+use widget::{
+    Dimmed, Disabled, DragAxis, Ghost, Grid, Handle, Interactive, Locked, Meter, Orientation,
+    Popup, RangeSlider, ReadOnly,
+};
+
+pub use builder::AppBuilder;
+pub use config::{Config, ConfigError, ParamConfig};
+pub use marks::TickMarks;
+pub use param::ParamId;
+pub use preset::PresetError;
+pub use render::RenderError;
+pub use style::{TickStyle, TickTier};
+
+// The message when a parameter widget is moved by the user
+#[derive(Debug, Clone)]
+pub enum Message {
+    SliderChanged(f32),
+    ButtonClicked(u8),
+    //
+    HSliderInt(Normal),
+    // Steps the horizontal slider's integer by whole steps, from its
+    // stepper buttons.
+    IntStep(i32),
+    // Changes the top of the horizontal slider's integer range.
+    HSliderMax(i32),
+    // Narrows or widens the frequency knob's range.
+    FreqSpan(FreqSpan),
+    VSliderDB(Normal),
+    KnobFreq(Normal),
+    KnobOctave(Normal),
+    Smoothing(Normal),
+    KnobModChanged(Normal),
+    // Turns the knob's modulation on or off, disabling its depth.
+    ToggleModulation,
+    // The tab of the XY pad moved, then its x and y.
+    XYPadFloat(usize, Normal, Normal),
+    // Shows and edits another of the XY pads.
+    SelectXYPad(usize),
+    // Limits the XY pad to a circle rather than its whole square.
+    ToggleXYCircle,
+    // The grid drawn over the XY pad, snapping to it, and how many cells
+    // it has across.
+    ToggleXYGrid,
+    ToggleXYSnap,
+    XYGridSize(u16),
+    // How lazily the XY pad follows the pointer. 0 follows it exactly.
+    XYSmoothingEdited(String),
+    XYSmoothingSubmitted,
+    // How wide the XY pad's deadzone around center is, from 0 for none to
+    // below 1.
+    XYDeadzoneEdited(String),
+    XYDeadzoneSubmitted,
+    RampChanged(Normal),
+    ToggleRampDirection,
+    // The band's bottom and top, each held on its side of the other.
+    RangeLow(Normal),
+    RangeHigh(Normal),
+    // Switches the vertical slider between dB and a tapered amplitude.
+    ToggleGainTaper,
+    // Shrinks the parameter widgets and drops their text marks.
+    ToggleCompact,
+    // Flips the sign of the frequency sent out, and the sine's phase.
+    TogglePhaseInvert,
+    // Shows each value's normal beside it, for debugging range mappings.
+    ToggleShowRaw,
+    // Snapping the frequency knob to notes, and the pitch A4 is tuned to.
+    ToggleNoteSnap,
+    // Whether crossing a step of the horizontal slider clicks.
+    ToggleDetentClick,
+    ToggleLimiter,
+    // Redraws while the detent flash fades, reads the output meter, plays
+    // back automation and applies values MIDI and OSC sent since the last.
+    Tick(Instant),
+    ReferenceEdited(String),
+    ReferenceSubmitted,
+    // Typing a note name, like "C#4", to set the frequency knob to, and the
+    // key that jumps to its box.
+    NoteEdited(String),
+    NoteSubmitted,
+    FocusNoteInput,
+    // The normals the frequency knob snaps to when dragged near one, as a
+    // comma-separated list, and how near it has to come.
+    KnobDetentsEdited(String),
+    KnobDetentsSubmitted,
+    KnobMagnetismEdited(String),
+    KnobMagnetismSubmitted,
+    // The LFO's rate from its knob, and typed in Hz, its shape, and how far
+    // it swings the parameters it modulates.
+    LfoRate(Normal),
+    LfoRateEdited(String),
+    LfoRateSubmitted,
+    LfoDepthEdited(String),
+    LfoDepthSubmitted,
+    LfoShape(Shape),
+    // The frequency knob's automation lane.
+    RecordAutomation,
+    PlayAutomation,
+    StopAutomation,
+    ClearAutomation,
+    ExportAutomation,
+    // Sent when a parameter widget is double-clicked.
+    Reset(ParamId),
+    // The change in normal space from scrolling over the knob.
+    KnobScroll(f32),
+    // Typing into and submitting a parameter's value box.
+    ValueEdited(ParamId, String),
+    ValueSubmitted(ParamId),
+    SavePreset,
+    LoadPreset,
+    // Picks a bank of presets and steps through it.
+    SelectBank(String),
+    NextPreset,
+    PrevPreset,
+    // Swaps the live parameters with the other A/B snapshot, or copies A
+    // over B.
+    SwapCompare,
+    CopyAToB,
+    // Copies the current values as Rust code for new defaults.
+    CopyAsCode,
+    ToggleTheme,
+    ToggleDragTooltips,
+    // Sets which way dragging turns the knobs.
+    KnobDrag(DragAxis),
+    KnobTravelEdited(String),
+    KnobTravelSubmitted,
+    ToggleFineMode,
+    // Sets every parameter to a safe value at once, for live use.
+    Panic,
+    // Sets every parameter back to its default in one undo step.
+    ResetAll,
+    // Sets every parameter to a random value from the seed, and editing the
+    // seed used next.
+    Randomize,
+    SeedEdited(String),
+    SeedSubmitted,
+    MorphTimeEdited(String),
+    MorphTimeSubmitted,
+    OscIntervalEdited(String),
+    OscIntervalSubmitted,
+    // Turns solo mode on or off, and editing how long it highlights for.
+    ToggleSolo,
+    SoloIdleEdited(String),
+    SoloIdleSubmitted,
+    // Turns clearing the status line when idle on or off, and editing how
+    // long it waits.
+    ToggleStatusClears,
+    StatusTimeoutEdited(String),
+    StatusTimeoutSubmitted,
+    // A control change from a MIDI controller: the controller number and its
+    // value in 0..=127.
+    MidiCc(u8, midi::Control),
+    // A note played on a MIDI keyboard, which sets the frequency knob.
+    MidiNote(u8),
+    MidiUnavailable(String),
+    // A parameter's mapped value received over OSC.
+    OscParam(ParamId, f32),
+    OscUnavailable(String),
+    // A parameter's mapped value sent by the embedding program, and its
+    // source closing.
+    ExternalParam(ParamId, f32),
+    ExternalClosed,
+    ToggleMidiLearn,
+    // Switches the last touched parameter's MIDI binding between absolute
+    // and relative.
+    ToggleCcMode,
+    // Shows or hides the list of keyboard shortcuts.
+    ToggleHelp,
+    CloseHelp,
+    ClearLog,
+    // Shows or hides each parameter's usage this session, and starts
+    // counting it afresh.
+    ToggleStats,
+    ResetStats,
+    ReconnectAudio,
+    // Sent when the user grabs and releases a parameter widget. Several can
+    // be held at once on a touchscreen.
+    Grabbed(ParamId),
+    Released(ParamId),
+    // Where a click on a slider's track would set it, as the cursor hovers
+    // over it. `None` once the cursor leaves, or is over the handle.
+    Hover(ParamId, Option<Normal>),
+    Undo,
+    Redo,
+    // Right-clicking a parameter widget opens its context menu, offering
+    // these.
+    OpenMenu(Focus),
+    CloseMenu,
+    MenuReset(Focus),
+    MenuCopy(Focus),
+    ToggleLock(Focus),
+    ToggleLfo(Focus),
+    MenuPaste(Focus),
+    // Tab and Shift+Tab move keyboard focus between the parameter widgets.
+    FocusNext,
+    FocusPrevious,
+    // Steps the focused parameter by this many steps, from the arrow keys.
+    // Up and Down nudge vertically, which matters for the XY pad.
+    Nudge(i32),
+    NudgeVertical(i32),
+    // The window's new width and height.
+    WindowResized(u32, u32),
+    CloseRequested,
+    // A file dragged over the window, dropped on it, or dragged away again.
+    FileHovered(PathBuf),
+    FileDropped(PathBuf),
+    FilesHoveredLeft,
+    // Takes up or turns down the state auto-saved last time.
+    RestoreScratch,
+    DiscardScratch,
+}
+
+// Where the Save/Load Preset buttons read and write.
+const PRESET_PATH: &str = "preset.json";
+
+// Where the automation lane is exported to.
+const AUTOMATION_CSV_PATH: &str = "automation.csv";
+
+// Each directory in here is a bank of presets.
+const BANKS_PATH: &str = "banks";
+
+// How tall the log panel is before it scrolls.
+const LOG_PANEL_HEIGHT: f32 = 120.0;
+
+// Where the factory defaults for the parameters are read from.
+const CONFIG_PATH: &str = "defaults.toml";
+
+// Where the chosen theme is remembered between runs.
+const PREFS_PATH: &str = "prefs.json";
+
+// Where the state is saved once it's been left alone for `AUTOSAVE_IDLE`,
+// to offer back on the next launch if nothing was saved after it.
+const SCRATCH_PATH: &str = "scratch.json";
+const AUTOSAVE_IDLE: Duration = Duration::from_secs(5);
+
+// What the status line goes back to once it's been idle a while, if
+// `prefs.status_clears` is on.
+const IDLE_STATUS: &str = "Try anything";
+
+// How far one line of scrolling moves the knob, and how much holding Shift
+// multiplies that by.
+const KNOB_SCROLL_STEP: f32 = 0.01;
+const KNOB_SCROLL_COARSE: f32 = 10.0;
+
+// The pitch of A4 that note snapping starts from.
+const DEFAULT_REFERENCE_PITCH: f32 = 440.0;
+
+// How near a drag has to bring the frequency knob to a detent to snap to
+// it, as a normal.
+const DEFAULT_KNOB_MAGNETISM: f32 = 0.02;
+
+// How often the app ticks, and how often while MIDI or OSC values are
+// waiting: about once a frame, so a flood of them redraws no faster than
+// the screen does.
+const TICK_INTERVAL: Duration = Duration::from_millis(30);
+const FRAME_INTERVAL: Duration = Duration::from_millis(16);
+
+// How long the horizontal slider flashes after crossing a step.
+const DETENT_FLASH: Duration = Duration::from_millis(120);
+
+// How far one arrow key press moves a continuous parameter, and how many
+// steps Page Up/Down jumps by.
+const NUDGE_STEP: f32 = 0.01;
+const NUDGE_PAGE: i32 = 10;
+
+// The tops offered for the horizontal slider's range, besides the one in the
+// config.
+const H_SLIDER_MAXIMA: [i32; 4] = [5, 10, 20, 50];
+
+// The spans the frequency knob can be switched to, besides the configured
+// one, in Hz: the bass, a sub control, and the bass left out.
+const FREQ_SPANS: [FreqSpan; 3] = [
+    FreqSpan(20.0, 2000.0),
+    FreqSpan(20.0, 200.0),
+    FreqSpan(200.0, 20480.0),
+];
+
+// How many dB apart the vertical slider's ticks are, and its labels.
+const DB_TICK_STEP: f32 = 3.0;
+const DB_LABEL_STEP: f32 = 6.0;
+
+// The XY pad's grid resolutions to pick from, in cells across and down.
+const XY_GRID_SIZES: [u16; 4] = [4, 8, 12, 16];
+const XY_GRID_SIZE: u16 = 8;
+
+// How many XY pads there are, each under its own tab.
+const XY_PAD_COUNT: usize = 4;
+
+// Compact mode's knobs, as a share of their usual size, and its sliders'
+// thickness and the length of those that would otherwise fill.
+const COMPACT_KNOB_SCALE: f32 = 0.6;
+const COMPACT_SLIDER_THICKNESS: f32 = 10.0;
+const COMPACT_SLIDER_LENGTH: f32 = 120.0;
+
+// How large solo mode shows the value last moved.
+const SOLO_TEXT_SIZE: f32 = 32.0;
+
+// Window widths the layout changes at. From `WIDE_WIDTH` up the controls and
+// the parameter widgets sit side by side. Below `NARROW_WIDTH` the knobs are
+// as small as they go and the horizontal slider drops labels so the rest
+// don't collide.
+const WIDE_WIDTH: u32 = 720;
+const NARROW_WIDTH: u32 = 400;
+
+// The most labels the horizontal slider shows, at each width.
+const STEP_LABELS: usize = 10;
+const STEP_LABELS_NARROW: usize = 5;
+
+// How far the drag tooltip sits from the cursor, right and down.
+const DRAG_TOOLTIP_OFFSET: Vector = Vector::new(16.0, 16.0);
+
+// The knobs are sized to a fraction of the window's smaller side, within
+// these bounds.
+const KNOB_SIZE_MIN: f32 = 24.0;
+const KNOB_SIZE_MAX: f32 = 48.0;
+const KNOB_SIZE_FRACTION: f32 = 1.0 / 16.0;
+
+// Knobs smaller than this drop their minor tick marks. The switch happens a
+// few pixels either side of it depending on the direction, so resizing the
+// window around the threshold doesn't flicker between the two.
+const KNOB_DENSE_MARKS_SIZE: f32 = 36.0;
+const KNOB_MARKS_HYSTERESIS: f32 = 3.0;
+
+// The frequencies the knobs mark.
+const KNOB_DECADES: [f32; 3] = [100.0, 1000.0, 10000.0];
+const KNOB_DECADE_STEPS: [f32; 5] = [50.0, 200.0, 500.0, 2000.0, 5000.0];
+
+// Holding Ctrl while dragging any parameter widget, or turning on fine mode,
+// moves it at a tenth of the usual rate, for fine adjustments.
+const FINE_DRAG_KEYS: keyboard::Modifiers = keyboard::Modifiers::CTRL;
+const FINE_DRAG_SCALAR: f32 = 0.1;
+
+// Dragging the frequency knob faster than the whole range a second covers
+// more ground, so fast throws go further while slow moves stay precise.
+const KNOB_ACCELERATION: Acceleration = Acceleration {
+    threshold: 1.0,
+    gain: 0.5,
+    exponent: 1.5,
+};
+
+// How far above the frequency knob the octave knob is linked.
+const OCTAVE_RATIO: f32 = 2.0;
+
+// How fast the output meter falls once the level drops. It rises at once.
+const METER_DECAY_DB_PER_SECOND: f32 = 20.0;
+
+// How long the limiting indicator stays lit after the limiter last acted,
+// so brief peaks are still visible.
+const LIMITING_HOLD: Duration = Duration::from_millis(300);
+
+// The loudest the vertical slider goes in dB, short of the top of its range.
+const GAIN_LIMIT_DB: f32 = 6.0;
+
+// How strongly randomizing favours quiet gains. A uniform sample is raised
+// to this, so half of all results land in the bottom quarter of travel.
+const RANDOM_GAIN_BIAS: f32 = 2.0;
+
+/// What [`AppBuilder::on_change`] calls with each changed parameter and its
+/// new value.
+pub type OnChange = Box<dyn Fn(ParamId, f32)>;
+
+// The power the vertical slider's amplitude taper raises its travel to.
+const AMPLITUDE_TAPER: f32 = 2.0;
+
+pub struct App {
+    slider_value: f32, //0 ..=1
+    button_id: u8,
+    /////
+    // Every parameter's widget state, range and display name, and the
+    // config they were built from.
+    params: HashMap<ParamId, Param>,
+    config: Config,
+    // During a drag of the frequency knob: when its widget last reported,
+    // the normal it reported, to measure how fast it's moving, and where
+    // the throw has taken the knob before any note snapping.
+    knob_throw: Option<(Instant, Normal, f32)>,
+    // The ring around the knob showing its modulation depth.
+    knob_mod_range: ModulationRange,
+    // Every XY pad's position, by tab. The one showing is the x and y
+    // parameters, and its entry here is only brought up to date when
+    // another tab is picked.
+    xy_pads: Vec<(Normal, Normal)>,
+    xy_pad_tab: usize,
+    // When on, the XY pad is a vector whose length is at most 1.
+    xy_circle: bool,
+    // Whether the grid shows over the XY pad, and whether drags snap to it.
+    // Snapping works with the grid hidden too.
+    xy_grid: bool,
+    xy_snap: bool,
+    xy_grid_size: u16,
+    // How much of the way to the pointer the XY pad stays behind at each
+    // step, from 0 for none to below 1, and while it's catching up, the
+    // position it's heading for.
+    xy_smoothing: f32,
+    xy_target: Option<(Normal, Normal)>,
+    xy_smoothing_draft: Option<String>,
+    // How far from center the XY pad reads as centered, as a fraction of
+    // the way to each edge.
+    xy_deadzone: f32,
+    xy_deadzone_draft: Option<String>,
+    ramp_direction: RampDirection,
+    // The horizontal slider's integer after its last change, and when it
+    // last crossed a step.
+    last_step: i32,
+    detent_flash: Option<Instant>,
+    detent_click: bool,
+    // Whether the vertical slider is a tapered amplitude rather than dB.
+    amplitude_taper: bool,
+    // Whether the frequency goes out negated and the sine phase-flipped.
+    // Like the taper it's saved in presets but isn't a parameter, so
+    // randomizing and undo leave it alone.
+    phase_invert: bool,
+    // The output meter's linear level after ballistics, the range it's
+    // drawn in, and when it was last read.
+    meter_level: f32,
+    meter_range: LogDBRange,
+    meter_read: Instant,
+    // Whether the output limiter is in, and when it last reduced the
+    // output, for the indicator by the meter.
+    limiter: bool,
+    limiting: Option<Instant>,
+    // When on, the knob snaps to the nearest equal-tempered note.
+    note_snap: bool,
+    reference_pitch: f32,
+    // The reference pitch typed but not yet submitted, like `value_drafts`.
+    reference_draft: Option<String>,
+    // A note name typed but not yet submitted. Without one, the box shows
+    // the note nearest the knob.
+    note_draft: Option<String>,
+    // The normals dragging the frequency knob snaps to, within
+    // `knob_magnetism` of one, and those typed but not yet submitted.
+    knob_detents: Vec<Normal>,
+    knob_magnetism: f32,
+    knob_detents_draft: Option<String>,
+    knob_magnetism_draft: Option<String>,
+    // The seed the next randomize uses, and one typed but not yet submitted.
+    random_seed: u64,
+    seed_draft: Option<String>,
+    morph_time_draft: Option<String>,
+    // While on, the parameter last moved is highlighted and the rest dimmed,
+    // until it's been left alone for `prefs.solo_idle`.
+    solo: bool,
+    soloed: Option<(ParamId, Instant)>,
+    solo_idle_draft: Option<String>,
+    knob_travel_draft: Option<String>,
+    // When the status line last changed, while it's waiting to be cleared,
+    // and whether it has been since.
+    status_since: Option<Instant>,
+    status_idle: bool,
+    status_timeout_draft: Option<String>,
+    // Recorded movement of the frequency knob.
+    automation: Lane,
+
+    // A group of tick marks with their size and position.
+    db_tick_marks: tick_marks::Group,
+    db_text_marks: text_marks::Group,
+    h_slider_tick_marks: tick_marks::Group,
+    h_slider_text_marks: text_marks::Group,
+    h_slider_max: i32,
+    // The frequency knob's bounds, which the octave knob shares.
+    freq_span: FreqSpan,
+    // The knobs' marks: decades only for small knobs, with the steps in
+    // between for larger ones.
+    knob_marks_sparse: tick_marks::Group,
+    knob_marks_dense: tick_marks::Group,
+    knob_size: f32,
+    window_width: u32,
+    knob_marks_are_dense: bool,
+    // Whether the log and tooltips show the normals behind the values.
+    show_raw: bool,
+    // Whether the parameters are drawn small and close together, with
+    // their values in tooltips rather than text marks.
+    compact: bool,
+    // How each parameter has been used this session, and whether that's
+    // shown.
+    stats: Stats,
+    stats_open: bool,
+    // What modulated parameters follow, how far it swings them, and the
+    // rate and depth typed but not yet submitted.
+    lfo: Lfo,
+    lfo_depth: f32,
+    lfo_rate_draft: Option<String>,
+    lfo_depth_draft: Option<String>,
+    // What the app has done, shown in the log panel, and the status set by
+    // the current `update` that hasn't been logged yet.
+    log: Log,
+    status: Option<String>,
+
+    // Text typed into a value box that hasn't been submitted yet. Boxes
+    // without a draft show the parameter's current value.
+    value_drafts: HashMap<ParamId, String>,
+    preset_path: PathBuf,
+    // A file being dragged over the window, to drop and load as a preset.
+    file_hovered: Option<PathBuf>,
+    // Where the state is auto-saved, if anywhere, when it last changed
+    // without being saved since, and the auto-saved state offered back at
+    // startup.
+    scratch_path: Option<PathBuf>,
+    edited: Option<Instant>,
+    scratch: Option<Preset>,
+    // The preset last saved or loaded, to show which parameters have
+    // drifted from it since.
+    saved: Option<Preset>,
+    // The banks found under `BANKS_PATH`, and the one selected.
+    banks: Vec<String>,
+    bank: Option<PresetBank>,
+    // The live parameters are the A or B snapshot, and this is the other.
+    // `None` until the slots first differ, standing for a copy of the live
+    // ones.
+    compare_b: bool,
+    compare_other: Option<Preset>,
+    // The light or dark theme, as saved in the preferences.
+    theme: Theme,
+    prefs: Prefs,
+
+    // Which parameter each MIDI CC number controls.
+    midi_bindings: HashMap<u8, Binding>,
+    // While learning, the next CC received is bound to `last_touched`.
+    midi_learn: bool,
+    last_touched: Option<ParamId>,
+    // Values from MIDI and OSC waiting for the next tick to apply them.
+    // Widgets and the keyboard set values straight away.
+    pending: Pending,
+    // A preset load still gliding to its values.
+    morph: Option<Morph>,
+    // Whether the keyboard shortcuts cover the window.
+    help_open: bool,
+    // Whether every drag is fine, as if Ctrl were held.
+    fine_mode: bool,
+    // The parameter the arrow keys nudge, moved with Tab.
+    focused: Option<Focus>,
+    history: History,
+    // The widgets being dragged, in the order they were grabbed.
+    drags: Vec<ParamId>,
+    // The value a click where the cursor hovers would set, shown but not
+    // applied.
+    hover: Option<(ParamId, Normal)>,
+    // The widget whose context menu is open, and the values last copied
+    // from one.
+    context_menu: Option<Focus>,
+    copied: Vec<(ParamId, Normal)>,
+    // Sends every parameter change over OSC. `None` if no socket could be
+    // opened.
+    osc: Option<osc::Sender>,
+    // Holds back OSC values sent too close together, and the interval
+    // typed but not yet submitted.
+    osc_throttle: Throttle,
+    osc_interval_draft: Option<String>,
+    // The embedding program's callback for every parameter change, and the
+    // channel it sends values in on, polled once a frame.
+    on_change: Option<OnChange>,
+    external: Option<Arc<Mutex<source::Source>>>,
+    // Overrides the theme's tick mark colors and sizes.
+    tick_style: Option<TickStyle>,
+
+    // The knob sets the oscillator's frequency and the vertical slider its
+    // gain. `audio` is `None` if no output stream could be opened.
+    audio: Option<Audio>,
+    audio_shared: Arc<audio::Shared>,
+    // The output stream has failed and is waiting to be reconnected.
+    device_lost: bool,
+}
+
+impl Application for App {
+    type Executor = executor::Default;
+    type Message = Message;
+    type Theme = Theme;
+    type Flags = AppBuilder;
+
+    fn new(flags: AppBuilder) -> (App, Command<Message>) {
+        let mut app = App::from_builder(flags);
+
+        let output_text = match Audio::start(Arc::clone(&app.audio_shared)) {
+            Ok(audio) => {
+                let text = format!("Audio running at {} Hz, try anything", audio.sample_rate());
+                app.audio = Some(audio);
+                text
+            }
+            Err(error) => format!("Audio unavailable: {error}"),
+        };
+
+        let output_text = match osc::Sender::new(app.prefs.osc_target) {
+            Ok(sender) => {
+                app.osc = Some(sender);
+                output_text
+            }
+            Err(error) => format!("OSC unavailable: {error}"),
+        };
+        app.log.push(output_text, false);
+
+        (app, Command::none())
+    }
+
+    fn title(&self) -> String {
+        "Simple Example - Iced Audio".to_string()
+    }
+
+    fn theme(&self) -> Theme {
+        self.theme.clone()
+    }
+
+    fn update(&mut self, event: Message) -> Command<Message> {
+        // A disabled or locked parameter's widget can't send anything, but a
+        // menu or value box might still try.
+        if self.is_ignored(&event) {
+            return Command::none();
+        }
+
+        if self.solo {
+            if let Some(id) = self.moved_param(&event) {
+                self.soloed = Some((id, Instant::now()));
+            }
+        }
+
+        match event {
+            Message::ButtonClicked(id) => {
+                self.set_status(format!("Button Clicked: {id}"));
+            }
+            Message::SliderChanged(value) => {
+                self.slider_value = value;
+                self.set_status(format!("Slider Changed: {value}"));
+            } //
+            // Retrieve the value by mapping the normalized value of the parameter
+            // to the corresponding range.
+            //
+            // Now do something useful with that value!
+            Message::HSliderInt(normal) => self.set_param(ParamId::HSliderInt, normal),
+            Message::IntStep(steps) => {
+                if let Some(normal) = self.int_stepped(steps) {
+                    self.set_param(ParamId::HSliderInt, normal);
+                }
+            }
+            Message::VSliderDB(normal) => self.set_param(ParamId::VSliderDB, normal),
+            Message::KnobFreq(normal) => self.throw_knob(normal),
+            Message::KnobOctave(normal) => self.set_param(ParamId::KnobOctave, normal),
+            Message::Smoothing(normal) => self.set_param(ParamId::Smoothing, normal),
+            // Dragging a bipolar widget sticks at center for a moment.
+            Message::KnobModChanged(normal) => {
+                let normal = self.center_detent(ParamId::KnobMod, normal);
+                let centered = self.centered_status(&[(ParamId::KnobMod, normal)]);
+
+                self.set_param(ParamId::KnobMod, normal);
+                if let Some(status) = centered {
+                    self.set_status(status);
+                }
+            }
+            Message::ToggleModulation => {
+                let depth = self.param_mut(ParamId::KnobMod);
+                depth.disabled = !depth.disabled;
+                let disabled = depth.disabled;
+
+                if disabled {
+                    self.value_drafts.remove(&ParamId::KnobMod);
+                    if self.focused == Some(Focus::ModRange) {
+                        self.focused = None;
+                    }
+                    if self.context_menu == Some(Focus::ModRange) {
+                        self.context_menu = None;
+                    }
+                }
+                self.set_status(if disabled {
+                    "Modulation off"
+                } else {
+                    "Modulation on"
+                });
+            }
+            // A pad that's no longer showing keeps its place for when it's
+            // picked again.
+            Message::XYPadFloat(tab, normal_x, normal_y) if tab != self.xy_pad_tab => {
+                self.xy_pads[tab] = (normal_x, normal_y);
+            }
+            Message::XYPadFloat(_, normal_x, normal_y) => {
+                let (normal_x, normal_y) = if self.xy_snap {
+                    (
+                        snap_to_grid(normal_x, self.xy_grid_size),
+                        snap_to_grid(normal_y, self.xy_grid_size),
+                    )
+                } else {
+                    (normal_x, normal_y)
+                };
+                let normal_x = deadzone(normal_x, self.xy_deadzone);
+                let normal_y = deadzone(normal_y, self.xy_deadzone);
+                let normal_x = self.center_detent(ParamId::XYPadX, normal_x);
+                let normal_y = self.center_detent(ParamId::XYPadY, normal_y);
+
+                if self.xy_smoothing > 0.0 {
+                    self.xy_target = Some(self.circled(normal_x, normal_y));
+                    self.step_xy_smoothing();
+                } else {
+                    self.move_xy_pad(normal_x, normal_y);
+                }
+            }
+            Message::SelectXYPad(tab) => self.select_xy_pad(tab),
+            Message::ToggleXYCircle => {
+                self.xy_circle = !self.xy_circle;
+
+                // Pull the current position inside straight away.
+                let x = self.param(ParamId::XYPadX).normal_param.value;
+                let y = self.param(ParamId::XYPadY).normal_param.value;
+                self.set_xy_pad(x, y);
+            }
+            Message::ToggleXYGrid => self.xy_grid = !self.xy_grid,
+            Message::ToggleXYSnap => {
+                self.xy_snap = !self.xy_snap;
+                self.set_status(if self.xy_snap {
+                    "XY pad snaps to the grid"
+                } else {
+                    "XY pad moves freely"
+                });
+            }
+            Message::XYGridSize(size) => self.xy_grid_size = size,
+            Message::XYSmoothingEdited(text) => self.xy_smoothing_draft = Some(text),
+            Message::XYSmoothingSubmitted => {
+                let Some(text) = self.xy_smoothing_draft.take() else {
+                    return Command::none();
+                };
+
+                match text.trim().parse::<f32>() {
+                    Ok(factor) if (0.0..1.0).contains(&factor) => {
+                        self.xy_smoothing = factor;
+                        if factor == 0.0 {
+                            self.settle_xy_smoothing();
+                        }
+                        self.set_status(if factor == 0.0 {
+                            "XY pad follows the pointer exactly".into()
+                        } else {
+                            format!("XY pad smoothing {factor}")
+                        });
+                    }
+                    _ => self.set_status(format!(
+                        "Invalid XY smoothing: {text:?} (from 0 up to but not including 1)"
+                    )),
+                }
+            }
+            Message::XYDeadzoneEdited(text) => self.xy_deadzone_draft = Some(text),
+            Message::XYDeadzoneSubmitted => {
+                let Some(text) = self.xy_deadzone_draft.take() else {
+                    return Command::none();
+                };
+
+                match text.trim().parse::<f32>() {
+                    Ok(size) if (0.0..1.0).contains(&size) => {
+                        self.xy_deadzone = size;
+                        self.set_status(if size == 0.0 {
+                            "XY pad has no deadzone".into()
+                        } else {
+                            format!("XY pad deadzone {size}")
+                        });
+                    }
+                    _ => self.set_status(format!(
+                        "Invalid XY deadzone: {text:?} (from 0 up to but not including 1)"
+                    )),
+                }
+            }
+            Message::RampChanged(normal) => self.set_param(ParamId::RampCurve, normal),
+            Message::RangeLow(normal) => self.set_param(ParamId::BandLow, normal),
+            Message::RangeHigh(normal) => self.set_param(ParamId::BandHigh, normal),
+            Message::ToggleRampDirection => {
+                self.ramp_direction = match self.ramp_direction {
+                    RampDirection::Up => RampDirection::Down,
+                    RampDirection::Down => RampDirection::Up,
+                };
+            }
+            Message::ToggleGainTaper => self.set_gain_taper(!self.amplitude_taper),
+            Message::TogglePhaseInvert => self.set_phase_invert(!self.phase_invert),
+            Message::ToggleCompact => self.compact = !self.compact,
+            Message::ToggleShowRaw => {
+                self.show_raw = !self.show_raw;
+                self.set_status(if self.show_raw {
+                    "Showing raw normals"
+                } else {
+                    "Hiding raw normals"
+                });
+            }
+            Message::ToggleNoteSnap => {
+                self.note_snap = !self.note_snap;
+
+                // Snap the current value straight away.
+                let knob = self.param(ParamId::KnobFreq).normal_param.value;
+                self.set_param(ParamId::KnobFreq, knob);
+                self.set_status(self.describe(ParamId::KnobFreq));
+            }
+            Message::HSliderMax(max) => self.set_h_slider_max(max),
+            Message::FreqSpan(span) => self.set_freq_span(span),
+            Message::ToggleDetentClick => {
+                self.detent_click = !self.detent_click;
+            }
+            Message::ToggleLimiter => {
+                self.limiter = !self.limiter;
+                self.audio_shared.set_limiter(self.limiter);
+                self.set_status(if self.limiter {
+                    "Output limiter on"
+                } else {
+                    "Output limiter bypassed"
+                });
+            }
+            Message::Tick(now) => {
+                if let Some(start) = self.detent_flash {
+                    if now.duration_since(start) >= DETENT_FLASH {
+                        self.detent_flash = None;
+                    }
+                }
+
+                if let Some(since) = self.status_since {
+                    let timeout = Duration::from_millis(self.prefs.status_timeout);
+                    if !self.prefs.status_clears {
+                        self.status_since = None;
+                    } else if now.duration_since(since) >= timeout {
+                        self.status_since = None;
+                        self.status_idle = true;
+                    }
+                }
+
+                if let Some((_, moved)) = self.soloed {
+                    let idle = Duration::from_millis(self.prefs.solo_idle);
+                    if self.prefs.solo_idle > 0 && now.duration_since(moved) >= idle {
+                        self.soloed = None;
+                    }
+                }
+
+                self.read_meter(now);
+
+                if !self.device_lost && self.audio_shared.device_lost() {
+                    self.device_lost = true;
+                    self.set_status("Audio device lost: press Reconnect to use the default device");
+                }
+
+                if let Some(normal) = self.automation.playback(now) {
+                    self.play_automation(normal);
+                }
+
+                self.step_morph(now);
+                self.step_xy_smoothing();
+
+                let modulated = self.modulated();
+                if modulated.is_empty() {
+                    self.lfo.pause();
+                } else {
+                    self.lfo.step(now);
+                    for id in modulated {
+                        self.output(id);
+                    }
+                }
+
+                if self
+                    .edited
+                    .is_some_and(|edited| now.duration_since(edited) >= AUTOSAVE_IDLE)
+                {
+                    self.autosave();
+                }
+
+                for (id, value) in self.osc_throttle.due(now) {
+                    if let Some(osc) = &self.osc {
+                        osc.send(id, value);
+                    }
+                }
+
+                let pending = self.pending.take();
+                if !pending.is_empty() {
+                    self.set_params(&pending);
+                }
+            }
+            Message::ReferenceEdited(text) => self.reference_draft = Some(text),
+            Message::ReferenceSubmitted => {
+                let Some(text) = self.reference_draft.take() else {
+                    return Command::none();
+                };
+
+                match text.trim().parse::<f32>() {
+                    Ok(pitch) if pitch.is_finite() && pitch > 0.0 => {
+                        self.reference_pitch = pitch;
+
+                        let knob = self.param(ParamId::KnobFreq).normal_param.value;
+                        self.set_param(ParamId::KnobFreq, knob);
+                        self.set_status(self.describe(ParamId::KnobFreq));
+                    }
+                    _ => self.set_status(format!("Invalid reference pitch: {text:?}")),
+                }
+            }
+            Message::KnobDetentsEdited(text) => self.knob_detents_draft = Some(text),
+            Message::KnobDetentsSubmitted => {
+                let Some(text) = self.knob_detents_draft.take() else {
+                    return Command::none();
+                };
+
+                match parse_detents(&text) {
+                    Some(detents) => {
+                        self.knob_detents = detents;
+                        self.set_status(match self.knob_detents.len() {
+                            0 => String::from("Frequency knob has no detents"),
+                            1 => String::from("Frequency knob has 1 detent"),
+                            n => format!("Frequency knob has {n} detents"),
+                        });
+                    }
+                    None => self.set_status(format!(
+                        "Invalid detents: {text:?} (normals from 0 to 1, separated by commas)"
+                    )),
+                }
+            }
+            Message::KnobMagnetismEdited(text) => self.knob_magnetism_draft = Some(text),
+            Message::KnobMagnetismSubmitted => {
+                let Some(text) = self.knob_magnetism_draft.take() else {
+                    return Command::none();
+                };
+
+                match text.trim().parse::<f32>() {
+                    Ok(radius) if (0.0..=0.5).contains(&radius) => {
+                        self.knob_magnetism = radius;
+                        self.set_status(format!("Detents pull within {radius}"));
+                    }
+                    _ => {
+                        self.set_status(format!("Invalid detent radius: {text:?} (from 0 to 0.5)"))
+                    }
+                }
+            }
+            Message::LfoRate(normal) => {
+                self.lfo_rate_draft = None;
+                self.lfo.set_rate(RATES.unmap_to_value(normal));
+                self.set_status(format!("LFO runs at {:.2} Hz", self.lfo.rate()));
+            }
+            Message::LfoRateEdited(text) => self.lfo_rate_draft = Some(text),
+            Message::LfoRateSubmitted => {
+                let Some(text) = self.lfo_rate_draft.take() else {
+                    return Command::none();
+                };
+
+                match text.trim().parse::<f32>() {
+                    Ok(rate) if (RATES.min()..=RATES.max()).contains(&rate) => {
+                        self.lfo.set_rate(rate);
+                        self.set_status(format!("LFO runs at {rate} Hz"));
+                    }
+                    _ => self.set_status(format!(
+                        "Invalid LFO rate: {text:?} (from {} to {} Hz)",
+                        RATES.min(),
+                        RATES.max()
+                    )),
+                }
+            }
+            Message::LfoDepthEdited(text) => self.lfo_depth_draft = Some(text),
+            Message::LfoDepthSubmitted => {
+                let Some(text) = self.lfo_depth_draft.take() else {
+                    return Command::none();
+                };
+
+                match text.trim().parse::<f32>() {
+                    Ok(depth) if (0.0..=1.0).contains(&depth) => {
+                        self.set_lfo_depth(depth);
+                        self.set_status(format!("LFO swings parameters by {depth}"));
+                    }
+                    _ => self.set_status(format!("Invalid LFO depth: {text:?} (from 0 to 1)")),
+                }
+            }
+            Message::LfoShape(shape) => {
+                self.lfo.set_shape(shape);
+                self.set_status(format!("LFO shape {shape}"));
+            }
+            Message::NoteEdited(text) => self.note_draft = Some(text),
+            Message::NoteSubmitted => {
+                let Some(text) = self.note_draft.take() else {
+                    return Command::none();
+                };
+
+                match tuning::parse_note(&text) {
+                    Some(note) => self.set_note(note),
+                    None => self.set_status(format!("Invalid note: {text:?}")),
+                }
+            }
+            Message::FocusNoteInput => return text_input::focus(note_input_id()),
+            Message::RecordAutomation => {
+                let knob = self.param(ParamId::KnobFreq).normal_param.value;
+                self.automation.record(Instant::now(), knob);
+                self.set_status("Recording the knob: move it, then press Stop");
+            }
+            Message::PlayAutomation => {
+                self.automation.play(Instant::now());
+                self.set_status("Playing the knob's automation");
+            }
+            Message::StopAutomation => {
+                self.automation.stop(Instant::now());
+                self.set_status("Automation stopped");
+            }
+            Message::ClearAutomation => {
+                self.automation.clear();
+                self.set_status("Automation cleared");
+            }
+            Message::ExportAutomation => {
+                // Exported in Hz, not just normals.
+                let range = self.param(ParamId::KnobFreq).range;
+                let status = if self.automation.is_empty() {
+                    "Nothing recorded to export".into()
+                } else {
+                    let csv = self
+                        .automation
+                        .to_csv(|normal| range.unmap_to_value(normal));
+                    match fs::write(AUTOMATION_CSV_PATH, csv) {
+                        Ok(()) => format!("Exported automation to {AUTOMATION_CSV_PATH}"),
+                        Err(error) => format!("Couldn't export automation: {error}"),
+                    }
+                };
+                self.set_status(status);
+            }
+            Message::Hover(id, normal) => {
+                self.hover = normal.map(|normal| (id, self.constrained(id, normal)));
+            }
+            Message::Grabbed(id) => {
+                self.last_touched = Some(id);
+                self.hover = None;
+                if id == ParamId::KnobFreq {
+                    let knob = self.param(id).normal_param.value;
+                    self.knob_throw = Some((Instant::now(), knob, knob.as_f32()));
+                }
+                // Undo the whole drag at once, not each step of it. Drags
+                // that overlap undo together.
+                if self.drags.is_empty() {
+                    self.history.begin();
+                }
+                if !self.drags.contains(&id) {
+                    self.drags.push(id);
+                }
+            }
+            Message::Released(id) => {
+                if id == ParamId::KnobFreq {
+                    self.knob_throw = None;
+                }
+                if id == ParamId::XYPadX {
+                    self.settle_xy_smoothing();
+                }
+                self.drags.retain(|&other| other != id);
+                // The value a drag ends on always goes out.
+                self.flush_osc();
+                if self.drags.is_empty() {
+                    self.history.end();
+                    self.log.end_gesture();
+                }
+            }
+            Message::Undo => {
+                if let Some(entry) = self.history.undo() {
+                    let redo = self.current_values(&entry);
+                    self.history.push_redo(redo);
+                    self.restore(&entry, "Undo");
+                }
+            }
+            Message::Redo => {
+                if let Some(entry) = self.history.redo() {
+                    let undo = self.current_values(&entry);
+                    self.history.push_undo(undo);
+                    self.restore(&entry, "Redo");
+                }
+            }
+            Message::OpenMenu(focus) => self.context_menu = Some(focus),
+            Message::CloseMenu => self.context_menu = None,
+            Message::MenuReset(focus) => {
+                self.context_menu = None;
+
+                let defaults: Vec<_> = focus
+                    .params()
+                    .iter()
+                    .map(|&id| (id, self.param(id).normal_param.default))
+                    .collect();
+                self.set_params(&defaults);
+            }
+            Message::MenuCopy(focus) => {
+                self.context_menu = None;
+                self.copied = focus
+                    .params()
+                    .iter()
+                    .map(|&id| (id, self.param(id).normal_param.value))
+                    .collect();
+                self.set_status(format!("Copied {}", self.describe(focus.param(false))));
+            }
+            Message::ToggleLock(focus) => {
+                self.context_menu = None;
+
+                let locked = !self.is_locked(focus.params());
+                for &id in focus.params() {
+                    self.param_mut(id).locked = locked;
+                }
+
+                let name = self.param(focus.param(false)).name;
+                self.set_status(if locked {
+                    format!("Locked {name}: type a value to change it")
+                } else {
+                    format!("Unlocked {name}")
+                });
+            }
+            Message::ToggleLfo(focus) => {
+                self.context_menu = None;
+
+                let source = if self.is_modulated(focus.params()) {
+                    None
+                } else {
+                    Some(ModSource::Lfo {
+                        depth: self.lfo_depth,
+                    })
+                };
+                // Stopping sends the value it's set to again, so nothing's
+                // left where the LFO last had it.
+                for &id in focus.params() {
+                    self.param_mut(id).mod_source = source;
+                    self.output(id);
+                }
+
+                let name = self.param(focus.param(false)).name;
+                self.set_status(if source.is_some() {
+                    format!("Modulating {name} from the LFO")
+                } else {
+                    format!("Stopped modulating {name}")
+                });
+            }
+            Message::MenuPaste(focus) => {
+                self.context_menu = None;
+
+                let pasted: Vec<_> = focus
+                    .params()
+                    .iter()
+                    .zip(&self.copied)
+                    .map(|(&id, &(source, normal))| (id, self.pasted(source, normal, id)))
+                    .collect();
+                self.set_params(&pasted);
+            }
+            Message::FocusNext => {
+                self.focused = Focus::next(self.focused, |focus| !self.is_disabled(focus.params()));
+            }
+            Message::FocusPrevious => {
+                self.focused =
+                    Focus::previous(self.focused, |focus| !self.is_disabled(focus.params()));
+            }
+            Message::Nudge(steps) => self.nudge_focused(steps, false),
+            Message::NudgeVertical(steps) => self.nudge_focused(steps, true),
+            Message::KnobScroll(delta) => {
+                self.last_touched = Some(ParamId::KnobFreq);
+
+                // `from_clipped` keeps the nudged value inside [0, 1].
+                let knob = self.param(ParamId::KnobFreq).normal_param.value;
+                let normal = Normal::from_clipped(knob.as_f32() + delta);
+                self.set_param(ParamId::KnobFreq, normal);
+            }
+            Message::Reset(id) => {
+                self.last_touched = Some(id);
+
+                // Use the default the param was constructed with, not a guess.
+                let default = self.param(id).normal_param.default;
+                self.set_param(id, default);
+            }
+            Message::ValueEdited(id, text) => {
+                self.value_drafts.insert(id, text);
+            }
+            Message::ValueSubmitted(id) => {
+                let Some(text) = self.value_drafts.remove(&id) else {
+                    return Command::none();
+                };
+
+                self.last_touched = Some(id);
+
+                match self.param(id).parse(&text) {
+                    Some(normal) => self.set_param(id, normal),
+                    None => self.set_status(format!("Invalid value: {text:?}")),
+                }
+            }
+            Message::ToggleTheme => {
+                self.theme = match self.theme {
+                    Theme::Dark => Theme::Light,
+                    _ => Theme::Dark,
+                };
+
+                self.prefs.dark_theme = self.theme == Theme::Dark;
+                self.save_prefs();
+            }
+            Message::ToggleDragTooltips => {
+                self.prefs.drag_tooltips = !self.prefs.drag_tooltips;
+                self.save_prefs();
+            }
+            Message::KnobDrag(axis) => {
+                self.prefs.knob_drag = axis;
+                self.save_prefs();
+                self.set_status(format!("Knob drag: {axis}"));
+            }
+            Message::KnobTravelEdited(text) => self.knob_travel_draft = Some(text),
+            Message::KnobTravelSubmitted => {
+                let Some(text) = self.knob_travel_draft.take() else {
+                    return Command::none();
+                };
+
+                match text.trim().parse::<f32>() {
+                    Ok(pixels) if pixels.is_finite() && pixels > 0.0 => {
+                        self.prefs.knob_travel = pixels;
+                        self.save_prefs();
+                        self.set_status(format!("Knobs turn fully over {pixels} px"));
+                    }
+                    _ => self.set_status(format!("Invalid knob travel: {text:?}")),
+                }
+            }
+            Message::ToggleFineMode => {
+                self.fine_mode = !self.fine_mode;
+                self.set_status(if self.fine_mode {
+                    "Fine mode on: drags move a tenth as far"
+                } else {
+                    "Fine mode off"
+                });
+            }
+            Message::Panic => {
+                let safe: Vec<_> = self
+                    .params
+                    .iter()
+                    .map(|(&id, param)| (id, param.safe_normal()))
+                    .collect();
+
+                // One undo entry brings everything back.
+                self.set_params(&safe);
+                self.set_status("Panic: all parameters reset");
+            }
+            Message::Randomize => self.randomize(),
+            Message::SeedEdited(text) => self.seed_draft = Some(text),
+            Message::SeedSubmitted => {
+                let Some(text) = self.seed_draft.take() else {
+                    return Command::none();
+                };
+
+                match text.trim().parse() {
+                    Ok(seed) => self.random_seed = seed,
+                    Err(_) => self.set_status(format!("Invalid seed: {text:?}")),
+                }
+            }
+            Message::MorphTimeEdited(text) => self.morph_time_draft = Some(text),
+            Message::MorphTimeSubmitted => {
+                let Some(text) = self.morph_time_draft.take() else {
+                    return Command::none();
+                };
+
+                match text.trim().parse() {
+                    Ok(millis) => {
+                        self.prefs.morph_time = millis;
+                        self.save_prefs();
+                        self.set_status(if millis == 0 {
+                            "Presets load instantly".into()
+                        } else {
+                            format!("Presets morph in over {millis} ms")
+                        });
+                    }
+                    Err(_) => self.set_status(format!("Invalid morph time: {text:?}")),
+                }
+            }
+            Message::OscIntervalEdited(text) => self.osc_interval_draft = Some(text),
+            Message::OscIntervalSubmitted => {
+                let Some(text) = self.osc_interval_draft.take() else {
+                    return Command::none();
+                };
+
+                match text.trim().parse() {
+                    Ok(millis) => {
+                        self.prefs.osc_interval = millis;
+                        self.osc_throttle
+                            .set_interval(Duration::from_millis(millis));
+                        self.save_prefs();
+                        self.set_status(if millis == 0 {
+                            "OSC sends every change".into()
+                        } else {
+                            format!("OSC sends each parameter at most every {millis} ms")
+                        });
+                    }
+                    Err(_) => self.set_status(format!("Invalid OSC interval: {text:?}")),
+                }
+            }
+            Message::ToggleSolo => {
+                self.solo = !self.solo;
+                self.soloed = None;
+                self.set_status(if self.solo {
+                    "Solo on: the parameter last moved is highlighted"
+                } else {
+                    "Solo off"
+                });
+            }
+            Message::SoloIdleEdited(text) => self.solo_idle_draft = Some(text),
+            Message::SoloIdleSubmitted => {
+                let Some(text) = self.solo_idle_draft.take() else {
+                    return Command::none();
+                };
+
+                match text.trim().parse() {
+                    Ok(millis) => {
+                        self.prefs.solo_idle = millis;
+                        self.save_prefs();
+                        self.set_status(if millis == 0 {
+                            "Solo highlights until another parameter moves".into()
+                        } else {
+                            format!("Solo highlights for {millis} ms")
+                        });
+                    }
+                    Err(_) => self.set_status(format!("Invalid solo time: {text:?}")),
+                }
+            }
+            Message::ToggleStatusClears => {
+                self.prefs.status_clears = !self.prefs.status_clears;
+                self.save_prefs();
+                self.set_status(if self.prefs.status_clears {
+                    "The status clears when idle"
+                } else {
+                    "The status stays"
+                });
+            }
+            Message::StatusTimeoutEdited(text) => self.status_timeout_draft = Some(text),
+            Message::StatusTimeoutSubmitted => {
+                let Some(text) = self.status_timeout_draft.take() else {
+                    return Command::none();
+                };
+
+                match text.trim().parse() {
+                    Ok(millis) => {
+                        self.prefs.status_timeout = millis;
+                        self.save_prefs();
+                        self.set_status(format!("The status clears after {millis} ms"));
+                    }
+                    Err(_) => self.set_status(format!("Invalid status timeout: {text:?}")),
+                }
+            }
+            Message::ResetAll => {
+                self.reset_all();
+                self.set_status("Reset all parameters to their defaults");
+            }
+            Message::SavePreset => {
+                let preset = self.preset();
+                let status = match preset.save(&self.preset_path) {
+                    Ok(()) => {
+                        self.edited = None;
+                        self.saved = Some(preset);
+                        format!("Saved preset to {}", self.preset_path.display())
+                    }
+                    Err(error) => format!("Couldn't save preset: {error}"),
+                };
+                self.set_status(status);
+            }
+            Message::LoadPreset => self.load_preset(&self.preset_path.clone()),
+            Message::FileHovered(path) => self.file_hovered = Some(path),
+            Message::FilesHoveredLeft => self.file_hovered = None,
+            Message::FileDropped(path) => {
+                self.file_hovered = None;
+                if preset::is_preset(&path) {
+                    self.load_preset(&path);
+                } else {
+                    self.set_status(format!(
+                        "Couldn't load {}: presets end in .json or .bin",
+                        path.display()
+                    ));
+                }
+            }
+            Message::SelectBank(name) => {
+                match PresetBank::load(&Path::new(BANKS_PATH).join(&name)) {
+                    Ok(bank) => {
+                        self.bank = Some(bank);
+                        self.apply_bank_preset(|bank| bank.current());
+                    }
+                    Err(error) => self.set_status(format!("Couldn't load bank {name}: {error}")),
+                }
+            }
+            Message::NextPreset => self.apply_bank_preset(PresetBank::next),
+            Message::PrevPreset => self.apply_bank_preset(PresetBank::prev),
+            Message::SwapCompare => {
+                let live = self.preset();
+                if let Some(other) = self.compare_other.replace(live) {
+                    self.apply_params(&other);
+                }
+                self.compare_b = !self.compare_b;
+                self.set_status(self.compare_label());
+            }
+            Message::CopyAsCode => {
+                self.set_status("Copied the current values as code");
+                self.flush_status();
+                return clipboard::write(self.defaults_code());
+            }
+            Message::CopyAToB => {
+                if !self.compare_b {
+                    self.compare_other = Some(self.preset());
+                } else if let Some(a) = self.compare_other.clone() {
+                    // A is the stored one, so B takes its values live.
+                    self.apply_params(&a);
+                }
+
+                self.set_status(format!("Copied A to B, {}", self.compare_label()));
+            }
+            Message::MidiCc(controller, _) if self.midi_learn => match self.last_touched {
+                Some(id) => {
+                    // A parameter follows a single controller, so drop any
+                    // binding it had before.
+                    self.midi_bindings.retain(|_, bound| bound.param != id);
+                    self.midi_bindings.insert(controller, Binding::absolute(id));
+                    self.midi_learn = false;
+                    self.set_status(format!("CC {controller} bound to {id:?}"));
+                }
+                None => self.set_status(format!("Touch a widget to bind it to CC {controller}")),
+            },
+            Message::MidiCc(controller, control) => {
+                if let Some(&Binding { param: id, mode }) = self.midi_bindings.get(&controller) {
+                    if self.param(id).disabled || self.param(id).locked {
+                        return Command::none();
+                    }
+
+                    let normal = match mode {
+                        CcMode::Absolute => Normal::from_clipped(f32::from(control.value) / 127.0),
+                        CcMode::Relative => {
+                            if control.jumps > 0 {
+                                self.set_status(format!(
+                                    "CC {controller} jumped too far for a relative encoder: \
+                                     is it absolute?"
+                                ));
+                            }
+                            if control.steps == 0 {
+                                return Command::none();
+                            }
+
+                            // From any value still waiting for the next frame,
+                            // so steps arriving between frames add up. Steps
+                            // are the same size as the arrow keys'.
+                            let param = self.param(id);
+                            let base = self.pending.get(id).unwrap_or(param.normal_param.value);
+                            param.range.stepped(base, control.steps, NUDGE_STEP)
+                        }
+                    };
+                    self.pending.set(id, normal);
+                }
+            }
+            Message::MidiNote(_)
+                if self.param(ParamId::KnobFreq).disabled
+                    || self.param(ParamId::KnobFreq).locked => {}
+            Message::MidiNote(note) => self.set_note(i32::from(note)),
+            Message::ToggleCcMode => self.toggle_cc_mode(),
+            Message::ToggleHelp => self.help_open = !self.help_open,
+            Message::CloseHelp => self.help_open = false,
+            Message::MidiUnavailable(reason) => {
+                self.set_status(format!("MIDI unavailable: {reason}"));
+            }
+            Message::OscParam(id, _) if self.param(id).disabled || self.param(id).locked => {}
+            Message::OscParam(id, value) => {
+                let normal = self.param(id).range.map_to_normal(value);
+                self.pending.set(id, normal);
+            }
+            Message::OscUnavailable(reason) => {
+                self.set_status(format!("OSC unavailable: {reason}"));
+            }
+            Message::ExternalParam(id, _) if self.param(id).disabled || self.param(id).locked => {}
+            // A host bug shouldn't reach the audio as a NaN.
+            Message::ExternalParam(id, value) if !value.is_finite() => {
+                let name = self.param(id).name;
+                self.set_status(format!("Ignored an external {name} of {value}"));
+            }
+            Message::ExternalParam(id, value) => {
+                let normal = self.param(id).range.map_to_normal(value);
+                self.pending.set(id, normal);
+            }
+            Message::ExternalClosed => {
+                self.external = None;
+                self.set_status("The external source closed");
+            }
+            Message::WindowResized(width, height) => {
+                self.knob_size = knob_size(width, height);
+
+                if step_labels(width) != step_labels(self.window_width) {
+                    let min = self.config.h_slider.min as i32;
+                    (self.h_slider_tick_marks, self.h_slider_text_marks) =
+                        step_marks(min..=self.h_slider_max, step_labels(width));
+                }
+                self.window_width = width;
+
+                // Only switch once the size is clearly past the threshold.
+                self.knob_marks_are_dense = if self.knob_marks_are_dense {
+                    self.knob_size >= KNOB_DENSE_MARKS_SIZE - KNOB_MARKS_HYSTERESIS
+                } else {
+                    self.knob_size >= KNOB_DENSE_MARKS_SIZE + KNOB_MARKS_HYSTERESIS
+                };
+            }
+            Message::CloseRequested => {
+                if let Some(audio) = self.audio.take() {
+                    audio.stop();
+                }
+
+                return window::close();
+            }
+            Message::RestoreScratch => {
+                if let Some(preset) = self.scratch.take() {
+                    let clamped = self.apply_preset(&preset);
+                    self.set_status(
+                        String::from("Restored the auto-saved state") + &clamp_warning(&clamped),
+                    );
+                }
+            }
+            Message::DiscardScratch => {
+                self.scratch = None;
+                if let Some(path) = &self.scratch_path {
+                    // It's overwritten by the next auto-save anyway.
+                    let _ = fs::remove_file(path);
+                }
+            }
+            Message::ClearLog => self.log.clear(),
+            Message::ToggleStats => self.stats_open = !self.stats_open,
+            Message::ResetStats => {
+                self.stats.reset();
+                self.set_status("Reset the parameter statistics");
+            }
+            Message::ReconnectAudio => {
+                // The parameters live in `audio_shared`, so the new stream
+                // starts where the old one left off.
+                let was = self.audio.take().map(|audio| {
+                    let sample_rate = audio.sample_rate();
+                    audio.stop();
+                    sample_rate
+                });
+
+                let status = match Audio::start(Arc::clone(&self.audio_shared)) {
+                    Ok(audio) => {
+                        let sample_rate = audio.sample_rate();
+                        self.audio = Some(audio);
+                        self.device_lost = false;
+
+                        match was {
+                            Some(was) if was != sample_rate => {
+                                format!("Audio reconnected at {sample_rate} Hz, was {was} Hz")
+                            }
+                            _ => format!("Audio reconnected at {sample_rate} Hz"),
+                        }
+                    }
+                    Err(error) => format!("Couldn't reconnect audio: {error}"),
+                };
+                self.set_status(status);
+            }
+            Message::ToggleMidiLearn => {
+                self.midi_learn = !self.midi_learn;
+                self.set_status(if self.midi_learn {
+                    "MIDI learn: touch a widget, then move a controller"
+                } else {
+                    "MIDI learn cancelled"
+                });
+            }
+        }
+
+        self.flush_status();
+
+        Command::none()
+    }
+
+    fn subscription(&self) -> Subscription<Message> {
+        let midi = midi::subscription().map(|event| match event {
+            midi::Event::ControlChange(controller, value) => Message::MidiCc(controller, value),
+            midi::Event::NoteOn(note) => Message::MidiNote(note),
+            midi::Event::Unavailable(reason) => Message::MidiUnavailable(reason),
+        });
+
+        let osc = osc::subscription(self.prefs.osc_port).map(|event| match event {
+            osc::Event::Param(id, value) => Message::OscParam(id, value),
+            osc::Event::Unavailable(reason) => Message::OscUnavailable(reason),
+        });
+
+        let external = match &self.external {
+            Some(external) => source::subscription(Arc::clone(external)).map(|event| match event {
+                source::Event::Param(id, value) => Message::ExternalParam(id, value),
+                source::Event::Closed => Message::ExternalClosed,
+            }),
+            None => Subscription::none(),
+        };
+
+        let window = subscription::events_with(|event, _status| match event {
+            event::Event::Window(window::Event::Resized { width, height }) => {
+                Some(Message::WindowResized(width, height))
+            }
+            event::Event::Window(window::Event::CloseRequested) => Some(Message::CloseRequested),
+            event::Event::Window(window::Event::FileHovered(path)) => {
+                Some(Message::FileHovered(path))
+            }
+            event::Event::Window(window::Event::FileDropped(path)) => {
+                Some(Message::FileDropped(path))
+            }
+            event::Event::Window(window::Event::FilesHoveredLeft) => {
+                Some(Message::FilesHoveredLeft)
+            }
+            _ => None,
+        });
+
+        // Keys a focused value box captured, like Left and Right moving its
+        // cursor, are left alone. While the help is open, only the keys that
+        // close it do anything.
+        let keys = if self.help_open {
+            subscription::events_with(|event, status| {
+                shortcut(event, status)
+                    .filter(|message| matches!(message, Message::ToggleHelp | Message::CloseHelp))
+            })
+        } else {
+            subscription::events_with(shortcut)
+        };
+
+        // Only tick while there's a flash to fade, a solo highlight to clear,
+        // OSC values held back, a meter to read, automation to play, values
+        // waiting to be applied, an XY pad catching up with the pointer,
+        // parameters being modulated, changes to auto-save or a status line
+        // to clear.
+        let playing = matches!(self.automation.state(), automation::State::Playing(_));
+        let tick = if !self.pending.is_empty()
+            || self.morph.is_some()
+            || self.xy_target.is_some()
+            || !self.modulated().is_empty()
+        {
+            iced::time::every(FRAME_INTERVAL).map(Message::Tick)
+        } else if self.detent_flash.is_some()
+            || self.soloed.is_some()
+            || self.osc_throttle.is_holding()
+            || (self.edited.is_some() && self.scratch_path.is_some())
+            || (self.status_since.is_some() && self.prefs.status_clears)
+            || self.audio.is_some()
+            || playing
+        {
+            iced::time::every(TICK_INTERVAL).map(Message::Tick)
+        } else {
+            Subscription::none()
+        };
+
+        Subscription::batch([midi, osc, external, window, keys, tick])
+    }
+
+    fn view(&self) -> Element<'_, Message> {
+        if self.help_open {
+            return help();
+        }
+
+        let level = self
+            .meter_range
+            .map_to_normal(audio::gain_to_db(self.meter_level));
+
+        // Create each parameter widget, passing in the current state of the widget.
+        let h_slider_widget = HSlider::new(
+            self.param(ParamId::HSliderInt).normal_param,
+            Message::HSliderInt,
+        )
+        .on_grab(|| Some(Message::Grabbed(ParamId::HSliderInt)))
+        .on_release(|| Some(Message::Released(ParamId::HSliderInt)))
+        .modifier_keys(self.fine_drag_keys())
+        .modifier_scalar(FINE_DRAG_SCALAR)
+        // Add the tick mark group to this widget.
+        .tick_marks(&self.h_slider_tick_marks)
+        .style(self.themed());
+        // Compact mode drops the text marks, leaving the value for a
+        // tooltip, and thins the sliders.
+        let h_slider_widget = if self.compact {
+            h_slider_widget.height(Length::Fixed(COMPACT_SLIDER_THICKNESS))
+        } else {
+            h_slider_widget.text_marks(&self.h_slider_text_marks)
+        };
+
+        let v_slider_widget = VSlider::new(
+            self.param(ParamId::VSliderDB).normal_param,
+            Message::VSliderDB,
+        )
+        .on_grab(|| Some(Message::Grabbed(ParamId::VSliderDB)))
+        .on_release(|| Some(Message::Released(ParamId::VSliderDB)))
+        .modifier_keys(self.fine_drag_keys())
+        .modifier_scalar(FINE_DRAG_SCALAR)
+        .style(self.themed());
+
+        // The dB marks only fit the dB range, not the taper.
+        let v_slider_widget = if self.amplitude_taper {
+            v_slider_widget
+        } else {
+            v_slider_widget.tick_marks(&self.db_tick_marks)
+        };
+        let v_slider_widget = match (self.compact, self.amplitude_taper) {
+            (true, _) => v_slider_widget
+                .width(Length::Fixed(COMPACT_SLIDER_THICKNESS))
+                .height(Length::Fixed(COMPACT_SLIDER_LENGTH)),
+            (false, true) => v_slider_widget,
+            (false, false) => v_slider_widget.text_marks(&self.db_text_marks),
+        };
+
+        let knob_widget = Knob::new(
+            self.param(ParamId::KnobFreq).normal_param,
+            Message::KnobFreq,
+        )
+        .on_grab(|| Some(Message::Grabbed(ParamId::KnobFreq)))
+        .on_release(|| Some(Message::Released(ParamId::KnobFreq)))
+        .modifier_keys(self.fine_drag_keys())
+        .scalar(self.knob_scalar())
+        .modifier_scalar(FINE_DRAG_SCALAR)
+        .size(self.knob_length())
+        .tick_marks(self.knob_marks())
+        .style(self.themed());
+
+        // The ring only shows while modulation is on.
+        let knob_widget = if self.param(ParamId::KnobMod).disabled {
+            knob_widget
+        } else {
+            knob_widget.mod_range(&self.knob_mod_range)
+        };
+
+        let octave_widget = Knob::new(
+            self.param(ParamId::KnobOctave).normal_param,
+            Message::KnobOctave,
+        )
+        .on_grab(|| Some(Message::Grabbed(ParamId::KnobOctave)))
+        .on_release(|| Some(Message::Released(ParamId::KnobOctave)))
+        .modifier_keys(self.fine_drag_keys())
+        .scalar(self.knob_scalar())
+        .modifier_scalar(FINE_DRAG_SCALAR)
+        .size(self.knob_length())
+        .tick_marks(self.knob_marks())
+        .style(self.themed());
+
+        let smoothing_widget = Knob::new(
+            self.param(ParamId::Smoothing).normal_param,
+            Message::Smoothing,
+        )
+        .on_grab(|| Some(Message::Grabbed(ParamId::Smoothing)))
+        .on_release(|| Some(Message::Released(ParamId::Smoothing)))
+        .modifier_keys(self.fine_drag_keys())
+        .scalar(self.knob_scalar())
+        .modifier_scalar(FINE_DRAG_SCALAR)
+        .size(self.knob_length())
+        .style(self.themed());
+
+        let lfo_rate_widget = Knob::new(
+            NormalParam {
+                value: RATES.map_to_normal(self.lfo.rate()),
+                default: RATES.map_to_normal(lfo::DEFAULT_RATE),
+            },
+            Message::LfoRate,
+        )
+        .modifier_keys(self.fine_drag_keys())
+        .scalar(self.knob_scalar())
+        .modifier_scalar(FINE_DRAG_SCALAR)
+        .size(Length::Fixed(32.0))
+        .style(self.themed());
+
+        let mod_range_widget = ModRangeInput::new(
+            self.param(ParamId::KnobMod).normal_param,
+            Message::KnobModChanged,
+        )
+        .on_grab(|| Some(Message::Grabbed(ParamId::KnobMod)))
+        .on_release(|| Some(Message::Released(ParamId::KnobMod)))
+        .modifier_keys(self.fine_drag_keys())
+        .modifier_scalar(FINE_DRAG_SCALAR)
+        .style(self.themed());
+
+        let tab = self.xy_pad_tab;
+        let xy_pad_widget = XYPad::new(
+            self.param(ParamId::XYPadX).normal_param,
+            self.param(ParamId::XYPadY).normal_param,
+            move |normal_x, normal_y| Message::XYPadFloat(tab, normal_x, normal_y),
+        )
+        .on_grab(|| Some(Message::Grabbed(ParamId::XYPadX)))
+        .on_release(|| Some(Message::Released(ParamId::XYPadX)))
+        .modifier_keys(self.fine_drag_keys())
+        .modifier_scalar(FINE_DRAG_SCALAR)
+        .style(self.themed());
+        let xy_pad_widget = if self.compact {
+            xy_pad_widget.size(Length::Fixed(COMPACT_SLIDER_LENGTH))
+        } else {
+            xy_pad_widget
+        };
+        let xy_pad_widget: Element<_> = if self.xy_grid {
+            let color = xy_pad::StyleSheet::active(&self.themed(), &self.theme).center_line_color;
+
+            Grid::new(xy_pad_widget, self.xy_grid_size)
+                .color(color)
+                .into()
+        } else {
+            xy_pad_widget.into()
+        };
+
+        let band_widget = RangeSlider::new(
+            self.param(ParamId::BandLow).normal_param.value,
+            self.param(ParamId::BandHigh).normal_param.value,
+            |handle, normal| match handle {
+                Handle::Low => Message::RangeLow(normal),
+                Handle::High => Message::RangeHigh(normal),
+            },
+        )
+        .on_grab(|handle| Message::Grabbed(band_param(handle)))
+        .on_release(|handle| Message::Released(band_param(handle)));
+
+        let ramp_widget = Ramp::new(
+            self.param(ParamId::RampCurve).normal_param,
+            Message::RampChanged,
+            self.ramp_direction,
+        )
+        .on_grab(|| Some(Message::Grabbed(ParamId::RampCurve)))
+        .on_release(|| Some(Message::Released(ParamId::RampCurve)))
+        .modifier_keys(self.fine_drag_keys())
+        .modifier_scalar(FINE_DRAG_SCALAR)
+        .style(self.themed());
+
+        // Double-clicking any widget resets it to its default. Clicking a
+        // slider's track jumps it there, as on a mixing desk's faders, and
+        // hovering over the track shows where first.
+        let h_slider_handle = style::h_slider_handle(&self.theme);
+        let h_slider_normal = self.param(ParamId::HSliderInt).normal_param.value;
+        let h_slider_click = move |bounds: Rectangle, position: Point| {
+            track_click(
+                position.x - bounds.x,
+                bounds.width,
+                h_slider_handle,
+                h_slider_normal,
+            )
+        };
+        let h_slider_widget = Ghost::new(h_slider_widget, Orientation::Horizontal, h_slider_handle)
+            .at(self
+                .hovered(ParamId::HSliderInt)
+                .or_else(|| self.live_normal(ParamId::HSliderInt)));
+        let h_slider_widget = Interactive::new(h_slider_widget)
+            .on_double_click([Message::Reset(ParamId::HSliderInt)])
+            .on_click(move |bounds, position| {
+                h_slider_click(bounds, position).map(Message::HSliderInt)
+            })
+            .on_hover(move |bounds, position| {
+                Some(Message::Hover(
+                    ParamId::HSliderInt,
+                    h_slider_click(bounds, position),
+                ))
+            })
+            .on_leave(Message::Hover(ParamId::HSliderInt, None));
+        let v_slider_handle = style::v_slider_handle(&self.theme);
+        let v_slider_normal = self.param(ParamId::VSliderDB).normal_param.value;
+        let v_slider_click = move |bounds: Rectangle, position: Point| {
+            track_click(
+                bounds.y + bounds.height - position.y,
+                bounds.height,
+                v_slider_handle,
+                v_slider_normal,
+            )
+        };
+        let v_slider_widget = Ghost::new(v_slider_widget, Orientation::Vertical, v_slider_handle)
+            .at(self
+                .hovered(ParamId::VSliderDB)
+                .or_else(|| self.live_normal(ParamId::VSliderDB)));
+        let v_slider_widget = Interactive::new(v_slider_widget)
+            .on_double_click([Message::Reset(ParamId::VSliderDB)])
+            .on_click(move |bounds, position| {
+                v_slider_click(bounds, position).map(Message::VSliderDB)
+            })
+            .on_hover(move |bounds, position| {
+                Some(Message::Hover(
+                    ParamId::VSliderDB,
+                    v_slider_click(bounds, position),
+                ))
+            })
+            .on_leave(Message::Hover(ParamId::VSliderDB, None));
+        let knob_widget = Interactive::new(knob_widget)
+            .on_double_click([Message::Reset(ParamId::KnobFreq)])
+            .on_scroll(|lines, modifiers| Message::KnobScroll(knob_scroll_delta(lines, modifiers)))
+            .drag_axis(self.prefs.knob_drag);
+        let octave_widget = Interactive::new(octave_widget)
+            .on_double_click([Message::Reset(ParamId::KnobOctave)])
+            .drag_axis(self.prefs.knob_drag);
+        let smoothing_widget = Interactive::new(smoothing_widget)
+            .on_double_click([Message::Reset(ParamId::Smoothing)])
+            .drag_axis(self.prefs.knob_drag);
+        let octave_widget = self.drag_tooltip(octave_widget, &[ParamId::KnobOctave]);
+        let smoothing_widget = self.drag_tooltip(smoothing_widget, &[ParamId::Smoothing]);
+        let mod_range_widget =
+            Interactive::new(mod_range_widget).on_double_click([Message::Reset(ParamId::KnobMod)]);
+        let xy_pad_widget = Interactive::new(xy_pad_widget).on_double_click([
+            Message::Reset(ParamId::XYPadX),
+            Message::Reset(ParamId::XYPadY),
+        ]);
+        // Both ends at once, so neither is held by where the other was.
+        let band_widget =
+            Interactive::new(band_widget).on_double_click([Message::MenuReset(Focus::Band)]);
+        let ramp_widget =
+            Interactive::new(ramp_widget).on_double_click([Message::Reset(ParamId::RampCurve)]);
+
+        let h_slider_widget = self.frame(h_slider_widget, Focus::HSlider);
+        let v_slider_widget = self.frame(v_slider_widget, Focus::VSlider);
+        let knob_widget = self.frame(knob_widget, Focus::Knob);
+        let mod_range_widget = self.frame(mod_range_widget, Focus::ModRange);
+        let xy_pad_widget = self.frame(xy_pad_widget, Focus::XYPad);
+        let band_widget = self.frame(band_widget, Focus::Band);
+        let ramp_widget = self.frame(ramp_widget, Focus::Ramp);
+
+        let reference = match &self.reference_draft {
+            Some(draft) => draft.clone(),
+            None => format!("{}", self.reference_pitch),
+        };
+        let knob_detents = match &self.knob_detents_draft {
+            Some(draft) => draft.clone(),
+            None => self
+                .knob_detents
+                .iter()
+                .map(|detent| detent.as_f32().to_string())
+                .collect::<Vec<_>>()
+                .join(", "),
+        };
+        let knob_magnetism = match &self.knob_magnetism_draft {
+            Some(draft) => draft.clone(),
+            None => self.knob_magnetism.to_string(),
+        };
+        let note = match &self.note_draft {
+            Some(draft) => draft.clone(),
+            None => {
+                let knob = self.param(ParamId::KnobFreq).value();
+                tuning::note_name(tuning::nearest_note(knob, self.reference_pitch))
+            }
+        };
+        let seed = match &self.seed_draft {
+            Some(draft) => draft.clone(),
+            None => self.random_seed.to_string(),
+        };
+        let morph_time = match &self.morph_time_draft {
+            Some(draft) => draft.clone(),
+            None => self.prefs.morph_time.to_string(),
+        };
+        let lfo_rate = match &self.lfo_rate_draft {
+            Some(draft) => draft.clone(),
+            None => format!("{:.2}", self.lfo.rate()),
+        };
+        let lfo_depth = match &self.lfo_depth_draft {
+            Some(draft) => draft.clone(),
+            None => self.lfo_depth.to_string(),
+        };
+        let osc_interval = match &self.osc_interval_draft {
+            Some(draft) => draft.clone(),
+            None => self.prefs.osc_interval.to_string(),
+        };
+        let solo_idle = match &self.solo_idle_draft {
+            Some(draft) => draft.clone(),
+            None => self.prefs.solo_idle.to_string(),
+        };
+        let status_timeout = match &self.status_timeout_draft {
+            Some(draft) => draft.clone(),
+            None => self.prefs.status_timeout.to_string(),
+        };
+        let knob_travel = match &self.knob_travel_draft {
+            Some(draft) => draft.clone(),
+            None => self.prefs.knob_travel.to_string(),
+        };
+        let xy_smoothing = match &self.xy_smoothing_draft {
+            Some(draft) => draft.clone(),
+            None => self.xy_smoothing.to_string(),
+        };
+        let xy_deadzone = match &self.xy_deadzone_draft {
+            Some(draft) => draft.clone(),
+            None => self.xy_deadzone.to_string(),
+        };
+
+        // Push the widgets into the iced DOM, each next to a box for typing
+        // an exact value.
+        let controls = column![
+            slider(0.0..=1.0, self.slider_value, Message::SliderChanged).step(0.025),
+            button(text("Click here")).on_press(Message::ButtonClicked(self.button_id)),
+            row![
+                button(text("Save Preset")).on_press(Message::SavePreset),
+                button(text("Load Preset")).on_press(Message::LoadPreset),
+                text(self.drift_label()),
+                button(text(if self.midi_learn {
+                    "Learning..."
+                } else {
+                    "MIDI Learn"
+                }))
+                .on_press(Message::ToggleMidiLearn),
+                self.cc_mode_button(),
+            ]
+            .spacing(10),
+            row![
+                pick_list(
+                    self.banks.as_slice(),
+                    self.bank.as_ref().map(|bank| bank.name().to_owned()),
+                    Message::SelectBank
+                )
+                .placeholder("Bank")
+                .width(Length::Fill),
+                button(text("<")).on_press(Message::PrevPreset),
+                button(text(">")).on_press(Message::NextPreset),
+            ]
+            .spacing(10),
+            text(self.bank_label()),
+            // 0 loads presets instantly.
+            row![
+                text("Morph (ms)"),
+                text_input("", &morph_time, Message::MorphTimeEdited)
+                    .on_submit(Message::MorphTimeSubmitted)
+                    .width(Length::Fill),
+            ]
+            .spacing(10)
+            .align_items(Alignment::Center),
+            // 0 sends every change.
+            row![
+                text("OSC every (ms)"),
+                text_input("", &osc_interval, Message::OscIntervalEdited)
+                    .on_submit(Message::OscIntervalSubmitted)
+                    .width(Length::Fill),
+            ]
+            .spacing(10)
+            .align_items(Alignment::Center),
+            // Parameters are modulated from their context menus.
+            row![
+                text("LFO (Hz)"),
+                lfo_rate_widget,
+                text_input("", &lfo_rate, Message::LfoRateEdited)
+                    .on_submit(Message::LfoRateSubmitted)
+                    .width(Length::Fill),
+                pick_list(&Shape::ALL[..], Some(self.lfo.shape()), Message::LfoShape),
+                text("Depth"),
+                text_input("", &lfo_depth, Message::LfoDepthEdited)
+                    .on_submit(Message::LfoDepthSubmitted)
+                    .width(Length::Fill),
+            ]
+            .spacing(10)
+            .align_items(Alignment::Center),
+            text(self.modulation_label()),
+            // Shows the live slot; pressing it swaps to the other.
+            row![
+                button(text(if self.compare_b { "B" } else { "A" })).on_press(Message::SwapCompare),
+                button(text("Copy A to B")).on_press(Message::CopyAToB),
+                button(text("Copy as Code")).on_press(Message::CopyAsCode),
+            ]
+            .spacing(10),
+            button(text(match self.theme {
+                Theme::Dark => "Light Theme",
+                _ => "Dark Theme",
+            }))
+            .on_press(Message::ToggleTheme),
+            button(text("Keyboard Shortcuts (?)")).on_press(Message::ToggleHelp),
+            button(text(if self.prefs.drag_tooltips {
+                "Drag Tooltips On"
+            } else {
+                "Drag Tooltips Off"
+            }))
+            .on_press(Message::ToggleDragTooltips),
+            button(text(if self.compact {
+                "Compact Layout"
+            } else {
+                "Full Layout"
+            }))
+            .on_press(Message::ToggleCompact),
+            button(text(if self.show_raw {
+                "Raw Normals On"
+            } else {
+                "Raw Normals Off"
+            }))
+            .on_press(Message::ToggleShowRaw),
+            row![
+                text("Knob drag"),
+                pick_list(
+                    &DragAxis::ALL[..],
+                    Some(self.prefs.knob_drag),
+                    Message::KnobDrag
+                ),
+            ]
+            .spacing(10)
+            .align_items(Alignment::Center),
+            // More pixels turn the knobs more finely.
+            row![
+                text("Knob travel (px)"),
+                text_input("", &knob_travel, Message::KnobTravelEdited)
+                    .on_submit(Message::KnobTravelSubmitted)
+                    .width(Length::Fill),
+            ]
+            .spacing(10)
+            .align_items(Alignment::Center),
+            // Highlighted while on, since it changes how every drag feels.
+            button(text(if self.fine_mode {
+                "Fine Mode On"
+            } else {
+                "Fine Mode Off"
+            }))
+            .style(if self.fine_mode {
+                theme::Button::Primary
+            } else {
+                theme::Button::Secondary
+            })
+            .on_press(Message::ToggleFineMode),
+            // 0 keeps the highlight until another parameter moves.
+            row![
+                button(text(if self.solo { "Solo On" } else { "Solo Off" }))
+                    .on_press(Message::ToggleSolo),
+                text("Idle (ms)"),
+                text_input("", &solo_idle, Message::SoloIdleEdited)
+                    .on_submit(Message::SoloIdleSubmitted)
+                    .width(Length::Fill),
+            ]
+            .spacing(10)
+            .align_items(Alignment::Center),
+            row![
+                button(text(if self.prefs.status_clears {
+                    "Status Clears"
+                } else {
+                    "Status Stays"
+                }))
+                .on_press(Message::ToggleStatusClears),
+                text("After (ms)"),
+                text_input("", &status_timeout, Message::StatusTimeoutEdited)
+                    .on_submit(Message::StatusTimeoutSubmitted)
+                    .width(Length::Fill),
+            ]
+            .spacing(10)
+            .align_items(Alignment::Center),
+            row![
+                button(text("Randomize")).on_press(Message::Randomize),
+                text_input("", &seed, Message::SeedEdited)
+                    .on_submit(Message::SeedSubmitted)
+                    .width(Length::Fill),
+            ]
+            .spacing(10)
+            .align_items(Alignment::Center),
+            row![
+                button(text("Reset All")).on_press(Message::ResetAll),
+                button(text("Panic"))
+                    .style(theme::Button::Destructive)
+                    .on_press(Message::Panic),
+            ]
+            .spacing(10),
+        ]
+        .spacing(20)
+        .align_items(Alignment::Center);
+
+        //////////////
+        // Compact mode packs the parameters closer together.
+        let (gap, row_gap) = if self.compact { (8, 4) } else { (20, 10) };
+        let params = column![
+            row![
+                h_slider_widget,
+                self.value_input(ParamId::HSliderInt),
+                column![self.int_step_button("+", 1), self.int_step_button("-", -1)],
+                text("Steps:"),
+                pick_list(
+                    self.h_slider_maxima(),
+                    Some(self.h_slider_max),
+                    Message::HSliderMax
+                )
+                .width(Length::Fixed(70.0)),
+            ]
+            .spacing(row_gap)
+            .align_items(Alignment::Center),
+            button(text(if self.detent_click {
+                "Step Clicks On"
+            } else {
+                "Step Clicks Off"
+            }))
+            .on_press(Message::ToggleDetentClick),
+            row![
+                button(text(if self.limiter {
+                    "Limiter On"
+                } else {
+                    "Limiter Bypassed"
+                }))
+                .on_press(Message::ToggleLimiter),
+                // Blank rather than absent, so the button doesn't shift.
+                container(text(if self.limiting.is_some() {
+                    "Limiting"
+                } else {
+                    ""
+                }))
+                .width(Length::Fixed(70.0)),
+            ]
+            .spacing(row_gap)
+            .align_items(Alignment::Center),
+            row![
+                v_slider_widget,
+                Meter::new(level),
+                // The same level on a slider, to compare against the gain.
+                ReadOnly::new(
+                    VSlider::new(
+                        NormalParam {
+                            value: level,
+                            default: Normal::MIN,
+                        },
+                        |_| ()
+                    )
+                    .style(self.themed())
+                ),
+                self.value_input(ParamId::VSliderDB),
+                button(text(if self.amplitude_taper {
+                    "Amplitude"
+                } else {
+                    "dB"
+                }))
+                .on_press(Message::ToggleGainTaper),
+            ]
+            .spacing(row_gap)
+            .align_items(Alignment::Center),
+            row![
+                knob_widget,
+                self.value_input(ParamId::KnobFreq),
+                mod_range_widget,
+                self.value_input(ParamId::KnobMod),
+            ]
+            .spacing(row_gap)
+            .align_items(Alignment::Center),
+            row![
+                button(text(if self.param(ParamId::KnobMod).disabled {
+                    "Modulation Off"
+                } else {
+                    "Modulation On"
+                }))
+                .on_press(Message::ToggleModulation),
+                button(text(if self.phase_invert {
+                    "Phase Inverted"
+                } else {
+                    "Phase Normal"
+                }))
+                .on_press(Message::TogglePhaseInvert),
+            ]
+            .spacing(row_gap),
+            row![octave_widget, self.value_input(ParamId::KnobOctave)]
+                .spacing(row_gap)
+                .align_items(Alignment::Center),
+            row![
+                text("Span:"),
+                pick_list(self.freq_spans(), Some(self.freq_span), Message::FreqSpan),
+            ]
+            .spacing(row_gap)
+            .align_items(Alignment::Center),
+            self.automation_controls(),
+            row![
+                button(text(if self.note_snap {
+                    "Snapping to Notes"
+                } else {
+                    "Snap to Notes"
+                }))
+                .on_press(Message::ToggleNoteSnap),
+                text_input("", &reference, Message::ReferenceEdited)
+                    .on_submit(Message::ReferenceSubmitted)
+                    .width(Length::Fixed(60.0)),
+                text("Hz A4"),
+            ]
+            .spacing(row_gap)
+            .align_items(Alignment::Center),
+            row![
+                text("Note (N):"),
+                text_input("", &note, Message::NoteEdited)
+                    .id(note_input_id())
+                    .on_submit(Message::NoteSubmitted)
+                    .width(Length::Fixed(60.0)),
+            ]
+            .spacing(row_gap)
+            .align_items(Alignment::Center),
+            // Normals, like "0.25, 0.5", and how near a drag snaps to one.
+            row![
+                text("Detents:"),
+                text_input("none", &knob_detents, Message::KnobDetentsEdited)
+                    .on_submit(Message::KnobDetentsSubmitted)
+                    .width(Length::Fill),
+                text("Radius:"),
+                text_input("", &knob_magnetism, Message::KnobMagnetismEdited)
+                    .on_submit(Message::KnobMagnetismSubmitted)
+                    .width(Length::Fixed(50.0)),
+            ]
+            .spacing(row_gap)
+            .align_items(Alignment::Center),
+            row![
+                xy_pad_widget,
+                column![
+                    self.xy_pad_tabs(row_gap),
+                    self.value_input(ParamId::XYPadX),
+                    self.value_input(ParamId::XYPadY),
+                    button(text(if self.xy_circle { "Circle" } else { "Square" }))
+                        .on_press(Message::ToggleXYCircle),
+                    // 0 follows the pointer exactly.
+                    row![
+                        text("Smoothing"),
+                        text_input("", &xy_smoothing, Message::XYSmoothingEdited)
+                            .on_submit(Message::XYSmoothingSubmitted)
+                            .width(Length::Fixed(60.0)),
+                    ]
+                    .spacing(row_gap)
+                    .align_items(Alignment::Center),
+                    // 0 has no deadzone.
+                    row![
+                        text("Deadzone"),
+                        text_input("", &xy_deadzone, Message::XYDeadzoneEdited)
+                            .on_submit(Message::XYDeadzoneSubmitted)
+                            .width(Length::Fixed(60.0)),
+                    ]
+                    .spacing(row_gap)
+                    .align_items(Alignment::Center),
+                    row![
+                        button(text(if self.xy_grid { "Grid On" } else { "Grid Off" }))
+                            .on_press(Message::ToggleXYGrid),
+                        button(text(if self.xy_snap { "Snap On" } else { "Snap Off" }))
+                            .on_press(Message::ToggleXYSnap),
+                        pick_list(
+                            &XY_GRID_SIZES[..],
+                            Some(self.xy_grid_size),
+                            Message::XYGridSize
+                        )
+                        .width(Length::Fixed(60.0)),
+                    ]
+                    .spacing(row_gap)
+                    .align_items(Alignment::Center),
+                ]
+                .spacing(row_gap)
+            ]
+            .spacing(row_gap)
+            .align_items(Alignment::Center),
+            row![
+                band_widget,
+                self.value_input(ParamId::BandLow),
+                self.value_input(ParamId::BandHigh),
+            ]
+            .spacing(row_gap)
+            .align_items(Alignment::Center),
+            row![
+                ramp_widget,
+                self.value_input(ParamId::RampCurve),
+                button(text("Flip")).on_press(Message::ToggleRampDirection),
+            ]
+            .spacing(row_gap)
+            .align_items(Alignment::Center),
+            row![
+                smoothing_widget,
+                self.value_input(ParamId::Smoothing),
+                text("Smoothing (ms)"),
+            ]
+            .spacing(row_gap)
+            .align_items(Alignment::Center),
+        ]
+        .spacing(gap)
+        .align_items(Alignment::Center);
+        // In solo mode the value last moved heads the parameters, large.
+        let params = if self.solo {
+            column![self.solo_label(), params]
+                .spacing(gap)
+                .align_items(Alignment::Center)
+        } else {
+            params
+        };
+
+        //////////////
+        let mut log = Column::new().spacing(20);
+        if self.device_lost {
+            log = log.push(
+                row![
+                    text("Audio device lost"),
+                    button(text("Reconnect"))
+                        .style(theme::Button::Destructive)
+                        .on_press(Message::ReconnectAudio),
+                ]
+                .spacing(10)
+                .align_items(Alignment::Center),
+            );
+        }
+        if let Some(path) = &self.file_hovered {
+            let name = path.file_name().unwrap_or_default().to_string_lossy();
+            log = log.push(text(if preset::is_preset(path) {
+                format!("Drop to load {name}")
+            } else {
+                format!("{name} isn't a preset")
+            }));
+        }
+        if self.scratch.is_some() {
+            log = log.push(
+                row![
+                    text("Restore the auto-saved state?"),
+                    button(text("Restore")).on_press(Message::RestoreScratch),
+                    button(text("Discard")).on_press(Message::DiscardScratch),
+                ]
+                .spacing(10)
+                .align_items(Alignment::Center),
+            );
+        }
+        let log = log
+            .push(container(text(self.status_line())).width(Length::Fill))
+            .push(self.log_panel())
+            .push(self.stats_panel());
+
+        // Wide windows put the parameters beside the controls and log rather
+        // than below them.
+        let content: Element<_> = if self.window_width >= WIDE_WIDTH {
+            row![
+                column![controls, log]
+                    .max_width(300)
+                    .spacing(20)
+                    .align_items(Alignment::Center),
+                params.max_width(300),
+            ]
+            .spacing(40)
+            .padding(20)
+            .into()
+        } else {
+            column![controls, params, log]
+                .max_width(300)
+                .spacing(20)
+                .padding(20)
+                .align_items(Alignment::Center)
+                .into()
+        };
+
+        // The column outgrows small windows, so let it scroll.
+        container(scrollable(content))
+            .width(Length::Fill)
+            .height(Length::Fill)
+            .center_x()
+            .center_y()
+            .into()
+    }
+}
+
+impl App {
+    /// Starts setting up the app, to [`run`](AppBuilder::run) it.
+    pub fn builder() -> AppBuilder {
+        AppBuilder::default()
+    }
+
+    // The app as `flags` set it up, but with no audio or OSC yet, which
+    // `new` then starts. Rendering to an image uses it as it is.
+    fn from_builder(flags: AppBuilder) -> App {
+        // The factory defaults, unless there's a valid config overriding
+        // them.
+        let (config, config_error) = match flags.config {
+            Some(config) => match config.validate() {
+                Ok(()) => (config, None),
+                Err(error) => (
+                    Config::default(),
+                    Some(format!("Invalid config, using defaults: {error}")),
+                ),
+            },
+            None => match Config::load(Path::new(CONFIG_PATH)) {
+                Ok(config) => (config, None),
+                Err(error) if error.is_missing() => (Config::default(), None),
+                Err(error) => (
+                    Config::default(),
+                    Some(format!(
+                        "Couldn't load {CONFIG_PATH}, using defaults: {error}"
+                    )),
+                ),
+            },
+        };
+
+        let mut app = App::with_config(config, Prefs::load(Path::new(PREFS_PATH)));
+        if let Some(path) = flags.preset_path {
+            app.preset_path = path;
+        }
+        let scratch_path = PathBuf::from(SCRATCH_PATH);
+        app.scratch = newer_scratch(&scratch_path, &app.preset_path);
+        app.scratch_path = Some(scratch_path);
+        for (id, template) in flags.templates {
+            app.param_mut(id).template = Some(template);
+        }
+        app.on_change = flags.on_change;
+        app.external = flags.external.map(|source| Arc::new(Mutex::new(source)));
+        app.tick_style = flags.tick_style;
+        if let Some((path, preset)) = flags.preset {
+            app.start_from_preset(&path, &preset);
+        }
+
+        if let Some(error) = config_error {
+            app.log.push(error, false);
+        }
+
+        app
+    }
+
+    // An app with the parameters from `config` but no audio or OSC yet,
+    // which `new` then starts. Tests run it as it is.
+    fn with_config(config: Config, prefs: Prefs) -> App {
+        let osc_interval = Duration::from_millis(prefs.osc_interval);
+        // Initalize each range:
+        let steps = config.h_slider.min as i32..=config.h_slider.max as i32;
+        let int_range = IntRange::new(*steps.start(), *steps.end());
+        let freq_range = FreqRange::new(config.knob.min, config.knob.max);
+        let (knob_marks_sparse, knob_marks_dense) = knob_marks(&freq_range);
+        let freq_span = FreqSpan(config.knob.min, config.knob.max);
+        let float_range = |param: ParamConfig| FloatRange::new(param.min, param.max);
+
+        let params = HashMap::from([
+            (
+                ParamId::HSliderInt,
+                Param::new("HSliderInt", Range::Int(int_range), config.h_slider.default)
+                    .with_random(config.h_slider.random)
+                    .with_decimals(config.h_slider.decimals),
+            ),
+            (ParamId::VSliderDB, gain_param(&config, false)),
+            (
+                ParamId::KnobFreq,
+                Param::new("KnobFreq", Range::Freq(freq_range), config.knob.default)
+                    .with_random(config.knob.random)
+                    .with_decimals(config.knob.decimals),
+            ),
+            (
+                ParamId::KnobOctave,
+                Param::new(
+                    "octave up",
+                    Range::Freq(freq_range),
+                    config.knob.default * OCTAVE_RATIO,
+                )
+                .with_decimals(config.knob.decimals),
+            ),
+            // The knob's bipolar modulation depth.
+            (
+                ParamId::KnobMod,
+                Param::new(
+                    "mod depth",
+                    Range::Float(float_range(config.knob_mod)),
+                    config.knob_mod.default,
+                )
+                .with_random(config.knob_mod.random)
+                .with_decimals(config.knob_mod.decimals),
+            ),
+            (
+                ParamId::XYPadX,
+                Param::new(
+                    "x",
+                    Range::Float(float_range(config.xy_pad_x)),
+                    config.xy_pad_x.default,
+                )
+                .with_random(config.xy_pad_x.random)
+                .with_decimals(config.xy_pad_x.decimals),
+            ),
+            (
+                ParamId::XYPadY,
+                Param::new(
+                    "y",
+                    Range::Float(float_range(config.xy_pad_y)),
+                    config.xy_pad_y.default,
+                )
+                .with_random(config.xy_pad_y.random)
+                .with_decimals(config.xy_pad_y.decimals),
+            ),
+            (
+                ParamId::RampCurve,
+                Param::new(
+                    "RampCurve",
+                    Range::Float(float_range(config.ramp)),
+                    config.ramp.default,
+                )
+                .with_random(config.ramp.random)
+                .with_decimals(config.ramp.decimals),
+            ),
+            (
+                ParamId::Smoothing,
+                Param::new(
+                    "Smoothing (ms)",
+                    Range::Float(float_range(config.smoothing)),
+                    config.smoothing.default,
+                )
+                .with_random(config.smoothing.random)
+                .with_decimals(config.smoothing.decimals),
+            ),
+            (
+                ParamId::BandLow,
+                Param::new(
+                    "band low",
+                    Range::Freq(FreqRange::new(config.band_low.min, config.band_low.max)),
+                    config.band_low.default,
+                )
+                .with_random(config.band_low.random)
+                .with_decimals(config.band_low.decimals),
+            ),
+            (
+                ParamId::BandHigh,
+                Param::new(
+                    "band high",
+                    Range::Freq(FreqRange::new(config.band_high.min, config.band_high.max)),
+                    config.band_high.default,
+                )
+                .with_random(config.band_high.random)
+                .with_decimals(config.band_high.decimals),
+            ),
+        ]);
+
+        let xy_pad = (
+            params[&ParamId::XYPadX].normal_param.default,
+            params[&ParamId::XYPadY].normal_param.default,
+        );
+        let db_range = db_range(&config);
+        let knob = &params[&ParamId::KnobFreq];
+        let gain = &params[&ParamId::VSliderDB];
+        let smoothing = params[&ParamId::Smoothing].value() / 1000.0;
+        let audio_shared = Arc::new(audio::Shared::new(
+            knob.value(),
+            param_gain(gain),
+            param_pan(&params[&ParamId::XYPadX]),
+            smoothing,
+        ));
+        let knob_mod_range =
+            knob_mod_range(knob.normal_param.value, params[&ParamId::KnobMod].value());
+
+        let theme = if prefs.dark_theme {
+            Theme::Dark
+        } else {
+            Theme::Light
+        };
+
+        let last_step = params[&ParamId::HSliderInt].value() as i32;
+        let (width, height) = window::Settings::default().size;
+        let (h_slider_tick_marks, h_slider_text_marks) =
+            step_marks(steps.clone(), step_labels(width));
+        let knob_size = knob_size(width, height);
+
+        App {
+            slider_value: 0.0,
+            button_id: 128,
+            //////////
+            params,
+            config,
+            knob_mod_range,
+            knob_throw: None,
+            xy_pads: vec![xy_pad; XY_PAD_COUNT],
+            xy_pad_tab: 0,
+            xy_circle: false,
+            xy_grid: false,
+            xy_snap: false,
+            xy_grid_size: XY_GRID_SIZE,
+            xy_smoothing: 0.0,
+            xy_target: None,
+            xy_smoothing_draft: None,
+            xy_deadzone: 0.0,
+            xy_deadzone_draft: None,
+            ramp_direction: RampDirection::Up,
+            last_step,
+            detent_flash: None,
+            detent_click: false,
+            amplitude_taper: false,
+            phase_invert: false,
+            meter_level: 0.0,
+            limiter: true,
+            limiting: None,
+            // Clamped at full scale: the top of the meter means clipping.
+            meter_range: LogDBRange::new(-48.0, 0.0, Normal::MAX),
+            meter_read: Instant::now(),
+            note_snap: false,
+            reference_pitch: DEFAULT_REFERENCE_PITCH,
+            reference_draft: None,
+            note_draft: None,
+            knob_detents: Vec::new(),
+            knob_magnetism: DEFAULT_KNOB_MAGNETISM,
+            knob_detents_draft: None,
+            knob_magnetism_draft: None,
+            // A different start each run, shown so a result can be repeated.
+            random_seed: SystemTime::now()
+                .duration_since(UNIX_EPOCH)
+                .map_or(0, |since| since.as_nanos() as u64),
+            seed_draft: None,
+            morph_time_draft: None,
+            solo: false,
+            soloed: None,
+            solo_idle_draft: None,
+            status_since: None,
+            status_idle: false,
+            status_timeout_draft: None,
+            knob_travel_draft: None,
+            automation: Lane::default(),
+
+            // Ticks every few dB out from 0 dB, labelled more sparsely.
+            db_tick_marks: marks::db_tick_marks(&db_range, DB_TICK_STEP),
+            db_text_marks: marks::db_text_marks(&db_range, DB_LABEL_STEP),
+            h_slider_tick_marks,
+            h_slider_text_marks,
+            h_slider_max: *steps.end(),
+            freq_span,
+            knob_marks_sparse,
+            knob_marks_dense,
+            knob_size,
+            window_width: width,
+            knob_marks_are_dense: knob_size >= KNOB_DENSE_MARKS_SIZE,
+            compact: false,
+            show_raw: false,
+            stats: Stats::new(),
+            stats_open: false,
+            lfo: Lfo::new(lfo::DEFAULT_RATE),
+            lfo_depth: lfo::DEFAULT_DEPTH,
+            lfo_rate_draft: None,
+            lfo_depth_draft: None,
+            log: Log::new(),
+            status: None,
+            value_drafts: HashMap::new(),
+            banks: bank::list(Path::new(BANKS_PATH)),
+            bank: None,
+            preset_path: PathBuf::from(PRESET_PATH),
+            file_hovered: None,
+            scratch_path: None,
+            edited: None,
+            scratch: None,
+            saved: None,
+            compare_b: false,
+            compare_other: None,
+            theme,
+            prefs,
+            midi_bindings: midi::default_bindings(),
+            midi_learn: false,
+            last_touched: None,
+            pending: Pending::default(),
+            morph: None,
+            help_open: false,
+            fine_mode: false,
+            focused: None,
+            history: History::default(),
+            drags: Vec::new(),
+            hover: None,
+            context_menu: None,
+            copied: Vec::new(),
+            osc: None,
+            osc_throttle: Throttle::new(osc_interval),
+            osc_interval_draft: None,
+            on_change: None,
+            external: None,
+            tick_style: None,
+            audio: None,
+            audio_shared,
+            device_lost: false,
+        }
+    }
+
+    fn param(&self, id: ParamId) -> &Param {
+        &self.params[&id]
+    }
+
+    fn param_mut(&mut self, id: ParamId) -> &mut Param {
+        self.params.get_mut(&id).unwrap()
+    }
+
+    // The raw normal of a parameter, as presets store it.
+    fn normal(&self, id: ParamId) -> f32 {
+        self.param(id).normal_param.value.as_f32()
+    }
+
+    fn preset(&self) -> Preset {
+        Preset {
+            version: PRESET_VERSION,
+            h_slider: self.normal(ParamId::HSliderInt),
+            v_slider: self.normal(ParamId::VSliderDB),
+            knob: self.normal(ParamId::KnobFreq),
+            xy_pad_x: self.normal(ParamId::XYPadX),
+            xy_pad_y: self.normal(ParamId::XYPadY),
+            slider_value: self.slider_value,
+            button_id: self.button_id,
+            midi_bindings: Some(
+                self.midi_bindings
+                    .iter()
+                    .map(|(&cc, binding)| (cc, binding.param))
+                    .collect(),
+            ),
+            ramp: self.normal(ParamId::RampCurve),
+            knob_mod: self.normal(ParamId::KnobMod),
+            amplitude_taper: self.amplitude_taper,
+            phase_invert: self.phase_invert,
+            xy_pads: self
+                .xy_pads()
+                .iter()
+                .map(|&(x, y)| (x.as_f32(), y.as_f32()))
+                .collect(),
+            xy_pad_tab: self.xy_pad_tab as u8,
+            lfo_rate: self.lfo.rate(),
+            lfo_depth: self.lfo_depth,
+            lfo_shape: self.lfo.shape(),
+            band_low: Some(self.normal(ParamId::BandLow)),
+            band_high: Some(self.normal(ParamId::BandHigh)),
+            smoothing: Some(self.normal(ParamId::Smoothing)),
+            midi_modes: self
+                .midi_bindings
+                .iter()
+                .filter(|(_, binding)| binding.mode != CcMode::Absolute)
+                .map(|(&cc, binding)| (cc, binding.mode))
+                .collect(),
+        }
+    }
+
+    // Applies `preset`, returning the names of what it held outside the live
+    // ranges and limits, which were clamped into them.
+    fn apply_preset(&mut self, preset: &Preset) -> Vec<&'static str> {
+        self.morph_params(preset);
+        self.apply_preset_settings(preset);
+
+        self.clamped(preset)
+    }
+
+    // What `preset` holds outside the live ranges and limits, such as a
+    // preset saved under a config with a wider range. Checked after the
+    // preset's gain taper is set, so the gain is against its own.
+    fn clamped(&self, preset: &Preset) -> Vec<&'static str> {
+        let mut clamped: Vec<_> = preset_values(preset)
+            .into_iter()
+            .filter(|&(id, value)| self.param(id).is_out_of_range(value))
+            .map(|(id, _)| self.param(id).name)
+            .collect();
+
+        let outside = |normal: f32| !(0.0..=1.0).contains(&normal);
+        if preset
+            .xy_pads
+            .iter()
+            .any(|&(x, y)| outside(x) || outside(y))
+        {
+            clamped.push("XY pads");
+        }
+        if RATES.clamp(preset.lfo_rate) != preset.lfo_rate {
+            clamped.push("LFO rate");
+        }
+        if outside(preset.lfo_depth) {
+            clamped.push("LFO depth");
+        }
+
+        clamped
+    }
+
+    // Sets what a preset holds besides the parameters.
+    fn apply_preset_settings(&mut self, preset: &Preset) {
+        self.slider_value = preset.slider_value;
+        self.button_id = preset.button_id;
+        if let Some(bindings) = &preset.midi_bindings {
+            self.midi_bindings = bindings
+                .iter()
+                .map(|(&cc, &param)| {
+                    let mode = preset.midi_modes.get(&cc).copied().unwrap_or_default();
+                    (cc, Binding { param, mode })
+                })
+                .collect();
+        }
+        self.lfo.set_rate(RATES.clamp(preset.lfo_rate));
+        self.lfo.set_shape(preset.lfo_shape);
+        self.set_lfo_depth(preset.lfo_depth.clamp(0.0, 1.0));
+    }
+
+    // Swings every modulated parameter by `depth`, and those modulated
+    // from now on.
+    fn set_lfo_depth(&mut self, depth: f32) {
+        self.lfo_depth = depth;
+        for id in self.modulated() {
+            self.param_mut(id).mod_source = Some(ModSource::Lfo { depth });
+            self.output(id);
+        }
+    }
+
+    // Opens on a preset given at launch. It's where the session starts
+    // rather than an edit, so it's applied at once and leaves nothing to
+    // undo, auto-save or count.
+    fn start_from_preset(&mut self, path: &Path, preset: &Preset) {
+        self.apply_params(preset);
+        self.apply_preset_settings(preset);
+        self.history = History::default();
+        self.edited = None;
+        self.stats.reset();
+        self.saved = Some(preset.clone());
+
+        let status = format!("Loaded preset from {}", path.display());
+        self.log
+            .push(status + &clamp_warning(&self.clamped(preset)), false);
+    }
+
+    // Applies the preset `step` moves the selected bank to.
+    fn apply_bank_preset(&mut self, step: impl FnOnce(&mut PresetBank) -> Option<(&str, &Preset)>) {
+        let Some(bank) = &mut self.bank else {
+            return;
+        };
+
+        let skipped = bank.skipped();
+        let Some((_, preset)) = step(bank) else {
+            let status = format!("Bank {} has no presets", bank.name());
+            return self.set_status(status);
+        };
+
+        let preset = preset.clone();
+        let clamped = self.apply_preset(&preset);
+
+        let mut status = format!("Loaded {}", self.bank_label());
+        if skipped > 0 {
+            status += &format!(" ({skipped} unreadable presets skipped)");
+        }
+        self.set_status(status + &clamp_warning(&clamped));
+    }
+
+    // The CC bound to the last touched parameter, if any.
+    fn touched_binding(&self) -> Option<(u8, Binding)> {
+        let id = self.last_touched?;
+
+        self.midi_bindings
+            .iter()
+            .find(|(_, binding)| binding.param == id)
+            .map(|(&cc, &binding)| (cc, binding))
+    }
+
+    fn toggle_cc_mode(&mut self) {
+        let Some((cc, binding)) = self.touched_binding() else {
+            return self.set_status("Touch a widget bound to a controller first");
+        };
+
+        let mode = match binding.mode {
+            CcMode::Absolute => CcMode::Relative,
+            CcMode::Relative => CcMode::Absolute,
+        };
+        self.midi_bindings.insert(cc, Binding { mode, ..binding });
+
+        let name = self.param(binding.param).name;
+        self.set_status(match mode {
+            CcMode::Absolute => format!("CC {cc} ({name}) is now absolute"),
+            CcMode::Relative => format!("CC {cc} ({name}) is now relative"),
+        });
+    }
+
+    // The horizontal slider's normal `steps` whole steps on, within its
+    // range and limits, or `None` if it's already as far as it goes.
+    fn int_stepped(&self, steps: i32) -> Option<Normal> {
+        let id = ParamId::HSliderInt;
+        let param = self.param(id);
+        let current = param.normal_param.value;
+        let normal = self.constrained(id, param.range.stepped(current, steps, NUDGE_STEP));
+
+        (normal != current).then_some(normal)
+    }
+
+    // A stepper button for the horizontal slider, disabled at the end it
+    // steps toward.
+    fn int_step_button<'a>(&self, label: &'a str, steps: i32) -> Element<'a, Message> {
+        let button = button(text(label).size(12)).padding([0, 6]);
+        match self.int_stepped(steps) {
+            Some(_) => button.on_press(Message::IntStep(steps)).into(),
+            None => button.into(),
+        }
+    }
+
+    // Shows the mode of the last touched parameter's binding, and switches
+    // it. Inactive while that parameter has no binding.
+    fn cc_mode_button<'a>(&self) -> Element<'a, Message> {
+        let binding = self.touched_binding();
+        let label = match binding {
+            Some((
+                _,
+                Binding {
+                    mode: CcMode::Relative,
+                    ..
+                },
+            )) => "Relative CC",
+            _ => "Absolute CC",
+        };
+
+        let button = button(text(label));
+        match binding {
+            Some(_) => button.on_press(Message::ToggleCcMode).into(),
+            None => button.into(),
+        }
+    }
+
+    // The selected bank and preset, like "Pads / Warm (2/5)".
+    fn bank_label(&self) -> String {
+        match &self.bank {
+            Some(bank) => match bank.current() {
+                Some((name, _)) => format!(
+                    "{} / {name} ({}/{})",
+                    bank.name(),
+                    bank.position(),
+                    bank.len()
+                ),
+                None => format!("{} / empty", bank.name()),
+            },
+            None if self.banks.is_empty() => format!("No banks in {BANKS_PATH}/"),
+            None => "No bank selected".into(),
+        }
+    }
+
+    // Sets just the parameters from a preset, as the A/B compare does.
+    fn apply_params(&mut self, preset: &Preset) {
+        // The preset's normal for the slider is in its own taper's range.
+        self.set_gain_taper(preset.amplitude_taper);
+        self.set_phase_invert(preset.phase_invert);
+        self.load_xy_pads(preset);
+
+        // Loading a preset can be undone in one go.
+        self.set_params(&preset_params(preset));
+    }
+
+    // Sets the parameters from a preset over the morph time, or straight
+    // away if that's zero. The whole morph is one undo entry, recorded as it
+    // starts; anything else setting a parameter takes it out of the morph.
+    fn morph_params(&mut self, preset: &Preset) {
+        let duration = Duration::from_millis(self.prefs.morph_time);
+        if duration.is_zero() {
+            self.morph = None;
+            return self.apply_params(preset);
+        }
+
+        self.set_gain_taper(preset.amplitude_taper);
+        self.set_phase_invert(preset.phase_invert);
+        self.load_xy_pads(preset);
+
+        let grouped = !self.history.is_open();
+        if grouped {
+            self.history.begin();
+        }
+
+        // The band's ends are held on their sides of each other's targets
+        // rather than of where they are now, which both may be leaving.
+        let values = ordered_band(preset_params(preset))
+            .into_iter()
+            .map(|(id, normal)| {
+                let from = self.param(id).normal_param.value;
+                let to = match id {
+                    ParamId::BandLow | ParamId::BandHigh => self.param(id).limited(normal),
+                    _ => self.constrained(id, normal),
+                };
+                if from != to {
+                    self.history.record(id, from);
+                }
+                (id, from, to)
+            })
+            .collect();
+
+        if grouped {
+            self.history.end();
+        }
+
+        self.morph = Some(Morph::new(Instant::now(), duration, values));
+    }
+
+    // Writes the morph's values for `now`, ending it once they reach the
+    // preset's. Like automation playback, it's neither undoable nor logged
+    // frame by frame.
+    fn step_morph(&mut self, now: Instant) {
+        let Some(morph) = &self.morph else {
+            return;
+        };
+
+        let (values, done) = morph.at(now);
+        for (id, normal) in values {
+            let normal = self.param(id).range.snapped(normal);
+            self.param_mut(id).normal_param.update(normal);
+            self.param_changed(id);
+        }
+
+        self.status = None;
+        if done {
+            self.morph = None;
+        }
+    }
+
+    // The config with the current values as its defaults, as code for
+    // `Config::default`. The steps run to the slider's current maximum, and
+    // the frequency over its current span.
+    fn defaults_code(&self) -> String {
+        let mut config = self.config.clone();
+        config.h_slider.max = self.h_slider_max as f32;
+        FreqSpan(config.knob.min, config.knob.max) = self.freq_span;
+
+        for id in ParamId::ALL {
+            let param = self.param(id);
+            let value = match param.range {
+                Range::Amplitude(_) => audio::gain_to_db(param.value()),
+                _ => param.value(),
+            };
+            if let Some(section) = config.section_mut(id) {
+                // Rounded to six significant digits, so a value mapped there
+                // and back reads 1000 rather than 999.99994. Silence is as
+                // low as the slider goes.
+                let value: f32 = format!("{value:.5e}").parse().unwrap_or(value);
+                section.default = value.clamp(section.min, section.max);
+            }
+        }
+
+        config.to_code()
+    }
+
+    fn load_preset(&mut self, path: &Path) {
+        match Preset::load(path) {
+            Ok(preset) => {
+                let clamped = self.apply_preset(&preset);
+                self.saved = Some(preset);
+                self.set_status(
+                    format!("Loaded preset from {}", path.display()) + &clamp_warning(&clamped),
+                );
+            }
+            Err(error) => self.set_status(format!("Couldn't load preset: {error}")),
+        }
+    }
+
+    // The parameters whose normals differ from the preset last saved or
+    // loaded, none if there's been neither.
+    fn drifted(&self) -> Vec<ParamId> {
+        let Some(saved) = &self.saved else {
+            return Vec::new();
+        };
+
+        preset_params(saved)
+            .into_iter()
+            .filter(|&(id, normal)| self.param(id).normal_param.value != normal)
+            .map(|(id, _)| id)
+            .collect()
+    }
+
+    fn drift_label(&self) -> String {
+        match self.drifted().len() {
+            _ if self.saved.is_none() => String::new(),
+            0 => String::from("No changes since saved"),
+            1 => String::from("1 change since saved"),
+            n => format!("{n} changes since saved"),
+        }
+    }
+
+    // Saves the state to the scratch file, quietly unless that fails.
+    fn autosave(&mut self) {
+        self.edited = None;
+
+        let Some(path) = &self.scratch_path else {
+            return;
+        };
+        if let Err(error) = self.preset().save(path) {
+            self.set_status(format!("Couldn't auto-save: {error}"));
+        }
+    }
+
+    fn compare_label(&self) -> String {
+        format!("Comparing {}", if self.compare_b { "B" } else { "A" })
+    }
+
+    // Writes a new value to a parameter from any source: a widget, a typed
+    // value, MIDI, and so on. The previous value is kept for undo.
+    fn set_param(&mut self, id: ParamId, normal: Normal) {
+        // The octave knob only follows the frequency knob, so moving it moves
+        // that instead. It's kept in step, and undone, through that knob.
+        if id == ParamId::KnobOctave {
+            let octave = self.param(ParamId::KnobOctave);
+            let normal = octave.link_source(self.param(ParamId::KnobFreq), OCTAVE_RATIO, normal);
+            return self.set_param(ParamId::KnobFreq, normal);
+        }
+
+        let normal = self.constrained(id, normal);
+        if let Some(morph) = &mut self.morph {
+            morph.release(id);
+        }
+
+        let before = self.param(id).normal_param.value;
+        if before != normal {
+            self.history.record(id, before);
+        }
+
+        if id == ParamId::KnobFreq {
+            self.automation.capture(Instant::now(), normal);
+        }
+
+        self.param_mut(id).normal_param.update(normal);
+        self.param_changed(id);
+    }
+
+    // `normal` as `id` would take it: on a step, on a note if snapping,
+    // within its limit, and on its side of the other end of the band.
+    fn constrained(&self, id: ParamId, normal: Normal) -> Normal {
+        let normal = self.param(id).range.snapped(normal);
+        let normal = match id {
+            ParamId::KnobFreq if self.note_snap => self.snapped_to_note(normal),
+            _ => normal,
+        };
+        let normal = self.param(id).limited(normal);
+
+        match id {
+            ParamId::BandLow => {
+                let high = self.param(ParamId::BandHigh).normal_param.value;
+                Normal::from_clipped(normal.as_f32().min(high.as_f32()))
+            }
+            ParamId::BandHigh => {
+                let low = self.param(ParamId::BandLow).normal_param.value;
+                Normal::from_clipped(normal.as_f32().max(low.as_f32()))
+            }
+            _ => normal,
+        }
+    }
+
+    // Puts every parameter back to the default it was constructed with, as
+    // one undo entry. Unlike `set_param` the defaults are written as they
+    // are, so note snapping can't leave the knob off its default.
+    fn reset_all(&mut self) {
+        let grouped = !self.history.is_open();
+        if grouped {
+            self.history.begin();
+        }
+
+        let ids: Vec<_> = self.params.keys().copied().collect();
+        for id in ids {
+            // Set along with the frequency knob.
+            if id == ParamId::KnobOctave {
+                continue;
+            }
+
+            let NormalParam { value, default } = self.param(id).normal_param;
+            if value != default {
+                self.history.record(id, value);
+            }
+            self.param_mut(id).normal_param.update(default);
+            self.param_changed(id);
+        }
+
+        if grouped {
+            self.history.end();
+        }
+    }
+
+    // Sets every parameter but the linked octave knob to a random value as
+    // one undo entry, then moves on to a new seed. Parameters are drawn in a
+    // fixed order so a seed always gives the same result.
+    fn randomize(&mut self) {
+        let seed = self.random_seed;
+        let mut rng = ChaCha8Rng::seed_from_u64(seed);
+
+        let values: Vec<_> = ParamId::ALL
+            .into_iter()
+            .filter(|&id| id != ParamId::KnobOctave)
+            .map(|id| {
+                // Loud results are the ones that hurt, so gain leans quiet.
+                let bias = if id == ParamId::VSliderDB {
+                    RANDOM_GAIN_BIAS
+                } else {
+                    1.0
+                };
+                (id, self.param(id).random_normal(rng.gen(), bias))
+            })
+            // Locked params still draw their sample, so locking one doesn't
+            // change what a seed gives the rest.
+            .filter(|&(id, _)| !self.param(id).locked)
+            .collect();
+        let values = ordered_band(values);
+
+        self.set_params(&values);
+        self.random_seed = rng.gen();
+        self.set_status(format!("Randomized with seed {seed}"));
+    }
+
+    // Writes a value played back from the automation lane. It's neither
+    // undoable nor logged, which would flood both.
+    fn play_automation(&mut self, normal: Normal) {
+        self.param_mut(ParamId::KnobFreq)
+            .normal_param
+            .update(normal);
+        self.param_changed(ParamId::KnobFreq);
+        self.status = None;
+    }
+
+    // Moves the frequency knob by the widget's movement since its last
+    // report, accelerated by how fast that was. The widget tracks its own
+    // position while dragging, so only the differences between its reports
+    // are used.
+    fn throw_knob(&mut self, widget: Normal) {
+        let Some((then, last, thrown)) = self.knob_throw else {
+            return self.drag_knob(widget);
+        };
+
+        let delta = widget.as_f32() - last.as_f32();
+        let seconds = then.elapsed().as_secs_f32();
+        // Clamped, so throwing past an end doesn't need undoing to come back.
+        let thrown = (thrown + KNOB_ACCELERATION.apply(delta, seconds)).clamp(0.0, 1.0);
+
+        self.knob_throw = Some((Instant::now(), widget, thrown));
+        self.drag_knob(Normal::from_clipped(thrown));
+    }
+
+    // Sets the frequency knob as a drag does, pulled onto a detent when
+    // it's near one, saying so if it is. Free of the detent, the drag
+    // carries on from where the pointer is.
+    fn drag_knob(&mut self, normal: Normal) {
+        match nearest_detent(normal, &self.knob_detents, self.knob_magnetism) {
+            Some(detent) => {
+                self.set_param(ParamId::KnobFreq, detent);
+                self.set_status(format!("{} (detent)", self.describe(ParamId::KnobFreq)));
+            }
+            None => self.set_param(ParamId::KnobFreq, normal),
+        }
+    }
+
+    // Moves the XY pad, constrained to the circle if that's on.
+    fn set_xy_pad(&mut self, normal_x: Normal, normal_y: Normal) {
+        let (normal_x, normal_y) = self.circled(normal_x, normal_y);
+
+        self.set_params(&[(ParamId::XYPadX, normal_x), (ParamId::XYPadY, normal_y)]);
+    }
+
+    // Sets the XY pad as a drag does, saying so if it comes to center.
+    fn move_xy_pad(&mut self, normal_x: Normal, normal_y: Normal) {
+        let centered =
+            self.centered_status(&[(ParamId::XYPadX, normal_x), (ParamId::XYPadY, normal_y)]);
+
+        self.set_xy_pad(normal_x, normal_y);
+        if let Some(status) = centered {
+            self.set_status(status);
+        }
+    }
+
+    // Moves the XY pad a step closer to where the pointer left it, and stops
+    // once it's there.
+    fn step_xy_smoothing(&mut self) {
+        let Some((target_x, target_y)) = self.xy_target else {
+            return;
+        };
+
+        let normal_x = smooth_toward(
+            self.param(ParamId::XYPadX).normal_param.value,
+            target_x,
+            self.xy_smoothing,
+        );
+        let normal_y = smooth_toward(
+            self.param(ParamId::XYPadY).normal_param.value,
+            target_y,
+            self.xy_smoothing,
+        );
+        if (normal_x, normal_y) == (target_x, target_y) {
+            self.xy_target = None;
+        }
+        self.move_xy_pad(normal_x, normal_y);
+    }
+
+    // Jumps the XY pad the rest of the way to where the pointer left it.
+    fn settle_xy_smoothing(&mut self) {
+        if let Some((normal_x, normal_y)) = self.xy_target.take() {
+            self.move_xy_pad(normal_x, normal_y);
+        }
+    }
+
+    // Every XY pad's position, the one showing included.
+    fn xy_pads(&self) -> Vec<(Normal, Normal)> {
+        let mut pads = self.xy_pads.clone();
+        pads[self.xy_pad_tab] = (
+            self.param(ParamId::XYPadX).normal_param.value,
+            self.param(ParamId::XYPadY).normal_param.value,
+        );
+        pads
+    }
+
+    // Puts the showing XY pad away under its tab and brings out the one
+    // under `tab`. Only the pad showing changes, so this isn't undoable.
+    fn select_xy_pad(&mut self, tab: usize) {
+        if tab == self.xy_pad_tab || tab >= XY_PAD_COUNT {
+            return;
+        }
+
+        self.settle_xy_smoothing();
+        self.xy_pads = self.xy_pads();
+        self.xy_pad_tab = tab;
+
+        let (normal_x, normal_y) = self.xy_pads[tab];
+        for (id, normal) in [(ParamId::XYPadX, normal_x), (ParamId::XYPadY, normal_y)] {
+            self.value_drafts.remove(&id);
+            self.param_mut(id).normal_param.update(normal);
+            self.param_changed(id);
+        }
+        self.hover = None;
+        self.set_status(format!("Showing XY pad {}", tab + 1));
+    }
+
+    // Takes the pads under the other tabs from a preset, and which one
+    // shows. The showing pad is then set like any other parameter. Older
+    // presets have only the one pad, which goes under the current tab.
+    fn load_xy_pads(&mut self, preset: &Preset) {
+        if preset.xy_pads.is_empty() {
+            return;
+        }
+
+        for (pad, &(x, y)) in self.xy_pads.iter_mut().zip(&preset.xy_pads) {
+            *pad = (Normal::from_clipped(x), Normal::from_clipped(y));
+        }
+        self.settle_xy_smoothing();
+        self.xy_pad_tab = usize::from(preset.xy_pad_tab).min(XY_PAD_COUNT - 1);
+        self.hover = None;
+    }
+
+    // A button for each XY pad, the one showing highlighted.
+    fn xy_pad_tabs(&self, spacing: u16) -> Element<'_, Message> {
+        let tabs = (0..XY_PAD_COUNT).map(|tab| {
+            button(text(tab + 1))
+                .style(if tab == self.xy_pad_tab {
+                    theme::Button::Primary
+                } else {
+                    theme::Button::Secondary
+                })
+                .on_press(Message::SelectXYPad(tab))
+                .into()
+        });
+
+        Row::with_children(tabs.collect()).spacing(spacing).into()
+    }
+
+    // The XY pad position pulled inside the circle, if it's limited to one.
+    fn circled(&self, normal_x: Normal, normal_y: Normal) -> (Normal, Normal) {
+        if self.xy_circle {
+            let range = self.param(ParamId::XYPadX).range;
+            let (x, y) = clamp_magnitude(
+                range.unmap_to_value(normal_x),
+                range.unmap_to_value(normal_y),
+                range.unmap_to_value(Normal::MAX),
+            );
+
+            (range.map_to_normal(x), range.map_to_normal(y))
+        } else {
+            (normal_x, normal_y)
+        }
+    }
+
+    // `normal` pulled to center if the parameter is bipolar and it's within
+    // the detent.
+    fn center_detent(&self, id: ParamId, normal: Normal) -> Normal {
+        let param = self.param(id);
+        if !param.is_bipolar() {
+            return normal;
+        }
+
+        let center = param.range.map_to_normal(0.0);
+        center_detent(normal, center, self.prefs.center_detent)
+    }
+
+    // Names the parameters that `values` bring to center from somewhere
+    // else, like "x and y centered". `None` if there are none.
+    fn centered_status(&self, values: &[(ParamId, Normal)]) -> Option<String> {
+        let names: Vec<_> = values
+            .iter()
+            .filter(|&&(id, normal)| {
+                let param = self.param(id);
+                let center = param.range.map_to_normal(0.0);
+                param.is_bipolar() && normal == center && param.normal_param.value != center
+            })
+            .map(|&(id, _)| self.param(id).name)
+            .collect();
+
+        (!names.is_empty()).then(|| format!("{} centered", names.join(" and ")))
+    }
+
+    // Sets the frequency knob to a MIDI note, tuned to the reference pitch.
+    // A note beyond the knob's range stops at its end.
+    fn set_note(&mut self, note: i32) {
+        let frequency = tuning::note_frequency(note, self.reference_pitch);
+        let range = self.param(ParamId::KnobFreq).range;
+        self.set_param(ParamId::KnobFreq, range.map_to_normal(frequency));
+
+        let knob = self.param(ParamId::KnobFreq);
+        let name = tuning::note_name(note);
+        let resolved = knob.formatted();
+        self.set_status(if (knob.value() / frequency - 1.0).abs() > 1e-3 {
+            format!("{name} is out of range, set to {resolved}")
+        } else {
+            format!("{name}: {resolved}")
+        });
+    }
+
+    // The normal of the note nearest the frequency at `normal`.
+    fn snapped_to_note(&self, normal: Normal) -> Normal {
+        let range = self.param(ParamId::KnobFreq).range;
+        let note = tuning::nearest_note(range.unmap_to_value(normal), self.reference_pitch);
+
+        range.map_to_normal(tuning::note_frequency(note, self.reference_pitch))
+    }
+
+    // Sets several parameters as a single undo entry, unless a gesture's
+    // entry is already open.
+    fn set_params(&mut self, values: &[(ParamId, Normal)]) {
+        let grouped = !self.history.is_open();
+        if grouped {
+            self.history.begin();
+        }
+
+        // A band moving wholly above its old top has to move its top first,
+        // or the bottom would be held under the old top.
+        let low = values.iter().find(|&&(id, _)| id == ParamId::BandLow);
+        let high = self.param(ParamId::BandHigh).normal_param.value;
+        let mut values = values.to_vec();
+        if low.is_some_and(|&(_, low)| low.as_f32() > high.as_f32()) {
+            values.sort_by_key(|&(id, _)| id != ParamId::BandHigh);
+        }
+
+        for (id, normal) in values {
+            self.set_param(id, normal);
+        }
+
+        if grouped {
+            self.history.end();
+        }
+    }
+
+    // The values the params in `entry` have now, to undo restoring it.
+    fn current_values(&self, entry: &[(ParamId, Normal)]) -> Vec<(ParamId, Normal)> {
+        entry
+            .iter()
+            .map(|&(id, _)| (id, self.param(id).normal_param.value))
+            .collect()
+    }
+
+    // Puts back the values from an undo or redo entry without recording it.
+    fn restore(&mut self, entry: &[(ParamId, Normal)], action: &str) {
+        for &(id, normal) in entry {
+            if let Some(morph) = &mut self.morph {
+                morph.release(id);
+            }
+            self.param_mut(id).normal_param.update(normal);
+            self.param_changed(id);
+        }
+
+        if let Some(&(id, _)) = entry.last() {
+            self.set_status(format!("{action}: {}", self.describe(id)));
+        }
+    }
+
+    // Called after any change to a parameter's value.
+    fn param_changed(&mut self, id: ParamId) {
+        self.edited = Some(Instant::now());
+        self.stats.record(id, self.param(id).value());
+        self.value_drafts.remove(&id);
+        self.set_status(self.describe(id));
+
+        if let ParamId::KnobFreq | ParamId::KnobMod = id {
+            self.knob_mod_range = knob_mod_range(
+                self.param(ParamId::KnobFreq).normal_param.value,
+                self.param(ParamId::KnobMod).value(),
+            );
+        }
+
+        if id == ParamId::HSliderInt {
+            let step = self.param(id).value() as i32;
+
+            if step != self.last_step {
+                self.last_step = step;
+                self.detent();
+            }
+        }
+
+        if id == ParamId::KnobFreq {
+            let octave = self.param(ParamId::KnobOctave);
+            let normal = octave.linked_normal(self.param(id), OCTAVE_RATIO);
+            self.param_mut(ParamId::KnobOctave)
+                .normal_param
+                .update(normal);
+            self.value_drafts.remove(&ParamId::KnobOctave);
+            self.send_osc(ParamId::KnobOctave);
+            self.notify(ParamId::KnobOctave);
+        }
+
+        self.output(id);
+    }
+
+    // Passes `id`'s live value on to OSC, the embedding program and the
+    // audio.
+    fn output(&mut self, id: ParamId) {
+        self.send_osc(id);
+        self.notify(id);
+
+        let live = self.live_param(id);
+        match id {
+            ParamId::KnobFreq => self.audio_shared.set_frequency(live.value()),
+            ParamId::VSliderDB => self.audio_shared.set_gain(param_gain(&live)),
+            ParamId::Smoothing => self.audio_shared.set_smoothing(live.value() / 1000.0),
+            ParamId::XYPadX => self.audio_shared.set_pan(param_pan(&live)),
+            _ => {}
+        }
+    }
+
+    // `id` as it's used downstream: moved by the LFO if it's modulated,
+    // though its widget stays where it's set.
+    fn live_param(&self, id: ParamId) -> Param {
+        let mut live = self.param(id).clone();
+        live.normal_param.value = live.modulated(self.lfo.value());
+
+        live
+    }
+
+    // Where the LFO has `id`, if it's modulated.
+    fn live_normal(&self, id: ParamId) -> Option<Normal> {
+        let param = self.param(id);
+
+        param.mod_source.map(|_| param.modulated(self.lfo.value()))
+    }
+
+    // The parameters the LFO is modulating.
+    fn modulated(&self) -> Vec<ParamId> {
+        ParamId::ALL
+            .into_iter()
+            .filter(|&id| self.param(id).mod_source.is_some())
+            .collect()
+    }
+
+    // Swaps the vertical slider's range, keeping its gain as near as the
+    // new range allows. Not undoable: the range isn't part of the history.
+    fn set_gain_taper(&mut self, amplitude_taper: bool) {
+        if amplitude_taper == self.amplitude_taper {
+            return;
+        }
+
+        let gain = param_gain(self.param(ParamId::VSliderDB));
+        let mut param = gain_param(&self.config, amplitude_taper);
+        let value = match param.range {
+            Range::Amplitude(_) => gain,
+            _ => audio::gain_to_db(gain),
+        };
+        param.normal_param.value = param.limited(param.range.map_to_normal(value));
+
+        self.amplitude_taper = amplitude_taper;
+        param.disabled = self.param(ParamId::VSliderDB).disabled;
+        param.locked = self.param(ParamId::VSliderDB).locked;
+        param.template = self.param(ParamId::VSliderDB).template;
+        param.mod_source = self.param(ParamId::VSliderDB).mod_source;
+        *self.param_mut(ParamId::VSliderDB) = param;
+        self.param_changed(ParamId::VSliderDB);
+    }
+
+    // Flips the phase of what goes out, and resends the frequency with its
+    // new sign. Not undoable, like the taper.
+    fn set_phase_invert(&mut self, phase_invert: bool) {
+        if phase_invert == self.phase_invert {
+            return;
+        }
+
+        self.phase_invert = phase_invert;
+        self.audio_shared.set_invert(phase_invert);
+        self.param_changed(ParamId::KnobFreq);
+    }
+
+    // Sets the status this `update` reports. Only the last one set is
+    // logged, so a change that goes through `param_changed` can still
+    // replace it with something more specific.
+    fn set_status(&mut self, status: impl Into<String>) {
+        self.status = Some(status.into());
+    }
+
+    fn flush_status(&mut self) {
+        if let Some(status) = self.status.take() {
+            self.status_since = Some(Instant::now());
+            self.status_idle = false;
+            // A drag logs one line rather than one per step.
+            self.log.push(status, self.history.is_open());
+        }
+    }
+
+    // The latest status, or a neutral prompt once it's been left long
+    // enough to clear.
+    fn status_line(&self) -> &str {
+        if self.status_idle && self.prefs.status_clears {
+            IDLE_STATUS
+        } else {
+            self.log.latest().unwrap_or_default()
+        }
+    }
+
+    // Record, Play and Stop follow the lane's state: only the ones that
+    // make sense now are enabled.
+    fn automation_controls(&self) -> Element<'_, Message> {
+        use automation::State;
+
+        let state = self.automation.state();
+        let record = button(text("Record"));
+        let play = button(text("Play"));
+        let stop = button(text("Stop"));
+        let clear = button(text("Clear"));
+        // Left enabled with nothing recorded, to say so.
+        let export = button(text("Export")).on_press(Message::ExportAutomation);
+
+        let (record, play, stop) = match state {
+            State::Idle if self.automation.is_empty() => {
+                (record.on_press(Message::RecordAutomation), play, stop)
+            }
+            State::Idle => (
+                record.on_press(Message::RecordAutomation),
+                play.on_press(Message::PlayAutomation),
+                stop,
+            ),
+            State::Recording(_) | State::Playing(_) => {
+                (record, play, stop.on_press(Message::StopAutomation))
+            }
+        };
+
+        let clear = if state == State::Idle && !self.automation.is_empty() {
+            clear.on_press(Message::ClearAutomation)
+        } else {
+            clear
+        };
+
+        row![record, play, stop, clear, export].spacing(10).into()
+    }
+
+    fn log_panel(&self) -> Element<'_, Message> {
+        let lines = self
+            .log
+            .lines()
+            .map(|line| text(line).size(14).into())
+            .collect();
+
+        column![
+            scrollable(Column::with_children(lines).width(Length::Fill))
+                .height(Length::Fixed(LOG_PANEL_HEIGHT)),
+            button(text("Clear Log")).on_press(Message::ClearLog),
+        ]
+        .spacing(10)
+        .into()
+    }
+
+    // Each parameter's changes and the lowest and highest values it reached
+    // this session, behind a button that shows or hides them.
+    fn stats_panel(&self) -> Element<'_, Message> {
+        let toggle = button(text(if self.stats_open {
+            "Hide Stats"
+        } else {
+            "Show Stats"
+        }))
+        .on_press(Message::ToggleStats);
+        if !self.stats_open {
+            return toggle.into();
+        }
+
+        let lines: Vec<_> = self
+            .stats
+            .iter()
+            .map(|(id, usage)| {
+                let param = self.param(id);
+                let amount = |value| format_amount(&param.range, value, param.decimals);
+
+                text(format!(
+                    "{}: {} {}, {} to {}",
+                    param.name,
+                    usage.changes,
+                    if usage.changes == 1 {
+                        "change"
+                    } else {
+                        "changes"
+                    },
+                    amount(usage.min),
+                    amount(usage.max),
+                ))
+                .size(14)
+                .into()
+            })
+            .collect();
+        let lines: Element<_> = if lines.is_empty() {
+            text("Nothing changed yet").size(14).into()
+        } else {
+            Column::with_children(lines).into()
+        };
+
+        column![
+            row![
+                toggle,
+                button(text("Reset Stats")).on_press(Message::ResetStats)
+            ]
+            .spacing(10),
+            lines,
+        ]
+        .spacing(10)
+        .into()
+    }
+
+    // The keys that make a drag fine. In fine mode that's none at all, which
+    // the widgets take as always held, so Ctrl changes nothing more.
+    fn fine_drag_keys(&self) -> keyboard::Modifiers {
+        if self.fine_mode {
+            keyboard::Modifiers::empty()
+        } else {
+            FINE_DRAG_KEYS
+        }
+    }
+
+    // The parameter widgets' style sheet, with any tick style set.
+    fn themed(&self) -> style::Themed {
+        style::Themed {
+            ticks: self.tick_style,
+        }
+    }
+
+    // How far a knob turns per pixel dragged, as a normal.
+    fn knob_scalar(&self) -> f32 {
+        1.0 / self.prefs.knob_travel
+    }
+
+    // How large the knobs are drawn: smaller in compact mode.
+    fn knob_length(&self) -> Length {
+        if self.compact {
+            Length::Fixed(self.knob_size * COMPACT_KNOB_SCALE)
+        } else {
+            Length::Fixed(self.knob_size)
+        }
+    }
+
+    fn knob_marks(&self) -> &tick_marks::Group {
+        if self.knob_marks_are_dense {
+            &self.knob_marks_dense
+        } else {
+            &self.knob_marks_sparse
+        }
+    }
+
+    // Sends `id`'s value over OSC, unless the throttle holds it back for
+    // the next tick.
+    fn send_osc(&mut self, id: ParamId) {
+        let Some(osc) = &self.osc else {
+            return;
+        };
+
+        let value = self.sent_value(id);
+        if let Some(value) = self.osc_throttle.offer(id, value, Instant::now()) {
+            osc.send(id, value);
+        }
+    }
+
+    // Sends every value the throttle is holding back.
+    fn flush_osc(&mut self) {
+        for (id, value) in self.osc_throttle.flush(Instant::now()) {
+            if let Some(osc) = &self.osc {
+                osc.send(id, value);
+            }
+        }
+    }
+
+    // Tells the embedding program `id` has changed, if it asked to know.
+    fn notify(&self, id: ParamId) {
+        if let Some(on_change) = &self.on_change {
+            on_change(id, self.sent_value(id));
+        }
+    }
+
+    // The value other programs are given for `id`: its live one, except the
+    // frequency is negated while the phase is inverted.
+    fn sent_value(&self, id: ParamId) -> f32 {
+        let value = self.live_param(id).value();
+
+        if id == ParamId::KnobFreq && self.phase_invert {
+            -value
+        } else {
+            value
+        }
+    }
+
+    // Takes the output peak for the meter: a louder peak shows straight
+    // away, a quieter one lets the meter fall at a steady rate.
+    fn read_meter(&mut self, now: Instant) {
+        let elapsed = now.duration_since(self.meter_read).as_secs_f32();
+        self.meter_read = now;
+
+        let decay = audio::db_to_gain(-METER_DECAY_DB_PER_SECOND * elapsed);
+        self.meter_level = (self.meter_level * decay).max(self.audio_shared.take_peak());
+
+        if self.audio_shared.take_limiting() {
+            self.limiting = Some(now);
+        } else if let Some(since) = self.limiting {
+            if now.duration_since(since) >= LIMITING_HOLD {
+                self.limiting = None;
+            }
+        }
+    }
+
+    // Feedback for the horizontal slider crossing a step.
+    fn detent(&mut self) {
+        self.detent_flash = Some(Instant::now());
+
+        if self.detent_click {
+            self.audio_shared.click();
+        }
+    }
+
+    // Retrieve the value by mapping the normalized value of the parameter
+    // to the corresponding range, formatted for the log. With raw normals
+    // showing, the normals behind it follow.
+    fn describe(&self, id: ParamId) -> String {
+        let described = self.describe_value(id);
+
+        if !self.show_raw {
+            return described;
+        }
+
+        let ids: &[ParamId] = match id {
+            ParamId::KnobFreq | ParamId::KnobMod => &[ParamId::KnobFreq, ParamId::KnobMod],
+            ParamId::XYPadX | ParamId::XYPadY => &[ParamId::XYPadX, ParamId::XYPadY],
+            ParamId::BandLow | ParamId::BandHigh => &[ParamId::BandLow, ParamId::BandHigh],
+            _ => std::slice::from_ref(&id),
+        };
+        let normals: Vec<_> = ids.iter().map(|&id| raw_normal(self.normal(id))).collect();
+
+        format!("{described} [{}]", normals.join(", "))
+    }
+
+    fn describe_value(&self, id: ParamId) -> String {
+        match id {
+            ParamId::KnobFreq | ParamId::KnobMod => {
+                let knob = self.param(ParamId::KnobFreq);
+                let depth = self.param(ParamId::KnobMod).label();
+
+                let pitch = tuning::pitch_label(knob.value(), self.reference_pitch);
+                let label = format!("{} ({pitch}), {depth}", knob.label());
+
+                if self.phase_invert {
+                    format!("{label}, inverted")
+                } else {
+                    label
+                }
+            }
+            ParamId::XYPadX | ParamId::XYPadY => {
+                let x = self.param(ParamId::XYPadX);
+                let y = self.param(ParamId::XYPadY);
+                let position = format!(
+                    "XYPadFloat: {}, {}, Pan {}",
+                    x.label(),
+                    y.label(),
+                    audio::pan_label(param_pan(x))
+                );
+
+                if self.xy_circle {
+                    let magnitude = x.value().hypot(y.value());
+                    let angle = y.value().atan2(x.value()).to_degrees();
+                    format!("{position} ({magnitude:.2} at {angle:.0}°)")
+                } else {
+                    position
+                }
+            }
+            ParamId::BandLow | ParamId::BandHigh => {
+                let low = self.param(ParamId::BandLow);
+                let high = self.param(ParamId::BandHigh);
+                let width = format_amount(&high.range, high.value() - low.value(), high.decimals);
+
+                format!("{}, {}, width {width}", low.label(), high.label())
+            }
+            id => self.param(id).label(),
+        }
+    }
+
+    fn nudge_focused(&mut self, steps: i32, vertical: bool) {
+        if let Some(focus) = self.focused {
+            let id = focus.param(vertical);
+            if self.param(id).disabled || self.param(id).locked {
+                return;
+            }
+
+            self.last_touched = Some(id);
+
+            let param = self.param(id);
+            let normal = param
+                .range
+                .stepped(param.normal_param.value, steps, NUDGE_STEP);
+            self.set_param(id, normal);
+        }
+    }
+
+    // Rebuilds the horizontal slider's range to end at `max`, moving its
+    // value and marks along with it.
+    fn set_h_slider_max(&mut self, max: i32) {
+        let min = self.config.h_slider.min as i32;
+        if max <= min || max == self.h_slider_max {
+            return;
+        }
+
+        self.h_slider_max = max;
+        let id = ParamId::HSliderInt;
+        self.param_mut(id)
+            .set_range(Range::Int(IntRange::new(min, max)));
+        (self.h_slider_tick_marks, self.h_slider_text_marks) =
+            step_marks(min..=max, step_labels(self.window_width));
+
+        // The step moved with the range, so there's nothing to click for.
+        self.last_step = self.param(id).value() as i32;
+        self.param_changed(id);
+    }
+
+    // Switches the frequency knob, and the octave knob with it, to `span`.
+    // The frequency stays where it was if it's inside, and goes to the
+    // nearer end if not. Like the slider's steps, this isn't undoable.
+    fn set_freq_span(&mut self, span: FreqSpan) {
+        if span == self.freq_span || span.0 <= 0.0 || span.0 >= span.1 {
+            return;
+        }
+
+        self.freq_span = span;
+        let range = FreqRange::new(span.0, span.1);
+        (self.knob_marks_sparse, self.knob_marks_dense) = knob_marks(&range);
+
+        let id = ParamId::KnobFreq;
+        let frequency = self.param(id).value();
+        let inside = frequency.clamp(span.0, span.1);
+        for id in [ParamId::KnobFreq, ParamId::KnobOctave] {
+            self.param_mut(id).set_range(Range::Freq(range));
+        }
+        self.param_mut(id)
+            .normal_param
+            .update(range.map_to_normal(inside));
+        self.value_drafts.remove(&id);
+        self.knob_throw = None;
+
+        self.param_changed(id);
+        let moved = if inside == frequency {
+            String::new()
+        } else {
+            format!(", moving the frequency to {}", self.param(id).formatted())
+        };
+        self.set_status(format!("Frequency span {span}{moved}"));
+    }
+
+    // The spans the frequency knob can be switched to: the configured one
+    // and the usual others.
+    fn freq_spans(&self) -> Vec<FreqSpan> {
+        let mut spans = vec![FreqSpan(self.config.knob.min, self.config.knob.max)];
+        for span in FREQ_SPANS {
+            if !spans.contains(&span) {
+                spans.push(span);
+            }
+        }
+        spans
+    }
+
+    // The tops the horizontal slider's range can be switched to. All of
+    // them lie above the configured minimum, and the configured maximum is
+    // always one.
+    fn h_slider_maxima(&self) -> Vec<i32> {
+        let min = self.config.h_slider.min as i32;
+        let mut maxima: Vec<_> = H_SLIDER_MAXIMA
+            .into_iter()
+            .chain([self.config.h_slider.max as i32])
+            .filter(|&max| max > min)
+            .collect();
+        maxima.sort_unstable();
+        maxima.dedup();
+
+        maxima
+    }
+
+    fn save_prefs(&mut self) {
+        if let Err(error) = self.prefs.save(Path::new(PREFS_PATH)) {
+            self.set_status(format!("Couldn't save preferences: {error}"));
+        }
+    }
+
+    // Shows the values of `ids` by the cursor while any of them is being
+    // dragged, unless drag tooltips are turned off.
+    fn drag_tooltip<'a>(
+        &self,
+        content: impl Into<Element<'a, Message>>,
+        ids: &[ParamId],
+    ) -> Element<'a, Message> {
+        if self.is_disabled(ids) {
+            return Disabled::new(content).into();
+        }
+
+        let popup = Popup::new(content).follow_cursor(DRAG_TOOLTIP_OFFSET);
+
+        let dragged = ids.iter().any(|id| self.drags.contains(id));
+        let label = if self.prefs.drag_tooltips && dragged {
+            let values: Vec<_> = ids
+                .iter()
+                .map(|&id| {
+                    let formatted = self.param(id).formatted();
+                    if self.show_raw {
+                        format!("{formatted} [{}]", raw_normal(self.normal(id)))
+                    } else {
+                        formatted
+                    }
+                })
+                .collect();
+            values.join(", ")
+        } else if let Some(preview) = ids.iter().find_map(|&id| self.hover_preview(id)) {
+            preview
+        } else {
+            return popup.into();
+        };
+
+        popup
+            .open(
+                container(text(label).size(16))
+                    .padding([2, 6])
+                    .style(theme::Container::Box),
+            )
+            .into()
+    }
+
+    // Where the LFO is and each modulated parameter's live value, like
+    // "LFO +0.71: Gain ~ -3.2 dB", as it moves them.
+    fn modulation_label(&self) -> String {
+        let live: Vec<_> = self
+            .modulated()
+            .into_iter()
+            .map(|id| {
+                let live = self.live_param(id);
+                let value = format_value(&live.range, live.normal_param.value, live.decimals);
+
+                format!("{} ~ {value}", live.name)
+            })
+            .collect();
+
+        if live.is_empty() {
+            String::from("Nothing modulated")
+        } else {
+            format!("LFO {:+.2}: {}", self.lfo.value(), live.join(", "))
+        }
+    }
+
+    // The value hovering over `id`'s track would set, if it's hovered.
+    fn hovered(&self, id: ParamId) -> Option<Normal> {
+        self.hover
+            .filter(|&(hovered, _)| hovered == id)
+            .map(|(_, normal)| normal)
+    }
+
+    // What clicking where `id` is hovered would set it to, like "→ 6.0 dB".
+    fn hover_preview(&self, id: ParamId) -> Option<String> {
+        let normal = self.hovered(id)?;
+        let param = self.param(id);
+
+        let value = format_value(&param.range, normal, param.decimals);
+
+        Some(if self.show_raw {
+            format!("→ {value} [{}]", raw_normal(normal.as_f32()))
+        } else {
+            format!("→ {value}")
+        })
+    }
+
+    // Surrounds a parameter widget with its focus ring, drag tooltip and
+    // context menu. A disabled one is only greyed out, and a locked one shows
+    // a padlock and can't be dragged.
+    fn frame<'a>(
+        &self,
+        content: impl Into<Element<'a, Message>>,
+        focus: Focus,
+    ) -> Element<'a, Message> {
+        if self.is_disabled(focus.params()) {
+            return Disabled::new(self.focus_frame(content, focus)).into();
+        }
+
+        let content: Element<_> = if self.is_locked(focus.params()) {
+            Locked::new(self.focus_frame(content, focus)).into()
+        } else {
+            self.drag_tooltip(self.focus_frame(content, focus), focus.params())
+        };
+        // Compact mode hides the text marks, so the value shows on hover.
+        let content: Element<_> = if self.compact {
+            tooltip(content, self.describe(focus.param(false)), Position::Top)
+                .style(theme::Container::Box)
+                .into()
+        } else {
+            content
+        };
+        let content: Element<_> = if self.soloed.is_some() && !self.is_soloed(focus) {
+            Dimmed::new(content).into()
+        } else {
+            content
+        };
+        let popup = Popup::new(content)
+            .on_right_click(Message::OpenMenu(focus))
+            .on_dismiss(Message::CloseMenu);
+
+        if self.context_menu == Some(focus) {
+            popup.open(self.context_menu(focus)).into()
+        } else {
+            popup.into()
+        }
+    }
+
+    fn context_menu<'a>(&self, focus: Focus) -> Element<'a, Message> {
+        let item = |label| button(text(label)).width(Length::Fill);
+        let paste = item("Paste value");
+        let paste = if self.copied.is_empty() {
+            paste
+        } else {
+            paste.on_press(Message::MenuPaste(focus))
+        };
+
+        container(
+            column![
+                item("Reset to default").on_press(Message::MenuReset(focus)),
+                item("Copy value").on_press(Message::MenuCopy(focus)),
+                paste,
+                item(if self.is_locked(focus.params()) {
+                    "Unlock"
+                } else {
+                    "Lock"
+                })
+                .on_press(Message::ToggleLock(focus)),
+                item(if self.is_modulated(focus.params()) {
+                    "Stop modulating"
+                } else {
+                    "Modulate"
+                })
+                .on_press(Message::ToggleLfo(focus)),
+            ]
+            .spacing(4),
+        )
+        .width(Length::Fixed(160.0))
+        .padding(4)
+        .style(theme::Container::Box)
+        .into()
+    }
+
+    // The normal to paste into `target` from a value copied from `source`.
+    // Back into the same parameter the normal is exact. Anywhere else the
+    // value is reinterpreted through the target's range, since the two
+    // ranges may differ.
+    fn pasted(&self, source: ParamId, normal: Normal, target: ParamId) -> Normal {
+        if source == target {
+            return normal;
+        }
+
+        let value = self.param(source).range.unmap_to_value(normal);
+        self.param(target).range.map_to_normal(value)
+    }
+
+    // Draws a focus ring around `content` when `focus` has keyboard focus.
+    fn focus_frame<'a>(
+        &self,
+        content: impl Into<Element<'a, Message>>,
+        focus: Focus,
+    ) -> Element<'a, Message> {
+        let focused = self.focused == Some(focus);
+        let flashing = focus == Focus::HSlider && self.detent_flash.is_some();
+        let drifted = self.drifted();
+        let drifted = focus.params().iter().any(|id| drifted.contains(id));
+        let content = content.into();
+        // Fill widgets keep filling inside the frame.
+        let width = content.as_widget().width();
+        // Always padded, so moving focus doesn't shift the layout.
+        let frame = container(content).width(width).padding(4);
+
+        if self.is_soloed(focus) {
+            frame
+                .style(theme::Container::Custom(Box::new(style::Solo)))
+                .into()
+        } else if focused {
+            frame
+                .style(theme::Container::Custom(Box::new(style::Focused)))
+                .into()
+        } else if flashing {
+            frame
+                .style(theme::Container::Custom(Box::new(style::Detent)))
+                .into()
+        } else if drifted {
+            frame
+                .style(theme::Container::Custom(Box::new(style::Drifted)))
+                .into()
+        } else {
+            frame.into()
+        }
+    }
+
+    fn value_input(&self, id: ParamId) -> Element<'_, Message> {
+        let value = match self.value_drafts.get(&id) {
+            Some(draft) => draft.clone(),
+            None => self.param(id).value_text(),
+        };
+
+        let input = text_input("", &value, move |text| Message::ValueEdited(id, text))
+            .on_submit(Message::ValueSubmitted(id))
+            .width(Length::Fixed(80.0));
+
+        if self.param(id).disabled {
+            Disabled::new(input).into()
+        } else {
+            input.into()
+        }
+    }
+
+    // The soloed parameter's value, or a prompt until one is moved.
+    fn solo_label(&self) -> Element<'_, Message> {
+        let label = match self.soloed {
+            Some((id, _)) => self.describe(id),
+            None => "Move a parameter".into(),
+        };
+
+        text(label).size(SOLO_TEXT_SIZE).into()
+    }
+
+    // Whether `focus` holds the parameter solo mode is highlighting.
+    fn is_soloed(&self, focus: Focus) -> bool {
+        self.soloed
+            .is_some_and(|(id, _)| focus.params().contains(&id))
+    }
+
+    // The parameter `message` moves from the UI, for solo mode. Typing a
+    // value doesn't move it until it's submitted.
+    fn moved_param(&self, message: &Message) -> Option<ParamId> {
+        match message {
+            Message::ValueEdited(..) => None,
+            Message::Grabbed(id) => Some(*id),
+            Message::Nudge(_) => self.focused.map(|focus| focus.param(false)),
+            Message::NudgeVertical(_) => self.focused.map(|focus| focus.param(true)),
+            message => moved_params(message).map(|(ids, _)| ids[0]),
+        }
+    }
+
+    // Whether any of `ids` is disabled.
+    fn is_disabled(&self, ids: &[ParamId]) -> bool {
+        ids.iter().any(|&id| self.param(id).disabled)
+    }
+
+    // Whether any of `ids` is locked.
+    fn is_locked(&self, ids: &[ParamId]) -> bool {
+        ids.iter().any(|&id| self.param(id).locked)
+    }
+
+    fn is_modulated(&self, ids: &[ParamId]) -> bool {
+        ids.iter().any(|&id| self.param(id).mod_source.is_some())
+    }
+
+    // Whether `message` would change a disabled parameter from the UI, or a
+    // locked one other than by typing its value.
+    fn is_ignored(&self, message: &Message) -> bool {
+        // The octave knob moves the frequency knob.
+        if matches!(message, Message::KnobOctave(_)) && self.param(ParamId::KnobFreq).locked {
+            return true;
+        }
+
+        let Some((ids, typed)) = moved_params(message) else {
+            return false;
+        };
+
+        self.is_disabled(ids) || (!typed && self.is_locked(ids))
+    }
+}
+
+// The parameters `message` changes from the UI, and whether it's by typing
+// a value. `None` for messages that don't change one.
+fn moved_params(message: &Message) -> Option<(&[ParamId], bool)> {
+    let moved: (&[ParamId], bool) = match message {
+        Message::HSliderInt(_) | Message::IntStep(_) => (&[ParamId::HSliderInt], false),
+        Message::VSliderDB(_) => (&[ParamId::VSliderDB], false),
+        Message::KnobFreq(_) | Message::KnobScroll(_) => (&[ParamId::KnobFreq], false),
+        Message::KnobOctave(_) => (&[ParamId::KnobOctave], false),
+        Message::Smoothing(_) => (&[ParamId::Smoothing], false),
+        Message::KnobModChanged(_) => (&[ParamId::KnobMod], false),
+        Message::XYPadFloat(..) => (&[ParamId::XYPadX, ParamId::XYPadY], false),
+        Message::RampChanged(_) => (&[ParamId::RampCurve], false),
+        Message::RangeLow(_) => (&[ParamId::BandLow], false),
+        Message::RangeHigh(_) => (&[ParamId::BandHigh], false),
+        Message::Reset(id) => (std::slice::from_ref(id), false),
+        Message::ValueEdited(id, _) | Message::ValueSubmitted(id) => {
+            (std::slice::from_ref(id), true)
+        }
+        Message::MenuReset(focus) | Message::MenuPaste(focus) => (focus.params(), false),
+        _ => return None,
+    };
+
+    Some(moved)
+}
+
+// The shortcut for a key press no widget captured, if it is one.
+fn shortcut(event: event::Event, status: event::Status) -> Option<Message> {
+    match (event, status) {
+        (
+            event::Event::Keyboard(keyboard::Event::KeyPressed {
+                key_code,
+                modifiers,
+            }),
+            event::Status::Ignored,
+        ) => shortcuts::message(key_code, modifiers),
+        _ => None,
+    }
+}
+
+// The keyboard shortcuts, shown in place of everything else until closed.
+fn help<'a>() -> Element<'a, Message> {
+    let rows = shortcuts::SHORTCUTS.iter().map(|shortcut| {
+        row![
+            text(shortcut.label).width(Length::Fixed(120.0)),
+            text(shortcut.action),
+        ]
+        .spacing(10)
+        .into()
+    });
+
+    let panel = column![
+        text("Keyboard shortcuts").size(24),
+        Column::with_children(rows.collect()).spacing(6),
+        button(text("Close")).on_press(Message::CloseHelp),
+    ]
+    .spacing(20)
+    .padding(20)
+    .align_items(Alignment::Center);
+
+    container(scrollable(container(panel).style(theme::Container::Box)))
+        .width(Length::Fill)
+        .height(Length::Fill)
+        .center_x()
+        .center_y()
+        .into()
+}
+
+fn note_input_id() -> text_input::Id {
+    text_input::Id::new("note")
+}
+
+fn knob_scroll_delta(lines: f32, modifiers: keyboard::Modifiers) -> f32 {
+    let step = if modifiers.shift() {
+        KNOB_SCROLL_STEP * KNOB_SCROLL_COARSE
+    } else {
+        KNOB_SCROLL_STEP
+    };
+
+    lines * step
+}
+
+// A tick on every step of an int range, and labels spread so there are no
+// more than about `max_labels` of them.
+fn step_marks(
+    steps: RangeInclusive<i32>,
+    max_labels: usize,
+) -> (tick_marks::Group, text_marks::Group) {
+    let range = IntRange::new(*steps.start(), *steps.end());
+    let stride = ((steps.end() - steps.start()) as usize).div_ceil(max_labels);
+
+    let ticks: Vec<_> = steps
+        .clone()
+        .map(|step| (range.map_to_normal(step), tick_marks::Tier::Two))
+        .collect();
+    let labels: Vec<_> = steps
+        .step_by(stride)
+        .map(|step| (range.map_to_normal(step), step.to_string()))
+        .collect();
+
+    (ticks.into(), labels.into())
+}
+
+// The state auto-saved at `scratch`, if it was saved after the preset at
+// `saved` or there isn't one.
+fn newer_scratch(scratch: &Path, saved: &Path) -> Option<Preset> {
+    let modified = |path| fs::metadata(path).and_then(|meta| meta.modified());
+    let scratch_time = modified(scratch).ok()?;
+
+    if modified(saved).is_ok_and(|saved_time| saved_time >= scratch_time) {
+        return None;
+    }
+    Preset::load(scratch).ok()
+}
+
+// A comma-separated list of normals, in order, or `None` if any isn't a
+// number from 0 to 1. Blank is no detents at all.
+fn parse_detents(text: &str) -> Option<Vec<Normal>> {
+    let mut detents = text
+        .split(',')
+        .map(str::trim)
+        .filter(|detent| !detent.is_empty())
+        .map(|detent| {
+            let normal = detent.parse::<f32>().ok()?;
+            (0.0..=1.0)
+                .contains(&normal)
+                .then(|| Normal::from_clipped(normal))
+        })
+        .collect::<Option<Vec<_>>>()?;
+
+    detents.sort_by(|a, b| a.as_f32().total_cmp(&b.as_f32()));
+    Some(detents)
+}
+
+// The parameter values a preset holds, in the order they're loaded.
+fn preset_params(preset: &Preset) -> Vec<(ParamId, Normal)> {
+    preset_values(preset)
+        .into_iter()
+        .map(|(id, normal)| (id, Normal::from_clipped(normal)))
+        .collect()
+}
+
+// The normals `preset` holds for each parameter, as saved, even if they're
+// outside 0 to 1. Parameters added after it was saved are left out.
+fn preset_values(preset: &Preset) -> Vec<(ParamId, f32)> {
+    let mut values = vec![
+        (ParamId::HSliderInt, preset.h_slider),
+        (ParamId::VSliderDB, preset.v_slider),
+        (ParamId::KnobFreq, preset.knob),
+        (ParamId::KnobMod, preset.knob_mod),
+        (ParamId::XYPadX, preset.xy_pad_x),
+        (ParamId::XYPadY, preset.xy_pad_y),
+        (ParamId::RampCurve, preset.ramp),
+    ];
+    values.extend(preset.band_low.map(|normal| (ParamId::BandLow, normal)));
+    values.extend(preset.band_high.map(|normal| (ParamId::BandHigh, normal)));
+    values.extend(preset.smoothing.map(|normal| (ParamId::Smoothing, normal)));
+
+    values
+}
+
+// Added to the status after loading a preset that had to be clamped, naming
+// what was.
+fn clamp_warning(clamped: &[&str]) -> String {
+    if clamped.is_empty() {
+        String::new()
+    } else {
+        format!("; clamped into range: {}", clamped.join(", "))
+    }
+}
+
+// The vertical slider's parameter, either in dB or as a tapered amplitude.
+fn gain_param(config: &Config, amplitude_taper: bool) -> Param {
+    if amplitude_taper {
+        let range = AmplitudeRange::new(AMPLITUDE_TAPER);
+        Param::new("VSliderAmp", Range::Amplitude(range), 1.0)
+    } else {
+        Param::new(
+            "VSliderDB",
+            Range::LogDB(db_range(config)),
+            config.v_slider.default,
+        )
+        .with_limits(f32::NEG_INFINITY, GAIN_LIMIT_DB)
+        .with_random(config.v_slider.random)
+        .with_decimals(config.v_slider.decimals)
+    }
+}
+
+// The vertical slider's range in dB. 0 dB sits as far up the slider as it
+// is through the range, so the default -12 to 12 dB puts it at the center.
+fn db_range(config: &Config) -> LogDBRange {
+    let ParamConfig { min, max, .. } = config.v_slider;
+    let zero = Normal::from_clipped(-min / (max - min));
+
+    LogDBRange::new(min, max, zero)
+}
+
+// A normal as the raw display shows it, precise enough to tell apart the
+// ends of a wide range's steps.
+fn raw_normal(normal: f32) -> String {
+    format!("{normal:.6}")
+}
+
+// The end of the band a range slider handle sets.
+fn band_param(handle: Handle) -> ParamId {
+    match handle {
+        Handle::Low => ParamId::BandLow,
+        Handle::High => ParamId::BandHigh,
+    }
+}
+
+// The linear gain the vertical slider's parameter is set to.
+fn param_gain(param: &Param) -> f32 {
+    match param.range {
+        Range::Amplitude(_) => param.value(),
+        _ => audio::db_to_gain(param.value()),
+    }
+}
+
+// `values` with the band's two ends swapped if they're the wrong way round,
+// so two random draws or a hand-edited preset make a band rather than
+// pinching it to a point.
+fn ordered_band(mut values: Vec<(ParamId, Normal)>) -> Vec<(ParamId, Normal)> {
+    let low = values.iter().position(|&(id, _)| id == ParamId::BandLow);
+    let high = values.iter().position(|&(id, _)| id == ParamId::BandHigh);
+
+    if let (Some(low), Some(high)) = (low, high) {
+        if values[low].1.as_f32() > values[high].1.as_f32() {
+            let (low_normal, high_normal) = (values[low].1, values[high].1);
+            values[low].1 = high_normal;
+            values[high].1 = low_normal;
+        }
+    }
+
+    values
+}
+
+// The stereo position the XY pad's x-axis is at: its bipolar normal, so the
+// pan follows the pad's travel whatever range the config gives it.
+fn param_pan(param: &Param) -> f32 {
+    param.normal_param.value.as_f32() * 2.0 - 1.0
+}
+
+// The ring around the knob spans from its value to its value offset by the
+// modulation depth, which is bipolar so the ring can extend either way.
+fn knob_mod_range(knob: Normal, depth: f32) -> ModulationRange {
+    let end = Normal::from_clipped(knob.as_f32() + depth);
+
+    ModulationRange::new(knob, end)
+}
+
+// Builds tick marks at the given frequencies, each set with its own tier.
+// Each is placed through the range's own mapping, so the marks line up with
+// the knob even though the range is logarithmic.
+fn freq_tick_marks(range: &FreqRange, tiers: &[(&[f32], tick_marks::Tier)]) -> tick_marks::Group {
+    let span = range.unmap_to_value(Normal::MIN)..=range.unmap_to_value(Normal::MAX);
+
+    // Frequencies outside a narrow range would all pile up at its ends.
+    tiers
+        .iter()
+        .flat_map(|&(frequencies, tier)| {
+            frequencies
+                .iter()
+                .filter(|frequency| span.contains(frequency))
+                .map(move |&frequency| (range.map_to_normal(frequency), tier))
+        })
+        .collect::<Vec<_>>()
+        .into()
+}
+
+// The frequency knob's marks for `range`: each decade, and on larger knobs
+// the steps between.
+fn knob_marks(range: &FreqRange) -> (tick_marks::Group, tick_marks::Group) {
+    let sparse = freq_tick_marks(range, &[(&KNOB_DECADES, tick_marks::Tier::Two)]);
+    let dense = freq_tick_marks(
+        range,
+        &[
+            (&KNOB_DECADES, tick_marks::Tier::Two),
+            (&KNOB_DECADE_STEPS, tick_marks::Tier::Three),
+        ],
+    );
+
+    (sparse, dense)
+}
+
+// The knobs' size in a window of this size.
+fn knob_size(width: u32, height: u32) -> f32 {
+    if width < NARROW_WIDTH {
+        return KNOB_SIZE_MIN;
+    }
+
+    (width.min(height) as f32 * KNOB_SIZE_FRACTION).clamp(KNOB_SIZE_MIN, KNOB_SIZE_MAX)
+}
+
+// How many labels the horizontal slider has room for in a window this wide.
+fn step_labels(width: u32) -> usize {
+    if width < NARROW_WIDTH {
+        STEP_LABELS_NARROW
+    } else {
+        STEP_LABELS
+    }
+}
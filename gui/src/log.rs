@@ -0,0 +1,95 @@
+//! A record of recent interactions for the log panel.
+
+use std::collections::VecDeque;
+use std::time::{Duration, Instant};
+
+// Older entries are dropped past this many.
+const MAX_ENTRIES: usize = 200;
+
+/// The latest messages, each stamped with the time since the log started.
+#[derive(Debug)]
+pub struct Log {
+    entries: VecDeque<(Duration, String)>,
+    started: Instant,
+    // Whether the last entry came from a gesture that's still going.
+    coalescing: bool,
+}
+
+impl Log {
+    pub fn new() -> Self {
+        Log {
+            entries: VecDeque::new(),
+            started: Instant::now(),
+            coalescing: false,
+        }
+    }
+
+    /// Adds an entry. Consecutive entries pushed with `coalesce`, such as
+    /// each step of a drag, replace one another so a gesture leaves a single
+    /// line.
+    pub fn push(&mut self, text: String, coalesce: bool) {
+        if coalesce && self.coalescing {
+            self.entries.pop_back();
+        }
+        self.coalescing = coalesce;
+
+        self.entries.push_back((self.started.elapsed(), text));
+
+        if self.entries.len() > MAX_ENTRIES {
+            self.entries.pop_front();
+        }
+    }
+
+    /// Stops the next coalesced entry from replacing the last one.
+    pub fn end_gesture(&mut self) {
+        self.coalescing = false;
+    }
+
+    pub fn clear(&mut self) {
+        self.entries.clear();
+        self.coalescing = false;
+    }
+
+    pub fn latest(&self) -> Option<&str> {
+        self.entries.back().map(|(_, text)| text.as_str())
+    }
+
+    /// Each entry with its timestamp, oldest first.
+    pub fn lines(&self) -> impl Iterator<Item = String> + '_ {
+        self.entries
+            .iter()
+            .map(|(time, text)| format!("{:>8.2}s  {text}", time.as_secs_f32()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn gesture_is_one_line() {
+        let mut log = Log::new();
+
+        log.push("grabbed".into(), false);
+        log.push("step 1".into(), true);
+        log.push("step 2".into(), true);
+        assert_eq!(log.lines().count(), 2);
+        assert_eq!(log.latest(), Some("step 2"));
+
+        log.end_gesture();
+        log.push("next".into(), true);
+        assert_eq!(log.lines().count(), 3);
+    }
+
+    #[test]
+    fn oldest_entries_are_dropped() {
+        let mut log = Log::new();
+
+        for i in 0..MAX_ENTRIES + 10 {
+            log.push(i.to_string(), false);
+        }
+
+        assert_eq!(log.lines().count(), MAX_ENTRIES);
+        assert!(log.lines().next().unwrap().ends_with(" 10"));
+    }
+}
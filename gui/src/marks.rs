@@ -0,0 +1,181 @@
+//! Build tick marks at a widget's ends and center, each in its own tier,
+//! or along a decibel range.
+
+use iced_audio::tick_marks::{Group, Tier};
+use iced_audio::{text_marks, LogDBRange, Normal};
+
+/// Tick marks at the minimum, center and maximum, each given its own tier
+/// or left out, plus any ticks in between.
+///
+/// ```
+/// use iced_audio::tick_marks::Tier;
+/// use iced_audio::Normal;
+/// use paris_green_gui::TickMarks;
+///
+/// let marks = TickMarks::new()
+///     .min(Tier::One)
+///     .max(Tier::One)
+///     .center(Tier::Two)
+///     .tick(Normal::from_clipped(0.25), Tier::Three)
+///     .build();
+/// assert_eq!(marks.len(), 4);
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct TickMarks {
+    min: Option<Tier>,
+    center: Option<Tier>,
+    max: Option<Tier>,
+    ticks: Vec<(Normal, Tier)>,
+}
+
+impl TickMarks {
+    /// No marks at all, until some are added.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn min(mut self, tier: Tier) -> Self {
+        self.min = Some(tier);
+        self
+    }
+
+    pub fn center(mut self, tier: Tier) -> Self {
+        self.center = Some(tier);
+        self
+    }
+
+    pub fn max(mut self, tier: Tier) -> Self {
+        self.max = Some(tier);
+        self
+    }
+
+    /// Adds a tick at `normal`, between or on top of the others.
+    pub fn tick(mut self, normal: Normal, tier: Tier) -> Self {
+        self.ticks.push((normal, tier));
+        self
+    }
+
+    pub fn build(&self) -> Group {
+        let fixed = [
+            (Normal::MIN, self.min),
+            (Normal::CENTER, self.center),
+            (Normal::MAX, self.max),
+        ];
+
+        fixed
+            .into_iter()
+            .filter_map(|(normal, tier)| Some((normal, tier?)))
+            .chain(self.ticks.iter().copied())
+            .collect::<Vec<_>>()
+            .into()
+    }
+}
+
+/// A tick every `step` dB across `range`, counted out from 0 dB so that's
+/// always one of them, in tier one and the rest in tier two. Each sits where
+/// the range maps its value, so they spread out around 0 dB and bunch up
+/// towards the ends.
+///
+/// # Panics
+///
+/// If `step` isn't above 0.
+pub fn db_tick_marks(range: &LogDBRange, step: f32) -> Group {
+    db_steps(range, step)
+        .map(|db| {
+            let tier = if db == 0.0 { Tier::One } else { Tier::Two };
+            (range.map_to_normal(db), tier)
+        })
+        .collect::<Vec<_>>()
+        .into()
+}
+
+/// Labels every `step` dB across `range`, like "-6", "0" and "+6", placed
+/// as [`db_tick_marks`] places its ticks. A larger step than the ticks'
+/// labels only some of them.
+///
+/// # Panics
+///
+/// If `step` isn't above 0.
+pub fn db_text_marks(range: &LogDBRange, step: f32) -> text_marks::Group {
+    db_steps(range, step)
+        .map(|db| {
+            let label = if db == 0.0 {
+                String::from("0")
+            } else {
+                format!("{db:+}")
+            };
+            (range.map_to_normal(db), label)
+        })
+        .collect::<Vec<_>>()
+        .into()
+}
+
+// Every multiple of `step` within `range`, from the bottom up.
+fn db_steps(range: &LogDBRange, step: f32) -> impl Iterator<Item = f32> {
+    assert!(step > 0.0, "step must be above 0 dB");
+
+    let below = (-range.unmap_to_value(Normal::MIN) / step).floor() as i32;
+    let above = (range.unmap_to_value(Normal::MAX) / step).floor() as i32;
+
+    (-below..=above).map(move |i| i as f32 * step)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn each_position_has_its_own_tier() {
+        let marks = TickMarks::new()
+            .min(Tier::Three)
+            .center(Tier::One)
+            .max(Tier::Two)
+            .build();
+
+        assert_eq!(marks.tier_1(), Some(&vec![Normal::CENTER]));
+        assert_eq!(marks.tier_2(), Some(&vec![Normal::MAX]));
+        assert_eq!(marks.tier_3(), Some(&vec![Normal::MIN]));
+    }
+
+    #[test]
+    fn leaves_out_what_isnt_set() {
+        let marks = TickMarks::new()
+            .center(Tier::Two)
+            .tick(Normal::from_clipped(0.25), Tier::Two)
+            .build();
+
+        assert_eq!(
+            marks.tier_2(),
+            Some(&vec![Normal::CENTER, Normal::from_clipped(0.25)])
+        );
+        assert_eq!(marks.tier_1(), None);
+        assert_eq!(marks.len(), 2);
+    }
+
+    #[test]
+    fn db_marks_follow_the_log_spacing() {
+        let range = LogDBRange::new(-12.0, 12.0, Normal::CENTER);
+        let marks = db_tick_marks(&range, 3.0);
+
+        assert_eq!(marks.tier_1(), Some(&vec![Normal::CENTER]));
+        let ticks: Vec<_> = marks.tier_2().unwrap().iter().map(|n| n.as_f32()).collect();
+        assert_eq!(ticks.len(), 8);
+        // -3 dB is a quarter of the way down to -12 dB, and the square root
+        // of that is half, so it sits half way down the lower half.
+        assert!((ticks[3] - 0.25).abs() < 1e-6);
+        assert!((ticks[4] - 0.75).abs() < 1e-6);
+        assert_eq!(ticks[0], 0.0);
+        assert_eq!(ticks[7], 1.0);
+        // The gaps shrink away from 0 dB.
+        assert!(ticks[3] - ticks[2] > ticks[1] - ticks[0]);
+    }
+
+    #[test]
+    fn db_labels_are_signed() {
+        let range = LogDBRange::new(-12.0, 6.0, Normal::from_clipped(2.0 / 3.0));
+        let labels = db_text_marks(&range, 6.0);
+
+        let text: Vec<_> = labels.group.iter().map(|(_, text)| text.as_str()).collect();
+        assert_eq!(text, ["-12", "-6", "0", "+6"]);
+    }
+}
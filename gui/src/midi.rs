@@ -0,0 +1,268 @@
+//! Drive parameters from a hardware controller over MIDI.
+
+use iced::futures::channel::mpsc;
+use iced::futures::StreamExt;
+use iced::{subscription, Subscription};
+use midir::{MidiInput, MidiInputConnection};
+use serde::{Deserialize, Serialize};
+
+use std::collections::{BTreeMap, HashMap};
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+use crate::param::ParamId;
+
+// Control change values that arrive within one frame of each other are
+// coalesced, so only the latest value per controller reaches `update`.
+const FRAME: Duration = Duration::from_millis(16);
+
+// The most an endless encoder is expected to step in one message, even spun
+// fast. A bigger relative step is most likely an absolute controller bound
+// as relative, so it's dropped rather than let jump the parameter.
+const MAX_RELATIVE_STEP: i32 = 16;
+
+#[derive(Debug, Clone)]
+pub enum Event {
+    // The control changes on `controller` over one frame.
+    ControlChange(u8, Control),
+    // The last note-on over one frame, as a MIDI note number.
+    NoteOn(u8),
+    // MIDI input couldn't be opened.
+    Unavailable(String),
+}
+
+/// How a binding reads its controller's values.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum CcMode {
+    /// Each value is a position, 0 to 127 across the parameter's range.
+    #[default]
+    Absolute,
+    /// Each value is a step from an endless encoder in 7-bit two's
+    /// complement: 1 is one step up, 127 one step down.
+    Relative,
+}
+
+/// The parameter a controller moves, and how.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Binding {
+    pub param: ParamId,
+    pub mode: CcMode,
+}
+
+impl Binding {
+    pub fn absolute(param: ParamId) -> Self {
+        Binding {
+            param,
+            mode: CcMode::Absolute,
+        }
+    }
+}
+
+/// What a controller sent over one frame, decoded both ways since only the
+/// binding knows which applies: the latest value for an absolute binding,
+/// and the sum of the values as steps for a relative one.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct Control {
+    pub value: u8,
+    pub steps: i32,
+    // Relative steps too big to trust, left out of `steps`.
+    pub jumps: u32,
+}
+
+impl Control {
+    pub fn push(&mut self, value: u8) {
+        self.value = value;
+
+        let step = relative_step(value);
+        if step.abs() > MAX_RELATIVE_STEP {
+            self.jumps += 1;
+        } else {
+            self.steps += step;
+        }
+    }
+}
+
+impl From<u8> for Control {
+    fn from(value: u8) -> Self {
+        let mut control = Control::default();
+        control.push(value);
+        control
+    }
+}
+
+/// A relative CC value as a signed step.
+pub fn relative_step(value: u8) -> i32 {
+    let value = i32::from(value & 0x7F);
+    if value < 64 {
+        value
+    } else {
+        value - 128
+    }
+}
+
+/// The note number of a note-on message on any channel. A note-on with a
+/// velocity of 0 is a note-off, and note-offs are left out: the last note
+/// played holds until the next.
+pub fn note_on(bytes: &[u8]) -> Option<u8> {
+    match *bytes {
+        [status, note, velocity] if status & 0xF0 == 0x90 && velocity > 0 => Some(note),
+        _ => None,
+    }
+}
+
+/// Which parameter each CC number controls. Insert into the returned map to
+/// bind more controllers.
+pub fn default_bindings() -> HashMap<u8, Binding> {
+    HashMap::from([
+        (1, Binding::absolute(ParamId::KnobFreq)),
+        (7, Binding::absolute(ParamId::VSliderDB)),
+    ])
+}
+
+/// Listens on the first available MIDI input port.
+pub fn subscription() -> Subscription<Event> {
+    struct Midi;
+
+    subscription::unfold(
+        std::any::TypeId::of::<Midi>(),
+        State::Starting,
+        |state| async move {
+            match state {
+                State::Starting => match connect() {
+                    Ok(input) => (None, State::Connected(input)),
+                    Err(error) => (Some(Event::Unavailable(error)), State::Finished),
+                },
+                State::Connected(mut input) => {
+                    if let Some((controller, control)) = input.ready.controls.pop_first() {
+                        return (
+                            Some(Event::ControlChange(controller, control)),
+                            State::Connected(input),
+                        );
+                    }
+                    if let Some(note) = input.ready.note.take() {
+                        return (Some(Event::NoteOn(note)), State::Connected(input));
+                    }
+
+                    if input.wake.next().await.is_none() {
+                        return (None, State::Finished);
+                    }
+
+                    // Give a burst of values a frame to settle before taking the
+                    // latest of each.
+                    tokio::time::sleep(FRAME).await;
+                    input.ready = std::mem::take(&mut *input.pending.lock().unwrap());
+
+                    (None, State::Connected(input))
+                }
+                State::Finished => iced::futures::future::pending().await,
+            }
+        },
+    )
+}
+
+enum State {
+    Starting,
+    Connected(Input),
+    Finished,
+}
+
+struct Input {
+    // Dropping the connection closes the port.
+    _connection: MidiInputConnection<()>,
+    // What's been sent since the last frame, written by the MIDI thread.
+    pending: Arc<Mutex<Frame>>,
+    // Signalled by the MIDI thread when `pending` gains a value.
+    wake: mpsc::Receiver<()>,
+    // Values taken from `pending` that haven't been emitted yet.
+    ready: Frame,
+}
+
+// What arrived over one frame: each controller's values, and the latest
+// note played.
+#[derive(Default)]
+struct Frame {
+    controls: BTreeMap<u8, Control>,
+    note: Option<u8>,
+}
+
+fn connect() -> Result<Input, String> {
+    let midi_in = MidiInput::new("paris_green").map_err(|error| error.to_string())?;
+
+    let ports = midi_in.ports();
+    let port = ports.first().ok_or("no MIDI input ports found")?;
+
+    let pending = Arc::new(Mutex::new(Frame::default()));
+    let (mut notify, wake) = mpsc::channel(1);
+
+    let connection = {
+        let pending = Arc::clone(&pending);
+
+        midi_in
+            .connect(
+                port,
+                "paris_green_input",
+                move |_timestamp, bytes, _| {
+                    let mut pending = pending.lock().unwrap();
+                    if let Some(note) = note_on(bytes) {
+                        pending.note = Some(note);
+                    } else if let [status, controller, value] = *bytes {
+                        // Control change on any channel.
+                        if status & 0xF0 != 0xB0 {
+                            return;
+                        }
+                        pending.controls.entry(controller).or_default().push(value);
+                    } else {
+                        return;
+                    }
+                    // A full channel means a wake-up is already queued.
+                    let _ = notify.try_send(());
+                },
+                (),
+            )
+            .map_err(|error| error.to_string())?
+    };
+
+    Ok(Input {
+        _connection: connection,
+        pending,
+        wake,
+        ready: Frame::default(),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn relative_values_are_twos_complement() {
+        assert_eq!(relative_step(1), 1);
+        assert_eq!(relative_step(63), 63);
+        assert_eq!(relative_step(127), -1);
+        assert_eq!(relative_step(64), -64);
+        assert_eq!(relative_step(0), 0);
+    }
+
+    #[test]
+    fn only_sounding_notes_are_notes_on() {
+        assert_eq!(note_on(&[0x90, 60, 100]), Some(60));
+        // Any channel.
+        assert_eq!(note_on(&[0x9F, 69, 1]), Some(69));
+        // Note-offs, either way they're sent.
+        assert_eq!(note_on(&[0x90, 60, 0]), None);
+        assert_eq!(note_on(&[0x80, 60, 64]), None);
+        assert_eq!(note_on(&[0xB0, 1, 64]), None);
+    }
+
+    #[test]
+    fn steps_add_up_over_a_frame_but_jumps_dont() {
+        let mut control = Control::default();
+        for value in [1, 2, 127, 100, 3] {
+            control.push(value);
+        }
+
+        assert_eq!(control.value, 3);
+        assert_eq!(control.steps, 5);
+        assert_eq!(control.jumps, 1);
+    }
+}
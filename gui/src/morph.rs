@@ -0,0 +1,119 @@
+//! Glide the parameters to a preset's values rather than jumping there.
+
+use iced_audio::Normal;
+
+use std::time::{Duration, Instant};
+
+use crate::param::ParamId;
+
+/// A move of several parameters from where they were to a target, in a
+/// straight line in normal space over a fixed time.
+#[derive(Debug)]
+pub struct Morph {
+    // Each parameter with the normal it starts from and the one it ends on.
+    values: Vec<(ParamId, Normal, Normal)>,
+    started: Instant,
+    duration: Duration,
+}
+
+impl Morph {
+    pub fn new(
+        started: Instant,
+        duration: Duration,
+        values: Vec<(ParamId, Normal, Normal)>,
+    ) -> Self {
+        Morph {
+            values,
+            started,
+            duration,
+        }
+    }
+
+    /// Leaves `id` where it is from now on, for when something else has
+    /// moved it.
+    pub fn release(&mut self, id: ParamId) {
+        self.values.retain(|&(other, _, _)| other != id);
+    }
+
+    /// Every parameter's normal at `now`, and whether the morph is over.
+    /// Once it is, the normals are exactly the targets.
+    pub fn at(&self, now: Instant) -> (Vec<(ParamId, Normal)>, bool) {
+        let elapsed = now.saturating_duration_since(self.started);
+        let done = elapsed >= self.duration;
+        let t = if done {
+            1.0
+        } else {
+            elapsed.as_secs_f32() / self.duration.as_secs_f32()
+        };
+
+        let values = self
+            .values
+            .iter()
+            .map(|&(id, from, to)| {
+                if done {
+                    return (id, to);
+                }
+
+                let normal = from.as_f32() + (to.as_f32() - from.as_f32()) * t;
+                (id, Normal::from_clipped(normal))
+            })
+            .collect();
+
+        (values, done)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn morph(started: Instant) -> Morph {
+        Morph::new(
+            started,
+            Duration::from_millis(1000),
+            vec![
+                (
+                    ParamId::KnobFreq,
+                    Normal::from_clipped(0.2),
+                    Normal::from_clipped(0.6),
+                ),
+                (ParamId::RampCurve, Normal::MAX, Normal::MIN),
+            ],
+        )
+    }
+
+    #[test]
+    fn moves_in_a_straight_line() {
+        let started = Instant::now();
+        let (values, done) = morph(started).at(started + Duration::from_millis(250));
+
+        assert!(!done);
+        assert!((values[0].1.as_f32() - 0.3).abs() < 1e-4);
+        assert!((values[1].1.as_f32() - 0.75).abs() < 1e-4);
+    }
+
+    #[test]
+    fn ends_exactly_on_the_targets() {
+        let started = Instant::now();
+        let (values, done) = morph(started).at(started + Duration::from_secs(5));
+
+        assert!(done);
+        assert_eq!(
+            values,
+            vec![
+                (ParamId::KnobFreq, Normal::from_clipped(0.6)),
+                (ParamId::RampCurve, Normal::MIN),
+            ]
+        );
+    }
+
+    #[test]
+    fn released_params_stay_put() {
+        let started = Instant::now();
+        let mut morph = morph(started);
+        morph.release(ParamId::KnobFreq);
+
+        let (values, _) = morph.at(started);
+        assert_eq!(values, vec![(ParamId::RampCurve, Normal::MAX)]);
+    }
+}
@@ -0,0 +1,230 @@
+//! Send and receive parameter values as OSC messages over UDP.
+//!
+//! A parameter's value is a message addressed `/param/<name>` with a single
+//! float argument (or an int, when receiving). Bundles are taken apart and
+//! each message in them applied as it arrives, whatever its time tag.
+
+use iced::futures::channel::mpsc;
+use iced::futures::StreamExt;
+use iced::{subscription, Subscription};
+use rosc::{decoder, encoder, OscMessage, OscPacket, OscType};
+
+use std::io;
+use std::net::{SocketAddr, UdpSocket};
+use std::thread;
+
+use crate::param::ParamId;
+
+#[derive(Debug, Clone)]
+pub enum Event {
+    // A parameter's mapped value arrived.
+    Param(ParamId, f32),
+    // The listening socket couldn't be opened.
+    Unavailable(String),
+}
+
+/// The OSC address a parameter is sent to and received on.
+pub fn address(id: ParamId) -> &'static str {
+    match id {
+        ParamId::HSliderInt => "/param/h_slider_int",
+        ParamId::VSliderDB => "/param/v_slider_db",
+        ParamId::KnobFreq => "/param/knob_freq",
+        ParamId::KnobOctave => "/param/knob_octave",
+        ParamId::KnobMod => "/param/knob_mod",
+        ParamId::XYPadX => "/param/xy_pad_x",
+        ParamId::XYPadY => "/param/xy_pad_y",
+        ParamId::RampCurve => "/param/ramp_curve",
+        ParamId::Smoothing => "/param/smoothing",
+        ParamId::BandLow => "/param/band_low",
+        ParamId::BandHigh => "/param/band_high",
+    }
+}
+
+fn param_at(address: &str) -> Option<ParamId> {
+    ParamId::ALL
+        .into_iter()
+        .find(|&id| self::address(id) == address)
+}
+
+/// Sends parameter values to one host and port.
+///
+/// The socket is non-blocking and a send that can't go out straight away is
+/// dropped, so a slow or missing receiver never holds up the UI.
+#[derive(Debug)]
+pub struct Sender {
+    socket: UdpSocket,
+    target: SocketAddr,
+}
+
+impl Sender {
+    pub fn new(target: SocketAddr) -> io::Result<Sender> {
+        let socket = UdpSocket::bind(("0.0.0.0", 0))?;
+        socket.set_nonblocking(true)?;
+
+        Ok(Sender { socket, target })
+    }
+
+    pub fn send(&self, id: ParamId, value: f32) {
+        // UDP gives no delivery guarantee anyway.
+        let _ = self
+            .socket
+            .send_to(&encode(address(id), value), self.target);
+    }
+}
+
+/// Listens for parameter values on `port`.
+pub fn subscription(port: u16) -> Subscription<Event> {
+    struct Osc;
+
+    subscription::unfold(
+        (std::any::TypeId::of::<Osc>(), port),
+        State::Starting(port),
+        |state| async move {
+            match state {
+                State::Starting(port) => match listen(port) {
+                    Ok(receiver) => (None, State::Listening(receiver)),
+                    Err(error) => (Some(Event::Unavailable(error.to_string())), State::Finished),
+                },
+                State::Listening(mut receiver) => match receiver.next().await {
+                    Some((id, value)) => {
+                        (Some(Event::Param(id, value)), State::Listening(receiver))
+                    }
+                    None => (None, State::Finished),
+                },
+                State::Finished => iced::futures::future::pending().await,
+            }
+        },
+    )
+}
+
+enum State {
+    Starting(u16),
+    Listening(mpsc::UnboundedReceiver<(ParamId, f32)>),
+    Finished,
+}
+
+// Blocking reads happen on a thread of their own, passing what they decode
+// to the subscription.
+fn listen(port: u16) -> io::Result<mpsc::UnboundedReceiver<(ParamId, f32)>> {
+    let socket = UdpSocket::bind(("0.0.0.0", port))?;
+    let (sender, receiver) = mpsc::unbounded();
+
+    thread::spawn(move || {
+        let mut packet = [0; decoder::MTU];
+
+        while let Ok(len) = socket.recv(&mut packet) {
+            for (address, value) in decode(&packet[..len]) {
+                let Some(id) = param_at(&address) else {
+                    continue;
+                };
+
+                // The app has gone away.
+                if sender.unbounded_send((id, value)).is_err() {
+                    return;
+                }
+            }
+        }
+    });
+
+    Ok(receiver)
+}
+
+/// Encodes a message with a single float argument.
+pub fn encode(address: &str, value: f32) -> Vec<u8> {
+    let message = OscMessage {
+        addr: address.to_owned(),
+        args: vec![OscType::Float(value)],
+    };
+
+    // Encoding into a `Vec` can't fail.
+    encoder::encode(&OscPacket::Message(message)).unwrap()
+}
+
+/// Decodes the messages with a single float or int argument in a packet,
+/// whether it's one message or a bundle of them. Anything else, floats that
+/// aren't finite included, is left out.
+pub fn decode(packet: &[u8]) -> Vec<(String, f32)> {
+    let mut messages = Vec::new();
+    if let Ok((_, packet)) = decoder::decode_udp(packet) {
+        collect(packet, &mut messages);
+    }
+
+    messages
+}
+
+fn collect(packet: OscPacket, messages: &mut Vec<(String, f32)>) {
+    match packet {
+        OscPacket::Message(message) => {
+            let value = match message.args[..] {
+                [OscType::Float(value)] => value,
+                [OscType::Int(value)] => value as f32,
+                _ => return,
+            };
+
+            if value.is_finite() {
+                messages.push((message.addr, value));
+            }
+        }
+        OscPacket::Bundle(bundle) => {
+            for packet in bundle.content {
+                collect(packet, messages);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn round_trips_a_float() {
+        let packet = encode("/param/knob_freq", 440.0);
+
+        assert_eq!(packet.len() % 4, 0);
+        assert_eq!(decode(&packet), [("/param/knob_freq".into(), 440.0)]);
+    }
+
+    #[test]
+    fn bundles_are_taken_apart() {
+        let message = |addr: &str, arg| {
+            OscPacket::Message(OscMessage {
+                addr: addr.into(),
+                args: vec![arg],
+            })
+        };
+        let bundle = |content| {
+            OscPacket::Bundle(rosc::OscBundle {
+                timetag: (0, 1).into(),
+                content,
+            })
+        };
+        let packet = bundle(vec![
+            message("/param/knob_freq", OscType::Float(440.0)),
+            bundle(vec![message("/param/xy_pad_x", OscType::Int(1))]),
+            message("/param/knob_mod", OscType::String("loud".into())),
+        ]);
+
+        assert_eq!(
+            decode(&encoder::encode(&packet).unwrap()),
+            [
+                ("/param/knob_freq".into(), 440.0),
+                ("/param/xy_pad_x".into(), 1.0)
+            ]
+        );
+    }
+
+    #[test]
+    fn non_finite_floats_are_dropped() {
+        for value in [f32::NAN, f32::INFINITY, f32::NEG_INFINITY] {
+            assert!(decode(&encode("/param/knob_freq", value)).is_empty());
+        }
+    }
+
+    #[test]
+    fn addresses_map_back() {
+        for id in [ParamId::KnobFreq, ParamId::XYPadY, ParamId::RampCurve] {
+            assert_eq!(param_at(address(id)), Some(id));
+        }
+    }
+}
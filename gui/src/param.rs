@@ -0,0 +1,930 @@
+//! Parameters: mapping, snapping and formatting their values. Nothing here
+//! touches the UI, so it can be tested without opening a window.
+
+use iced_audio::{FloatRange, FreqRange, IntRange, LogDBRange, Normal, NormalParam};
+use serde::{Deserialize, Serialize};
+
+use std::fmt;
+
+// Identifies each parameter so messages that don't come straight from a
+// widget callback (reset, and so on) can still address it. Binary presets
+// store a parameter as its position here, so new ones go on the end.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum ParamId {
+    HSliderInt,
+    VSliderDB,
+    KnobFreq,
+    // Follows the frequency knob an octave up.
+    KnobOctave,
+    KnobMod,
+    XYPadX,
+    XYPadY,
+    RampCurve,
+    // How long the audio takes to follow the frequency and gain.
+    Smoothing,
+    // The bottom and top of a frequency band. The bottom never goes above
+    // the top.
+    BandLow,
+    BandHigh,
+}
+
+impl ParamId {
+    /// Every parameter, in the order they're declared.
+    pub const ALL: [ParamId; 11] = [
+        ParamId::HSliderInt,
+        ParamId::VSliderDB,
+        ParamId::KnobFreq,
+        ParamId::KnobOctave,
+        ParamId::KnobMod,
+        ParamId::XYPadX,
+        ParamId::XYPadY,
+        ParamId::RampCurve,
+        ParamId::Smoothing,
+        ParamId::BandLow,
+        ParamId::BandHigh,
+    ];
+}
+
+// The ranges handle converting the input/output of a parameter to and from
+// a usable value.
+//
+// There are 4 built-in options available for a range:
+//
+// * FloatRange - a linear range of f32 values
+// * IntRange - a discrete range of i32 values. This will cause the widget
+// to "step" when moved.
+// * LogDBRange - a logarithmic range of decibel values. Values around 0 dB
+// will increment slower than values farther away from 0 dB.
+// * FreqRange - a logarithmic range of frequency values. Each octave in
+// the 10 octave spectrum (from 20 Hz to 20480 Hz) is spaced evenly.
+//
+// AmplitudeRange, defined below, adds a tapered range of linear amplitudes.
+#[derive(Debug, Clone, Copy)]
+pub enum Range {
+    Float(FloatRange),
+    Int(IntRange),
+    LogDB(LogDBRange),
+    Freq(FreqRange),
+    Amplitude(AmplitudeRange),
+}
+
+/// A linear amplitude from 0 to 1, tapered so a fader feels natural.
+///
+/// The value is the normal raised to `exponent`, so with an exponent above 1
+/// the bottom half of travel covers the quiet values more finely.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct AmplitudeRange {
+    exponent: f32,
+}
+
+impl AmplitudeRange {
+    pub fn new(exponent: f32) -> Self {
+        AmplitudeRange { exponent }
+    }
+
+    pub fn unmap_to_value(&self, normal: Normal) -> f32 {
+        normal.as_f32().powf(self.exponent)
+    }
+
+    /// The inverse of `unmap_to_value`. Values outside 0..1 are clamped.
+    pub fn map_to_normal(&self, value: f32) -> Normal {
+        Normal::from_clipped(value.max(0.0).powf(self.exponent.recip()))
+    }
+}
+
+impl Range {
+    /// The value at `normal`. The bottom of a dB range that reaches below
+    /// 0 dB is silence rather than the range minimum, so it maps to negative
+    /// infinity. A range starting at 0 dB or above keeps its minimum there.
+    pub fn unmap_to_value(&self, normal: Normal) -> f32 {
+        match self {
+            Range::Float(range) => range.unmap_to_value(normal),
+            Range::Int(range) => range.unmap_to_value(normal) as f32,
+            Range::LogDB(range)
+                if normal == Normal::MIN && range.unmap_to_value(Normal::MIN) < 0.0 =>
+            {
+                f32::NEG_INFINITY
+            }
+            Range::LogDB(range) => range.unmap_to_value(normal),
+            Range::Freq(range) => range.unmap_to_value(normal),
+            Range::Amplitude(range) => range.unmap_to_value(normal),
+        }
+    }
+
+    /// The inverse of `unmap_to_value`. Values outside the range are clamped
+    /// and int ranges round to the nearest step.
+    pub fn map_to_normal(&self, value: f32) -> Normal {
+        match self {
+            Range::Float(range) => range.map_to_normal(value),
+            Range::Int(range) => range.map_to_normal(value.round() as i32),
+            Range::LogDB(_) if value == f32::NEG_INFINITY => Normal::MIN,
+            Range::LogDB(range) => range.map_to_normal(value),
+            Range::Freq(range) => range.map_to_normal(value),
+            Range::Amplitude(range) => range.map_to_normal(value),
+        }
+    }
+
+    /// Integer parameters must be snapped to make the widget "step" when
+    /// moved. Other ranges are continuous.
+    pub fn snapped(&self, normal: Normal) -> Normal {
+        match self {
+            Range::Int(range) => range.snapped(normal),
+            _ => normal,
+        }
+    }
+
+    /// The normal `steps` steps from `normal`: whole integers for an int
+    /// range, `step` in normal space for the continuous ones.
+    pub fn stepped(&self, normal: Normal, steps: i32, step: f32) -> Normal {
+        match self {
+            Range::Int(range) => range.map_to_normal(range.unmap_to_value(normal) + steps),
+            _ => Normal::from_clipped(normal.as_f32() + steps as f32 * step),
+        }
+    }
+
+    /// `value` as its value box shows it, with no unit, to `decimals`
+    /// places if given. The bottom of a dB range reads "-inf".
+    pub fn format(&self, value: f32, decimals: Option<usize>) -> String {
+        match (self, decimals) {
+            (Range::Int(_), _) => format!("{}", value),
+            (Range::LogDB(_), _) if value == f32::NEG_INFINITY => String::from("-inf"),
+            (_, Some(decimals)) => format!("{:.*}", decimals, value),
+            (Range::LogDB(_), None) => format!("{:.3}", value),
+            (Range::Float(_) | Range::Freq(_), None) => format!("{:.2}", value),
+            (Range::Amplitude(_), None) => format!("{:.3}", value),
+        }
+    }
+}
+
+/// Formats the value at `normal` for display, with the unit its range
+/// implies. Frequencies switch to kHz from 1000 Hz up.
+///
+/// `decimals` fixes the number of decimal places instead of each range's
+/// own. Frequencies then stay in Hz throughout, so the places always mean
+/// the same thing. Ints never have any.
+pub fn format_value(range: &Range, normal: Normal, decimals: Option<usize>) -> String {
+    match split_value(range, normal, decimals) {
+        (number, "") => number,
+        (number, unit) => format!("{number} {unit}"),
+    }
+}
+
+/// The number and unit [`format_value`] shows, apart. The unit is empty for
+/// ranges without one.
+pub fn split_value(
+    range: &Range,
+    normal: Normal,
+    decimals: Option<usize>,
+) -> (String, &'static str) {
+    split_amount(range, range.unmap_to_value(normal), decimals)
+}
+
+/// Formats `value` as [`format_value`] would a value of `range`, though it
+/// needn't lie within the range, for a difference between two of them.
+pub fn format_amount(range: &Range, value: f32, decimals: Option<usize>) -> String {
+    match split_amount(range, value, decimals) {
+        (number, "") => number,
+        (number, unit) => format!("{number} {unit}"),
+    }
+}
+
+/// The bounds of a frequency range, in Hz, for switching a knob between
+/// them.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct FreqSpan(pub f32, pub f32);
+
+// Spans are picked from a list, which needs them comparable. Their bounds
+// are never NaN.
+impl Eq for FreqSpan {}
+
+impl fmt::Display for FreqSpan {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let range = Range::Freq(FreqRange::new(self.0, self.1));
+        let min = format_amount(&range, self.0, None);
+        let max = format_amount(&range, self.1, None);
+
+        write!(f, "{min} to {max}")
+    }
+}
+
+fn split_amount(range: &Range, value: f32, decimals: Option<usize>) -> (String, &'static str) {
+    if let Some(decimals) = decimals {
+        return match range {
+            Range::Int(_) => (format!("{}", value), ""),
+            Range::LogDB(_) if value == f32::NEG_INFINITY => (String::from("-inf"), "dB"),
+            Range::LogDB(_) => (format!("{:.*}", decimals, value), "dB"),
+            Range::Freq(_) => (format!("{:.*}", decimals, value), "Hz"),
+            Range::Float(_) | Range::Amplitude(_) => (format!("{:.*}", decimals, value), ""),
+        };
+    }
+
+    match range {
+        Range::Int(_) => (format!("{}", value), ""),
+        Range::Float(_) | Range::Amplitude(_) => (format!("{:.2}", value), ""),
+        Range::LogDB(_) if value == f32::NEG_INFINITY => (String::from("-inf"), "dB"),
+        Range::LogDB(_) => (format!("{:.1}", value), "dB"),
+        // Each threshold sits half a displayed digit below its boundary, so
+        // a value like 999.99 reads "1.00 kHz" rather than "1000.0 Hz".
+        Range::Freq(_) if value >= 9995.0 => (format!("{:.1}", value / 1000.0), "kHz"),
+        Range::Freq(_) if value >= 999.95 => (format!("{:.2}", value / 1000.0), "kHz"),
+        Range::Freq(_) if value >= 99.995 => (format!("{:.1}", value), "Hz"),
+        Range::Freq(_) => (format!("{:.2}", value), "Hz"),
+    }
+}
+
+/// The template a parameter's label is filled in from when it has none of
+/// its own.
+pub const DEFAULT_TEMPLATE: &str = "{name}: {value} {unit}";
+
+/// `template` with each `{name}`, `{value}` and `{unit}` replaced. Any other
+/// braces are left as they are. Trailing space is trimmed, so a template
+/// ending in `{unit}` reads cleanly for a range without one.
+pub fn fill_template(template: &str, name: &str, value: &str, unit: &str) -> String {
+    let filled = template
+        .replace("{name}", name)
+        .replace("{value}", value)
+        .replace("{unit}", unit);
+
+    filled.trim_end().to_owned()
+}
+
+/// Scales the vector `(x, y)` down so its length is at most `max`,
+/// keeping its direction.
+pub fn clamp_magnitude(x: f32, y: f32, max: f32) -> (f32, f32) {
+    let magnitude = x.hypot(y);
+
+    if magnitude <= max {
+        return (x, y);
+    }
+
+    let scale = max / magnitude;
+    (x * scale, y * scale)
+}
+
+/// `normal`, or `center` if it's within `width / 2` of it, so a drag can
+/// land exactly on center. Moving on past the zone lets go of it again.
+pub fn center_detent(normal: Normal, center: Normal, width: f32) -> Normal {
+    if (normal.as_f32() - center.as_f32()).abs() <= width / 2.0 {
+        center
+    } else {
+        normal
+    }
+}
+
+/// The detent nearest `normal`, if one is within `radius` of it.
+pub fn nearest_detent(normal: Normal, detents: &[Normal], radius: f32) -> Option<Normal> {
+    let distance = |detent: &Normal| (detent.as_f32() - normal.as_f32()).abs();
+
+    detents
+        .iter()
+        .filter(|detent| distance(detent) <= radius)
+        .min_by(|a, b| distance(a).total_cmp(&distance(b)))
+        .copied()
+}
+
+/// `normal` with a deadzone `size` wide around center, as a joystick has:
+/// read as -1 to 1 about center, anything within `size` of it is exactly
+/// center, and the rest stretches out from there to the same ends, so
+/// there's no jump at the deadzone's edge. A size of 0 leaves it as it is.
+pub fn deadzone(normal: Normal, size: f32) -> Normal {
+    if size <= 0.0 {
+        return normal;
+    }
+
+    let size = size.min(1.0);
+    let bipolar = normal.as_f32() * 2.0 - 1.0;
+    if bipolar.abs() <= size {
+        return Normal::CENTER;
+    }
+
+    let stretched = (bipolar.abs() - size) / (1.0 - size);
+    Normal::from_clipped((1.0 + bipolar.signum() * stretched) / 2.0)
+}
+
+/// The normal a click `offset` along a slider's track jumps to. The track is
+/// `length` long and its handle `handle` long, centered on the value, so the
+/// value only travels between the handle's end positions. `None` when the
+/// click is on the handle at `current`, which grabs it where it is instead.
+pub fn track_click(offset: f32, length: f32, handle: f32, current: Normal) -> Option<Normal> {
+    if (offset - track_offset(current, length, handle)).abs() <= handle / 2.0 {
+        return None;
+    }
+
+    Some(track_normal(offset, length, handle))
+}
+
+/// The normal the handle's center sits at `offset` along the track for, as
+/// [`track_click`] lays it out, clipped to the ends.
+pub fn track_normal(offset: f32, length: f32, handle: f32) -> Normal {
+    let travel = (length - handle).max(1.0);
+
+    Normal::from_clipped((offset - handle / 2.0) / travel)
+}
+
+/// The inverse of [`track_normal`]: how far along the track the handle's
+/// center sits at `normal`.
+pub fn track_offset(normal: Normal, length: f32, handle: f32) -> f32 {
+    let travel = (length - handle).max(1.0);
+
+    handle / 2.0 + normal.as_f32() * travel
+}
+
+/// `normal` moved to the nearest of `divisions + 1` evenly spaced lines
+/// from the minimum to the maximum.
+pub fn snap_to_grid(normal: Normal, divisions: u16) -> Normal {
+    let divisions = f32::from(divisions.max(1));
+
+    Normal::from_clipped((normal.as_f32() * divisions).round() / divisions)
+}
+
+/// `from` moved `1 - factor` of the way to `to`, so a factor of 0 jumps
+/// straight there and one nearer 1 follows more lazily. Once within
+/// [`SMOOTH_SETTLE`] it lands exactly on `to`, so smoothing always ends
+/// where it's heading rather than creeping ever closer.
+pub fn smooth_toward(from: Normal, to: Normal, factor: f32) -> Normal {
+    let (from, to) = (from.as_f32(), to.as_f32());
+    let next = to + (from - to) * factor.clamp(0.0, 1.0);
+
+    if (next - to).abs() < SMOOTH_SETTLE {
+        Normal::from_clipped(to)
+    } else {
+        Normal::from_clipped(next)
+    }
+}
+
+/// How near [`smooth_toward`] has to get before it settles, as a normal.
+pub const SMOOTH_SETTLE: f32 = 0.001;
+
+/// What moves a parameter on its own, around the value it's set to.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum ModSource {
+    /// The app's LFO, swinging the normal up to `depth` either side.
+    Lfo { depth: f32 },
+}
+
+/// A parameter: the state of the widget controlling it, the range mapping
+/// that state to a value, and its display name.
+#[derive(Debug, Clone)]
+pub struct Param {
+    pub normal_param: NormalParam,
+    pub range: Range,
+    pub name: &'static str,
+    // Soft limits on the value, inside the range. The range itself is left
+    // alone so the limits can be loosened later.
+    pub limits: Option<(f32, f32)>,
+    // The values randomizing picks between, rather than the whole range.
+    pub random: Option<(f32, f32)>,
+    // A parameter that doesn't apply right now. Its widget is greyed out
+    // and nothing but the app itself changes it.
+    pub disabled: bool,
+    // A parameter held where it is. Dragging, MIDI, OSC and randomizing
+    // leave it alone, but a typed value still sets it.
+    pub locked: bool,
+    // The decimal places its value is shown with, or `None` for the range's
+    // own choice.
+    pub decimals: Option<usize>,
+    // What its label says, as for `fill_template`, or `None` for
+    // `DEFAULT_TEMPLATE`.
+    pub template: Option<&'static str>,
+    // What modulates it, if anything. Its widget stays at the value it's
+    // set to, and only what's sent on moves.
+    pub mod_source: Option<ModSource>,
+}
+
+impl Param {
+    /// A parameter starting at, and resetting to, `default`.
+    pub fn new(name: &'static str, range: Range, default: f32) -> Self {
+        let default = range.map_to_normal(default);
+
+        Param {
+            normal_param: NormalParam {
+                value: default,
+                default,
+            },
+            range,
+            name,
+            limits: None,
+            random: None,
+            disabled: false,
+            locked: false,
+            decimals: None,
+            template: None,
+            mod_source: None,
+        }
+    }
+
+    /// Keeps randomized values within `bounds`, if there are any.
+    pub fn with_random(mut self, bounds: Option<(f32, f32)>) -> Self {
+        self.random = bounds;
+        self
+    }
+
+    /// Shows the value with `decimals` places, if given.
+    pub fn with_decimals(mut self, decimals: Option<usize>) -> Self {
+        self.decimals = decimals;
+        self
+    }
+
+    /// The normal moved by its modulation, with the LFO at `lfo`, between
+    /// -1 and 1. It's clamped at the ends of the range rather than wrapping
+    /// round, so a frequency swung past the top doesn't come back in at the
+    /// bottom.
+    pub fn modulated(&self, lfo: f32) -> Normal {
+        let normal = self.normal_param.value;
+
+        match self.mod_source {
+            Some(ModSource::Lfo { depth }) => Normal::from_clipped(normal.as_f32() + depth * lfo),
+            None => normal,
+        }
+    }
+
+    /// Maps a uniform `sample` from 0 to 1 to a random normal within the
+    /// random bounds. The sample is raised to `bias` first, so a bias above
+    /// 1 favours the bottom of the bounds. It's spread evenly in normal
+    /// space, following the range's curve, not in value space.
+    pub fn random_normal(&self, sample: f32, bias: f32) -> Normal {
+        let (low, high) = match self.random {
+            Some((low, high)) => (
+                self.range.map_to_normal(low).as_f32(),
+                self.range.map_to_normal(high).as_f32(),
+            ),
+            None => (0.0, 1.0),
+        };
+
+        Normal::from_clipped(low + (high - low) * sample.powf(bias))
+    }
+
+    /// Swaps in a new range. The value keeps its position along the slider,
+    /// snapped to the new range's steps, while the default keeps its value,
+    /// clamped if the new range no longer reaches it.
+    pub fn set_range(&mut self, range: Range) {
+        let default = self.range.unmap_to_value(self.normal_param.default);
+
+        self.normal_param = NormalParam {
+            value: range.snapped(self.normal_param.value),
+            default: range.map_to_normal(default),
+        };
+        self.range = range;
+    }
+
+    /// Keeps the value within `min..=max`, a narrower span than the range
+    /// covers. Either end can be infinite to leave that side free.
+    pub fn with_limits(mut self, min: f32, max: f32) -> Self {
+        self.limits = Some((min, max));
+        self
+    }
+
+    /// `normal`, moved inside the soft limits if there are any.
+    pub fn limited(&self, normal: Normal) -> Normal {
+        match self.limits {
+            Some((min, max)) => {
+                let value = self.range.unmap_to_value(normal);
+                self.range.map_to_normal(value.clamp(min, max))
+            }
+            None => normal,
+        }
+    }
+
+    /// Whether the raw `normal`, as a preset might hold it, lies outside the
+    /// range or its soft limits, so taking it would clamp it.
+    pub fn is_out_of_range(&self, normal: f32) -> bool {
+        if !(0.0..=1.0).contains(&normal) {
+            return true;
+        }
+
+        // Allowing for the rounding of a trip through the range and back,
+        // so a normal saved right at a limit isn't counted.
+        let limited = self.limited(Normal::from_clipped(normal));
+        (limited.as_f32() - normal).abs() > 1e-5
+    }
+
+    /// The value the panic button sets: silence for a dB or amplitude range,
+    /// the default otherwise. The app's bipolar defaults are already zero.
+    pub fn safe_normal(&self) -> Normal {
+        match self.range {
+            Range::LogDB(_) | Range::Amplitude(_) => Normal::MIN,
+            _ => self.normal_param.default,
+        }
+    }
+
+    /// Whether the range runs from negative to positive, with zero as its
+    /// center, like the modulation depth.
+    pub fn is_bipolar(&self) -> bool {
+        matches!(self.range, Range::Float(_))
+            && self.range.unmap_to_value(Normal::MIN) < 0.0
+            && self.range.unmap_to_value(Normal::MAX) > 0.0
+    }
+
+    pub fn value(&self) -> f32 {
+        self.range.unmap_to_value(self.normal_param.value)
+    }
+
+    /// The value as shown in its value box: a bare number, so it can be
+    /// edited and parsed back.
+    pub fn value_text(&self) -> String {
+        self.range.format(self.value(), self.decimals)
+    }
+
+    /// The inverse of `value_text`: parse a typed value and map it back to a
+    /// normal through the range. NaN would slip through `map_to_normal`'s
+    /// clamping, so it's rejected. "-inf" parses as negative infinity.
+    pub fn parse(&self, text: &str) -> Option<Normal> {
+        let value: f32 = text.trim().parse().ok()?;
+
+        if value.is_nan() {
+            return None;
+        }
+
+        Some(self.range.map_to_normal(value))
+    }
+
+    /// Where this parameter sits when linked to show `ratio` times the value
+    /// of `source`, clamped to its range.
+    pub fn linked_normal(&self, source: &Param, ratio: f32) -> Normal {
+        self.range.map_to_normal(source.value() * ratio)
+    }
+
+    /// The inverse of `linked_normal`: where `source` moves to when this
+    /// parameter is set to `normal`. At the top of the range this parameter
+    /// may be clamped, so there `source` only moves up to meet it. Pulling it
+    /// down to the ceiling over `ratio` would make the link drift.
+    pub fn link_source(&self, source: &Param, ratio: f32, normal: Normal) -> Normal {
+        let value = self.range.unmap_to_value(normal) / ratio;
+
+        if normal == Normal::MAX && source.value() >= value {
+            return source.normal_param.value;
+        }
+
+        source.range.map_to_normal(value)
+    }
+
+    /// The value formatted for display, with its unit.
+    pub fn formatted(&self) -> String {
+        format_value(&self.range, self.normal_param.value, self.decimals)
+    }
+
+    /// The name and formatted value, for `output_text`, filled into the
+    /// parameter's template.
+    pub fn label(&self) -> String {
+        let (value, unit) = split_value(&self.range, self.normal_param.value, self.decimals);
+
+        fill_template(
+            self.template.unwrap_or(DEFAULT_TEMPLATE),
+            self.name,
+            &value,
+            unit,
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn int_snaps_to_whole_steps() {
+        let range = IntRange::new(0, 10);
+
+        for i in 0..=100 {
+            let normal = Normal::from_clipped(i as f32 / 100.0);
+            let snapped = range.snapped(normal);
+            let value = range.unmap_to_value(snapped);
+
+            assert_eq!(range.map_to_normal(value), snapped);
+        }
+    }
+
+    #[test]
+    fn db_round_trips_at_center() {
+        let range = Range::LogDB(LogDBRange::new(-12.0, 12.0, Normal::CENTER));
+
+        assert_eq!(range.unmap_to_value(Normal::CENTER), 0.0);
+        assert_eq!(range.map_to_normal(0.0), Normal::CENTER);
+    }
+
+    #[test]
+    fn db_bottom_is_silence() {
+        let range = Range::LogDB(LogDBRange::new(-12.0, 12.0, Normal::CENTER));
+
+        assert_eq!(range.unmap_to_value(Normal::MIN), f32::NEG_INFINITY);
+        assert_eq!(range.map_to_normal(f32::NEG_INFINITY), Normal::MIN);
+    }
+
+    #[test]
+    fn db_bottom_at_0_db_is_not_silence() {
+        let range = Range::LogDB(LogDBRange::new(0.0, 12.0, Normal::MIN));
+
+        assert_eq!(range.unmap_to_value(Normal::MIN), 0.0);
+        assert_eq!(format_value(&range, Normal::MIN, None), "0.0 dB");
+    }
+
+    #[test]
+    fn freq_maps_ends_of_spectrum() {
+        let range = FreqRange::new(20.0, 20480.0);
+
+        assert_eq!(range.map_to_normal(20.0), Normal::MIN);
+        assert_eq!(range.map_to_normal(20480.0), Normal::MAX);
+        // Every octave is the same width, so the middle octave is the middle.
+        assert!((range.map_to_normal(640.0).as_f32() - 0.5).abs() < 1e-4);
+    }
+
+    #[test]
+    fn amplitude_taper_favours_quiet_values() {
+        let range = Range::Amplitude(AmplitudeRange::new(2.0));
+
+        assert_eq!(range.unmap_to_value(Normal::MIN), 0.0);
+        assert_eq!(range.unmap_to_value(Normal::MAX), 1.0);
+        assert_eq!(range.unmap_to_value(Normal::CENTER), 0.25);
+        assert_eq!(range.map_to_normal(0.25), Normal::CENTER);
+        assert_eq!(range.map_to_normal(2.0), Normal::MAX);
+    }
+
+    #[test]
+    fn out_of_range_includes_the_limits() {
+        let range = Range::LogDB(LogDBRange::new(-12.0, 12.0, Normal::CENTER));
+        let param = Param::new("gain", range, 0.0).with_limits(f32::NEG_INFINITY, 6.0);
+
+        assert!(!param.is_out_of_range(0.0));
+        assert!(!param.is_out_of_range(range.map_to_normal(6.0).as_f32()));
+        assert!(param.is_out_of_range(range.map_to_normal(9.0).as_f32()));
+        assert!(param.is_out_of_range(1.5));
+        assert!(param.is_out_of_range(-0.1));
+    }
+
+    #[test]
+    fn limits_stop_short_of_the_range() {
+        let range = Range::LogDB(LogDBRange::new(-12.0, 12.0, Normal::CENTER));
+        let param = Param::new("gain", range, 0.0).with_limits(f32::NEG_INFINITY, 6.0);
+
+        let top = param.limited(Normal::MAX);
+        assert!((range.unmap_to_value(top) - 6.0).abs() < 1e-4);
+        // Silence is still allowed below.
+        assert_eq!(param.limited(Normal::MIN), Normal::MIN);
+    }
+
+    #[test]
+    fn stepped_moves_whole_integers() {
+        let range = Range::Int(IntRange::new(0, 10));
+        let five = range.map_to_normal(5.0);
+
+        assert_eq!(range.unmap_to_value(range.stepped(five, 2, 0.01)), 7.0);
+        assert_eq!(range.unmap_to_value(range.stepped(five, 20, 0.01)), 10.0);
+    }
+
+    #[test]
+    fn set_range_keeps_proportion() {
+        let mut param = Param::new("steps", Range::Int(IntRange::new(0, 10)), 5.0);
+        param.normal_param.update(param.range.map_to_normal(3.0));
+
+        param.set_range(Range::Int(IntRange::new(0, 20)));
+        assert_eq!(param.value(), 6.0);
+        assert_eq!(param.range.unmap_to_value(param.normal_param.default), 5.0);
+
+        // Shrinking clamps a default past the new top.
+        param.set_range(Range::Int(IntRange::new(0, 4)));
+        assert_eq!(param.value(), 1.0);
+        assert_eq!(param.range.unmap_to_value(param.normal_param.default), 4.0);
+    }
+
+    #[test]
+    fn defaults_map_back_exactly() {
+        let db = Range::LogDB(LogDBRange::new(-12.0, 12.0, Normal::CENTER));
+        let gain = Param::new("gain", db, 0.0);
+        assert_eq!(gain.range.unmap_to_value(gain.normal_param.default), 0.0);
+
+        let freq = Param::new("freq", Range::Freq(FreqRange::new(20.0, 20480.0)), 1000.0);
+        let value = freq.range.unmap_to_value(freq.normal_param.default);
+        assert!((value - 1000.0).abs() < 1e-2);
+    }
+
+    #[test]
+    fn random_stays_in_bounds() {
+        let range = Range::Freq(FreqRange::new(20.0, 20480.0));
+        let param = Param::new("freq", range, 1000.0).with_random(Some((100.0, 2000.0)));
+
+        let bottom = range.unmap_to_value(param.random_normal(0.0, 1.0));
+        let top = range.unmap_to_value(param.random_normal(1.0, 1.0));
+        assert!((bottom - 100.0).abs() < 0.1);
+        assert!((top - 2000.0).abs() < 1.0);
+
+        // Bias leans towards the bottom of the bounds.
+        let unbounded = Param::new("gain", range, 1000.0);
+        assert_eq!(unbounded.random_normal(0.5, 2.0).as_f32(), 0.25);
+    }
+
+    #[test]
+    fn parse_rejects_nan() {
+        let param = Param::new("x", Range::Float(FloatRange::default_bipolar()), 0.0);
+
+        assert_eq!(param.parse("NaN"), None);
+        assert_eq!(param.parse(" 0 "), Some(Normal::CENTER));
+    }
+
+    #[test]
+    fn link_holds_at_ceiling() {
+        let range = Range::Freq(FreqRange::new(20.0, 20480.0));
+        let mut source = Param::new("freq", range, 15000.0);
+        let linked = Param::new("octave", range, 20480.0);
+
+        assert_eq!(linked.linked_normal(&source, 2.0), Normal::MAX);
+        // Dragging the clamped knob leaves the source where it was.
+        let held = linked.link_source(&source, 2.0, Normal::MAX);
+        assert_eq!(held, source.normal_param.value);
+
+        // Below the ceiling the source meets it.
+        source.normal_param.update(range.map_to_normal(5000.0));
+        let met = linked.link_source(&source, 2.0, Normal::MAX);
+        assert!((range.unmap_to_value(met) - 10240.0).abs() < 1.0);
+    }
+
+    #[test]
+    fn clamp_magnitude_keeps_direction() {
+        assert_eq!(clamp_magnitude(0.5, 0.5, 1.0), (0.5, 0.5));
+
+        let (x, y) = clamp_magnitude(1.0, 1.0, 1.0);
+        assert!((x.hypot(y) - 1.0).abs() < 1e-6);
+        assert_eq!(x, y);
+    }
+
+    #[test]
+    fn track_click_skips_the_handle() {
+        // A 110 px track with a 10 px handle leaves 100 px of travel.
+        let current = Normal::from_clipped(0.5);
+
+        assert_eq!(track_click(55.0, 110.0, 10.0, current), None);
+        assert_eq!(track_click(59.0, 110.0, 10.0, current), None);
+        assert_eq!(
+            track_click(30.0, 110.0, 10.0, current),
+            Some(Normal::from_clipped(0.25))
+        );
+        assert_eq!(track_click(108.0, 110.0, 10.0, current), Some(Normal::MAX));
+        assert_eq!(track_click(-4.0, 110.0, 10.0, current), Some(Normal::MIN));
+    }
+
+    #[test]
+    fn track_positions_round_trip() {
+        let normal = Normal::from_clipped(0.25);
+
+        assert_eq!(track_offset(normal, 110.0, 10.0), 30.0);
+        assert_eq!(track_normal(30.0, 110.0, 10.0), normal);
+        assert_eq!(track_normal(0.0, 110.0, 10.0), Normal::MIN);
+    }
+
+    #[test]
+    fn snap_to_grid_finds_the_nearest_line() {
+        assert_eq!(
+            snap_to_grid(Normal::from_clipped(0.3), 8),
+            Normal::from_clipped(0.25)
+        );
+        assert_eq!(
+            snap_to_grid(Normal::from_clipped(0.32), 8),
+            Normal::from_clipped(0.375)
+        );
+        assert_eq!(snap_to_grid(Normal::MAX, 8), Normal::MAX);
+        assert_eq!(snap_to_grid(Normal::from_clipped(0.3), 0), Normal::MIN);
+    }
+
+    #[test]
+    fn smoothing_settles_on_the_target() {
+        let to = Normal::from_clipped(0.8);
+        let mut normal = Normal::MIN;
+
+        normal = smooth_toward(normal, to, 0.5);
+        assert_eq!(normal, Normal::from_clipped(0.4));
+        for _ in 0..20 {
+            normal = smooth_toward(normal, to, 0.5);
+        }
+        assert_eq!(normal, to);
+
+        assert_eq!(smooth_toward(Normal::MIN, to, 0.0), to);
+    }
+
+    #[test]
+    fn detents_pull_in_only_within_the_radius() {
+        let detents = [0.25, 0.5, 0.6].map(Normal::from_clipped);
+        let near = |normal| nearest_detent(Normal::from_clipped(normal), &detents, 0.05);
+
+        assert_eq!(near(0.27), Some(Normal::from_clipped(0.25)));
+        // The nearer of two in range.
+        assert_eq!(near(0.56), Some(Normal::from_clipped(0.6)));
+        assert_eq!(near(0.4), None);
+        assert_eq!(nearest_detent(Normal::CENTER, &[], 1.0), None);
+    }
+
+    #[test]
+    fn deadzone_stretches_the_rest_without_a_jump() {
+        let dead = |normal| deadzone(Normal::from_clipped(normal), 0.2).as_f32();
+
+        assert_eq!(dead(0.55), 0.5);
+        assert_eq!(dead(0.4), 0.5);
+        // Just past the edge is just off center, on either side.
+        assert!((dead(0.601) - 0.5).abs() < 0.002);
+        assert!((dead(0.399) - 0.5).abs() < 0.002);
+        // Half way out of the zone is half way to the end.
+        assert!((dead(0.8) - 0.75).abs() < 1e-6);
+        assert_eq!(dead(1.0), 1.0);
+        assert_eq!(dead(0.0), 0.0);
+
+        let normal = Normal::from_clipped(0.52);
+        assert_eq!(deadzone(normal, 0.0), normal);
+    }
+
+    #[test]
+    fn center_detent_holds_near_center() {
+        let center = Normal::CENTER;
+
+        assert_eq!(
+            center_detent(Normal::from_clipped(0.51), center, 0.04),
+            center
+        );
+        assert_eq!(
+            center_detent(Normal::from_clipped(0.485), center, 0.04),
+            center
+        );
+        let outside = Normal::from_clipped(0.53);
+        assert_eq!(center_detent(outside, center, 0.04), outside);
+        // A zero width turns it off.
+        let near = Normal::from_clipped(0.501);
+        assert_eq!(center_detent(near, center, 0.0), near);
+    }
+
+    #[test]
+    fn modulation_clamps_at_the_ends() {
+        let mut param = Param::new("ramp", Range::Float(FloatRange::default()), 0.9);
+        assert_eq!(param.modulated(1.0), param.normal_param.value);
+
+        param.mod_source = Some(ModSource::Lfo { depth: 0.2 });
+        assert!((param.modulated(-1.0).as_f32() - 0.7).abs() < 1e-6);
+        // Past the top it holds there rather than wrapping to the bottom.
+        assert_eq!(param.modulated(1.0), Normal::MAX);
+    }
+
+    #[test]
+    fn bipolar_ranges_span_zero() {
+        let depth = Param::new("depth", Range::Float(FloatRange::default_bipolar()), 0.0);
+        assert!(depth.is_bipolar());
+
+        let ramp = Param::new("ramp", Range::Float(FloatRange::default()), 0.5);
+        assert!(!ramp.is_bipolar());
+    }
+
+    #[test]
+    fn format_value_units() {
+        let freq = Range::Freq(FreqRange::default());
+        assert_eq!(
+            format_value(&freq, freq.map_to_normal(440.0), None),
+            "440.0 Hz"
+        );
+        assert_eq!(
+            format_value(&freq, freq.map_to_normal(1000.0), None),
+            "1.00 kHz"
+        );
+
+        let db = Range::LogDB(LogDBRange::new(-12.0, 12.0, Normal::CENTER));
+        assert_eq!(format_value(&db, Normal::CENTER, None), "0.0 dB");
+        assert_eq!(format_value(&db, Normal::MIN, None), "-inf dB");
+
+        let int = Range::Int(IntRange::new(0, 10));
+        assert_eq!(format_value(&int, int.map_to_normal(3.0), None), "3");
+    }
+
+    #[test]
+    fn freq_spans_read_in_hz_and_khz() {
+        assert_eq!(FreqSpan(20.0, 2000.0).to_string(), "20.00 Hz to 2.00 kHz");
+        assert_eq!(FreqSpan(200.0, 20480.0).to_string(), "200.0 Hz to 20.5 kHz");
+    }
+
+    #[test]
+    fn labels_fill_their_template() {
+        let mut param = Param::new("Cutoff", Range::Freq(FreqRange::new(20.0, 20480.0)), 2500.0);
+        assert_eq!(param.label(), "Cutoff: 2.50 kHz");
+
+        param.template = Some("{value}{unit} de {name} {x}");
+        assert_eq!(param.label(), "2.50kHz de Cutoff {x}");
+
+        // No unit leaves no trailing space.
+        assert_eq!(
+            fill_template(DEFAULT_TEMPLATE, "Steps", "3", ""),
+            "Steps: 3"
+        );
+    }
+
+    #[test]
+    fn fixed_decimals() {
+        let freq = Range::Freq(FreqRange::default());
+        let normal = freq.map_to_normal(1234.56);
+        assert_eq!(format_value(&freq, normal, Some(0)), "1235 Hz");
+        assert_eq!(format_value(&freq, normal, Some(1)), "1234.6 Hz");
+
+        let db = Range::LogDB(LogDBRange::new(-12.0, 12.0, Normal::CENTER));
+        assert_eq!(format_value(&db, Normal::CENTER, Some(1)), "0.0 dB");
+        assert_eq!(format_value(&db, Normal::MIN, Some(1)), "-inf dB");
+
+        let int = Range::Int(IntRange::new(0, 10));
+        assert_eq!(format_value(&int, int.map_to_normal(3.0), Some(2)), "3");
+
+        let knob = Param::new("knob", freq, 440.0).with_decimals(Some(0));
+        assert_eq!(knob.value_text(), "440");
+        assert_eq!(knob.label(), "knob: 440 Hz");
+    }
+}
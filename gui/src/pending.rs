@@ -0,0 +1,65 @@
+//! Coalesce parameter changes that arrive faster than the screen redraws.
+
+use iced_audio::Normal;
+
+use crate::param::ParamId;
+
+/// The latest value received for each parameter since they were last
+/// applied.
+///
+/// A controller can send hundreds of values a second, but only the last one
+/// before each frame is ever seen. Setting a parameter again replaces its
+/// value, so the one applied is never older than the last received.
+#[derive(Debug, Default)]
+pub struct Pending {
+    // In the order the values were last set, so linked parameters like the
+    // two frequency knobs still resolve to whichever moved last.
+    values: Vec<(ParamId, Normal)>,
+}
+
+impl Pending {
+    pub fn set(&mut self, id: ParamId, normal: Normal) {
+        self.values.retain(|&(other, _)| other != id);
+        self.values.push((id, normal));
+    }
+
+    /// The value waiting for `id`, if any.
+    pub fn get(&self, id: ParamId) -> Option<Normal> {
+        self.values
+            .iter()
+            .find(|&&(other, _)| other == id)
+            .map(|&(_, normal)| normal)
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.values.is_empty()
+    }
+
+    /// Takes every pending value, leaving none.
+    pub fn take(&mut self) -> Vec<(ParamId, Normal)> {
+        std::mem::take(&mut self.values)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn keeps_the_latest_value() {
+        let mut pending = Pending::default();
+
+        pending.set(ParamId::KnobFreq, Normal::from_clipped(0.1));
+        pending.set(ParamId::VSliderDB, Normal::from_clipped(0.5));
+        pending.set(ParamId::KnobFreq, Normal::from_clipped(0.9));
+
+        assert_eq!(
+            pending.take(),
+            vec![
+                (ParamId::VSliderDB, Normal::from_clipped(0.5)),
+                (ParamId::KnobFreq, Normal::from_clipped(0.9)),
+            ]
+        );
+        assert!(pending.is_empty());
+    }
+}
@@ -0,0 +1,167 @@
+//! App preferences that survive restarts, kept apart from presets.
+
+use serde::{Deserialize, Serialize};
+
+use crate::widget::DragAxis;
+
+use std::fs;
+use std::io;
+use std::net::SocketAddr;
+use std::path::Path;
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Prefs {
+    #[serde(default)]
+    pub dark_theme: bool,
+    // Where parameter changes are sent over OSC, and the port OSC is
+    // received on.
+    #[serde(default = "default_osc_target")]
+    pub osc_target: SocketAddr,
+    #[serde(default = "default_osc_port")]
+    pub osc_port: u16,
+    // Whether dragging a widget shows its value by the cursor.
+    #[serde(default = "default_drag_tooltips")]
+    pub drag_tooltips: bool,
+    // How much of a bipolar widget's travel around center snaps to it, as
+    // a normal. 0 turns the detent off.
+    #[serde(default = "default_center_detent")]
+    pub center_detent: f32,
+    // How long loading a preset takes to glide to its values, in
+    // milliseconds. 0 loads it instantly.
+    #[serde(default)]
+    pub morph_time: u64,
+    // How long solo mode keeps highlighting the last parameter moved, in
+    // milliseconds. 0 keeps it until another is moved.
+    #[serde(default = "default_solo_idle")]
+    pub solo_idle: u64,
+    // The least time between two values of one parameter sent over OSC, in
+    // milliseconds. 0 sends every change.
+    #[serde(default = "default_osc_interval")]
+    pub osc_interval: u64,
+    // Which way dragging turns the knobs.
+    #[serde(default)]
+    pub knob_drag: DragAxis,
+    // How far a knob is dragged to turn it through its whole range, in
+    // logical pixels. More is finer.
+    #[serde(default = "default_knob_travel")]
+    pub knob_travel: f32,
+    // Whether the status line goes back to a neutral prompt once nothing's
+    // happened for `status_timeout` milliseconds.
+    #[serde(default)]
+    pub status_clears: bool,
+    #[serde(default = "default_status_timeout")]
+    pub status_timeout: u64,
+}
+
+impl Default for Prefs {
+    fn default() -> Self {
+        Prefs {
+            dark_theme: false,
+            osc_target: default_osc_target(),
+            osc_port: default_osc_port(),
+            drag_tooltips: default_drag_tooltips(),
+            center_detent: default_center_detent(),
+            morph_time: 0,
+            solo_idle: default_solo_idle(),
+            osc_interval: default_osc_interval(),
+            knob_drag: DragAxis::default(),
+            knob_travel: default_knob_travel(),
+            status_clears: false,
+            status_timeout: default_status_timeout(),
+        }
+    }
+}
+
+fn default_osc_target() -> SocketAddr {
+    SocketAddr::from(([127, 0, 0, 1], 9000))
+}
+
+fn default_osc_port() -> u16 {
+    9001
+}
+
+fn default_drag_tooltips() -> bool {
+    true
+}
+
+fn default_osc_interval() -> u64 {
+    20
+}
+
+// About what iced_audio turns a knob by per pixel on its own.
+fn default_knob_travel() -> f32 {
+    260.0
+}
+
+fn default_status_timeout() -> u64 {
+    5000
+}
+
+fn default_center_detent() -> f32 {
+    0.04
+}
+
+fn default_solo_idle() -> u64 {
+    3000
+}
+
+impl Prefs {
+    /// Reads the preferences, falling back to the defaults when there are
+    /// none saved yet or they can't be read, and for any value that can't
+    /// be used.
+    pub fn load(path: &Path) -> Prefs {
+        fs::read_to_string(path)
+            .ok()
+            .and_then(|json| serde_json::from_str(&json).ok())
+            .map(Prefs::validated)
+            .unwrap_or_default()
+    }
+
+    // A hand-edited file could hold anything. A knob travel of 0 or less
+    // would turn knobs by an infinite or backwards amount per pixel, and a
+    // detent of the whole travel would hold bipolar widgets at center.
+    fn validated(mut self) -> Prefs {
+        if !(self.knob_travel.is_finite() && self.knob_travel > 0.0) {
+            self.knob_travel = default_knob_travel();
+        }
+        if !(0.0..1.0).contains(&self.center_detent) {
+            self.center_detent = default_center_detent();
+        }
+
+        self
+    }
+
+    pub fn save(&self, path: &Path) -> io::Result<()> {
+        let json = serde_json::to_string_pretty(self)?;
+        fs::write(path, json)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn unusable_values_fall_back_to_their_defaults() {
+        for travel in ["0", "-100", "1e39"] {
+            let json = format!(r#"{{"knob_travel": {travel}}}"#);
+            let prefs: Prefs = serde_json::from_str(&json).unwrap();
+
+            assert_eq!(prefs.validated().knob_travel, default_knob_travel());
+        }
+
+        let prefs: Prefs = serde_json::from_str(r#"{"knob_travel": 520}"#).unwrap();
+        assert_eq!(prefs.validated().knob_travel, 520.0);
+
+        for detent in ["1.0", "2.5", "-0.1", "1e39"] {
+            let json = format!(r#"{{"center_detent": {detent}}}"#);
+            let prefs: Prefs = serde_json::from_str(&json).unwrap();
+
+            assert_eq!(prefs.validated().center_detent, default_center_detent());
+        }
+
+        // 0 turns the detent off.
+        let prefs: Prefs = serde_json::from_str(r#"{"center_detent": 0}"#).unwrap();
+        assert_eq!(prefs.validated().center_detent, 0.0);
+    }
+}
@@ -0,0 +1,751 @@
+//! Save and restore the state of every widget as a preset.
+//!
+//! Presets are JSON, for editing by hand, unless the file name ends in
+//! `.bin`, which stores them in a compact binary format instead: the magic
+//! bytes `PGPR`, the preset version as a little-endian u32, then the preset
+//! in [`bincode`]. Floats are stored as their bits, so normals come back
+//! exactly as they were saved.
+//!
+//! Unlike JSON, bincode can't default a missing field, so the version in
+//! front says which layout follows. Each layout is a frozen struct of its
+//! own, like `PresetV11`: a version that changes the layout adds a new one
+//! and keeps the old, so older files still decode and are converted into a
+//! [`Preset`] before [`Preset::migrate`] upgrades them like older JSON.
+//!
+//! Versions 1 to 10 wrote one record per field instead: a tag byte, a
+//! little-endian u16 payload length and the payload. Those are still read.
+
+use serde::{Deserialize, Serialize};
+
+use crate::lfo::{self, Shape};
+use crate::midi::CcMode;
+use crate::param::ParamId;
+
+use std::collections::{BTreeMap, HashMap};
+use std::ffi::OsStr;
+use std::fmt;
+use std::fs;
+use std::io;
+use std::path::Path;
+
+/// The current version of the [`Preset`] layout.
+///
+/// Fields added in later versions must have a serde default so JSON presets
+/// saved by older versions keep parsing.
+pub const PRESET_VERSION: u32 = 11;
+
+// The start of every binary preset.
+const MAGIC: &[u8; 4] = b"PGPR";
+
+// The record tags binary presets before version 11 were written with.
+const TAG_H_SLIDER: u8 = 1;
+const TAG_V_SLIDER: u8 = 2;
+const TAG_KNOB: u8 = 3;
+const TAG_XY_PAD_X: u8 = 4;
+const TAG_XY_PAD_Y: u8 = 5;
+const TAG_SLIDER_VALUE: u8 = 6;
+const TAG_BUTTON_ID: u8 = 7;
+const TAG_MIDI_BINDINGS: u8 = 8;
+const TAG_RAMP: u8 = 9;
+const TAG_KNOB_MOD: u8 = 10;
+const TAG_AMPLITUDE_TAPER: u8 = 11;
+const TAG_MIDI_MODES: u8 = 12;
+const TAG_PHASE_INVERT: u8 = 13;
+const TAG_XY_PADS: u8 = 14;
+const TAG_XY_PAD_TAB: u8 = 15;
+const TAG_LFO_RATE: u8 = 16;
+const TAG_LFO_DEPTH: u8 = 17;
+const TAG_LFO_SHAPE: u8 = 18;
+const TAG_BAND_LOW: u8 = 19;
+const TAG_BAND_HIGH: u8 = 20;
+
+/// A snapshot of every widget.
+///
+/// Parameters are stored as raw normals rather than mapped values, so a
+/// preset stays valid if the bounds of a range change later.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Preset {
+    pub version: u32,
+    pub h_slider: f32,
+    pub v_slider: f32,
+    pub knob: f32,
+    pub xy_pad_x: f32,
+    pub xy_pad_y: f32,
+    pub slider_value: f32,
+    pub button_id: u8,
+    // Added in version 2. `None` for older presets, which leaves the current
+    // bindings alone when loaded.
+    #[serde(default)]
+    pub midi_bindings: Option<HashMap<u8, ParamId>>,
+    // Added in version 3.
+    #[serde(default = "default_ramp")]
+    pub ramp: f32,
+    // Added in version 4.
+    #[serde(default = "default_knob_mod")]
+    pub knob_mod: f32,
+    // Added in version 5. Older presets used the dB range.
+    #[serde(default)]
+    pub amplitude_taper: bool,
+    // Added in version 6. The mode of each binding that isn't absolute.
+    #[serde(default)]
+    pub midi_modes: HashMap<u8, CcMode>,
+    // Added in version 7. Older presets kept the phase as it is.
+    #[serde(default)]
+    pub phase_invert: bool,
+    // Added in version 8. Every XY pad's x and y, by tab, and the tab
+    // showing, whose pad is also `xy_pad_x` and `xy_pad_y`. Empty for older
+    // presets, which leaves the other pads alone when loaded.
+    #[serde(default)]
+    pub xy_pads: Vec<(f32, f32)>,
+    #[serde(default)]
+    pub xy_pad_tab: u8,
+    // Added in version 9. The LFO's rate in Hz, depth and shape. Older
+    // presets get a new LFO's.
+    #[serde(default = "default_lfo_rate")]
+    pub lfo_rate: f32,
+    #[serde(default = "default_lfo_depth")]
+    pub lfo_depth: f32,
+    #[serde(default)]
+    pub lfo_shape: Shape,
+    // Added in version 10. `None` for older presets, which leaves the band
+    // alone when loaded.
+    #[serde(default)]
+    pub band_low: Option<f32>,
+    #[serde(default)]
+    pub band_high: Option<f32>,
+    // Added in version 11. `None` for older presets, which leaves the
+    // smoothing time alone when loaded.
+    #[serde(default)]
+    pub smoothing: Option<f32>,
+}
+
+// Older presets get a linear ramp.
+fn default_ramp() -> f32 {
+    0.5
+}
+
+// Older presets get no modulation: the center of the bipolar range.
+fn default_knob_mod() -> f32 {
+    0.5
+}
+
+fn default_lfo_rate() -> f32 {
+    lfo::DEFAULT_RATE
+}
+
+fn default_lfo_depth() -> f32 {
+    lfo::DEFAULT_DEPTH
+}
+
+impl Preset {
+    /// Reads a preset in the format its extension calls for, upgraded to the
+    /// current version.
+    pub fn load(path: &Path) -> Result<Preset, PresetError> {
+        let preset = if is_binary(path) {
+            Preset::decode(&fs::read(path)?)?
+        } else {
+            serde_json::from_str(&fs::read_to_string(path)?)?
+        };
+
+        if preset.version > PRESET_VERSION {
+            return Err(PresetError::UnsupportedVersion(preset.version));
+        }
+        preset.validate()?;
+
+        Ok(preset.migrate())
+    }
+
+    /// Writes the preset in the format its extension calls for. It goes to
+    /// a file beside `path` first and is then moved over it, so a crash
+    /// partway leaves the old file whole rather than half written.
+    pub fn save(&self, path: &Path) -> Result<(), PresetError> {
+        let bytes = if is_binary(path) {
+            self.encode()
+        } else {
+            serde_json::to_string_pretty(self)?.into_bytes()
+        };
+
+        let mut partial = path.as_os_str().to_owned();
+        partial.push(".partial");
+        fs::write(&partial, bytes)?;
+        fs::rename(&partial, path)?;
+
+        Ok(())
+    }
+
+    // Checks every normal is a number, and the LFO's rate is a rate: a
+    // hand-edited or damaged file could hold anything. Numbers outside the
+    // live ranges are clamped into them when the preset's applied instead,
+    // since a preset saved under a wider range can hold them.
+    fn validate(&self) -> Result<(), PresetError> {
+        let pads = self.xy_pads.iter().flat_map(|&(x, y)| [x, y]);
+        let optional = [self.band_low, self.band_high, self.smoothing];
+        let normals = [
+            self.h_slider,
+            self.v_slider,
+            self.knob,
+            self.xy_pad_x,
+            self.xy_pad_y,
+            self.ramp,
+            self.knob_mod,
+            self.lfo_depth,
+        ];
+
+        if normals
+            .into_iter()
+            .chain(pads)
+            .chain(optional.into_iter().flatten())
+            .all(f32::is_finite)
+            && self.lfo_rate.is_finite()
+            && self.lfo_rate > 0.0
+        {
+            Ok(())
+        } else {
+            Err(PresetError::OutOfRange)
+        }
+    }
+
+    // Brings an older preset up to the current version. Missing fields have
+    // already been defaulted, so this only covers what a default can't.
+    fn migrate(mut self) -> Preset {
+        // Version 1 had no MIDI bindings. A binding map in a version 1 file
+        // was written by hand against a later layout, so it's dropped rather
+        // than trusted.
+        if self.version < 2 {
+            self.midi_bindings = None;
+            self.midi_modes.clear();
+        }
+
+        self.version = PRESET_VERSION;
+        self
+    }
+
+    /// The preset in the binary format.
+    pub fn encode(&self) -> Vec<u8> {
+        // Always the current layout, whatever version the preset came from.
+        let mut bytes = MAGIC.to_vec();
+        bytes.extend(PRESET_VERSION.to_le_bytes());
+        // Only an I/O error can fail this, and a `Vec` has none.
+        bytes.extend(bincode::serialize(&PresetV11::from(self)).unwrap());
+
+        bytes
+    }
+
+    /// The inverse of [`Preset::encode`], which also reads presets saved in
+    /// every older binary layout.
+    pub fn decode(bytes: &[u8]) -> Result<Preset, PresetError> {
+        let rest = bytes
+            .strip_prefix(MAGIC)
+            .ok_or(PresetError::Binary("not a binary preset"))?;
+        let (version, body) = split(rest, 4)?;
+        let version = u32::from_le_bytes(version.try_into().unwrap());
+
+        match version {
+            version if version > PRESET_VERSION => Err(PresetError::UnsupportedVersion(version)),
+            11..=PRESET_VERSION => {
+                let layout: PresetV11 = bincode::deserialize(body)?;
+                Ok(layout.into_preset(version))
+            }
+            _ => decode_records(version, body),
+        }
+    }
+}
+
+/// The layout of binary presets from version 11. Frozen: a later version
+/// that changes the layout gets a struct of its own, and this one stays to
+/// read older files.
+///
+/// The maps are ordered so the same preset always encodes the same way.
+#[derive(Serialize, Deserialize)]
+struct PresetV11 {
+    h_slider: f32,
+    v_slider: f32,
+    knob: f32,
+    xy_pad_x: f32,
+    xy_pad_y: f32,
+    slider_value: f32,
+    button_id: u8,
+    midi_bindings: Option<BTreeMap<u8, ParamId>>,
+    ramp: f32,
+    knob_mod: f32,
+    amplitude_taper: bool,
+    midi_modes: BTreeMap<u8, CcMode>,
+    phase_invert: bool,
+    xy_pads: Vec<(f32, f32)>,
+    xy_pad_tab: u8,
+    lfo_rate: f32,
+    lfo_depth: f32,
+    lfo_shape: Shape,
+    band_low: Option<f32>,
+    band_high: Option<f32>,
+    smoothing: Option<f32>,
+}
+
+impl From<&Preset> for PresetV11 {
+    fn from(preset: &Preset) -> Self {
+        PresetV11 {
+            h_slider: preset.h_slider,
+            v_slider: preset.v_slider,
+            knob: preset.knob,
+            xy_pad_x: preset.xy_pad_x,
+            xy_pad_y: preset.xy_pad_y,
+            slider_value: preset.slider_value,
+            button_id: preset.button_id,
+            midi_bindings: preset
+                .midi_bindings
+                .as_ref()
+                .map(|bindings| bindings.iter().map(|(&cc, &id)| (cc, id)).collect()),
+            ramp: preset.ramp,
+            knob_mod: preset.knob_mod,
+            amplitude_taper: preset.amplitude_taper,
+            midi_modes: preset
+                .midi_modes
+                .iter()
+                .map(|(&cc, &mode)| (cc, mode))
+                .collect(),
+            phase_invert: preset.phase_invert,
+            xy_pads: preset.xy_pads.clone(),
+            xy_pad_tab: preset.xy_pad_tab,
+            lfo_rate: preset.lfo_rate,
+            lfo_depth: preset.lfo_depth,
+            lfo_shape: preset.lfo_shape,
+            band_low: preset.band_low,
+            band_high: preset.band_high,
+            smoothing: preset.smoothing,
+        }
+    }
+}
+
+impl PresetV11 {
+    fn into_preset(self, version: u32) -> Preset {
+        Preset {
+            version,
+            h_slider: self.h_slider,
+            v_slider: self.v_slider,
+            knob: self.knob,
+            xy_pad_x: self.xy_pad_x,
+            xy_pad_y: self.xy_pad_y,
+            slider_value: self.slider_value,
+            button_id: self.button_id,
+            midi_bindings: self
+                .midi_bindings
+                .map(|bindings| bindings.into_iter().collect()),
+            ramp: self.ramp,
+            knob_mod: self.knob_mod,
+            amplitude_taper: self.amplitude_taper,
+            midi_modes: self.midi_modes.into_iter().collect(),
+            phase_invert: self.phase_invert,
+            xy_pads: self.xy_pads,
+            xy_pad_tab: self.xy_pad_tab,
+            lfo_rate: self.lfo_rate,
+            lfo_depth: self.lfo_depth,
+            lfo_shape: self.lfo_shape,
+            band_low: self.band_low,
+            band_high: self.band_high,
+            smoothing: self.smoothing,
+        }
+    }
+}
+
+// Reads the records versions 1 to 10 were written in. Tags it doesn't know
+// are skipped and fields that are missing take the same defaults as JSON.
+fn decode_records(version: u32, mut rest: &[u8]) -> Result<Preset, PresetError> {
+    let mut preset = Preset {
+        version,
+        h_slider: f32::NAN,
+        v_slider: f32::NAN,
+        knob: f32::NAN,
+        xy_pad_x: f32::NAN,
+        xy_pad_y: f32::NAN,
+        slider_value: f32::NAN,
+        button_id: 0,
+        midi_bindings: None,
+        ramp: default_ramp(),
+        knob_mod: default_knob_mod(),
+        amplitude_taper: false,
+        midi_modes: HashMap::new(),
+        phase_invert: false,
+        xy_pads: Vec::new(),
+        xy_pad_tab: 0,
+        lfo_rate: default_lfo_rate(),
+        lfo_depth: default_lfo_depth(),
+        lfo_shape: Shape::default(),
+        band_low: None,
+        band_high: None,
+        smoothing: None,
+    };
+    let mut has_button_id = false;
+
+    while !rest.is_empty() {
+        let (header, after) = split(rest, 3)?;
+        let length = usize::from(u16::from_le_bytes([header[1], header[2]]));
+        let (payload, after) = split(after, length)?;
+        rest = after;
+
+        match header[0] {
+            TAG_H_SLIDER => preset.h_slider = float(payload)?,
+            TAG_V_SLIDER => preset.v_slider = float(payload)?,
+            TAG_KNOB => preset.knob = float(payload)?,
+            TAG_XY_PAD_X => preset.xy_pad_x = float(payload)?,
+            TAG_XY_PAD_Y => preset.xy_pad_y = float(payload)?,
+            TAG_SLIDER_VALUE => preset.slider_value = float(payload)?,
+            TAG_BUTTON_ID => {
+                preset.button_id = byte(payload)?;
+                has_button_id = true;
+            }
+            TAG_MIDI_BINDINGS => {
+                if payload.len() % 2 != 0 {
+                    return Err(PresetError::Binary("truncated MIDI binding"));
+                }
+
+                let bindings = payload
+                    .chunks(2)
+                    .map(|pair| Ok((pair[0], param_from_code(pair[1])?)))
+                    .collect::<Result<_, PresetError>>()?;
+                preset.midi_bindings = Some(bindings);
+            }
+            TAG_RAMP => preset.ramp = float(payload)?,
+            TAG_KNOB_MOD => preset.knob_mod = float(payload)?,
+            TAG_AMPLITUDE_TAPER => preset.amplitude_taper = byte(payload)? != 0,
+            TAG_MIDI_MODES => {
+                if payload.len() % 2 != 0 {
+                    return Err(PresetError::Binary("truncated MIDI mode"));
+                }
+
+                preset.midi_modes = payload
+                    .chunks(2)
+                    .map(|pair| Ok((pair[0], mode_from_code(pair[1])?)))
+                    .collect::<Result<_, PresetError>>()?;
+            }
+            TAG_PHASE_INVERT => preset.phase_invert = byte(payload)? != 0,
+            TAG_XY_PADS => {
+                if payload.len() % 8 != 0 {
+                    return Err(PresetError::Binary("truncated XY pad"));
+                }
+
+                preset.xy_pads = payload
+                    .chunks(8)
+                    .map(|pad| Ok((float(&pad[..4])?, float(&pad[4..])?)))
+                    .collect::<Result<_, PresetError>>()?;
+            }
+            TAG_XY_PAD_TAB => preset.xy_pad_tab = byte(payload)?,
+            TAG_LFO_RATE => preset.lfo_rate = float(payload)?,
+            TAG_LFO_DEPTH => preset.lfo_depth = float(payload)?,
+            TAG_LFO_SHAPE => preset.lfo_shape = shape_from_code(byte(payload)?)?,
+            TAG_BAND_LOW => preset.band_low = Some(float(payload)?),
+            TAG_BAND_HIGH => preset.band_high = Some(float(payload)?),
+            // Written by a later version.
+            _ => {}
+        }
+    }
+
+    // The fields every version has can't be defaulted.
+    let required = [
+        preset.h_slider,
+        preset.v_slider,
+        preset.knob,
+        preset.xy_pad_x,
+        preset.xy_pad_y,
+        preset.slider_value,
+    ];
+    if required.iter().any(|value| value.is_nan()) || !has_button_id {
+        return Err(PresetError::Binary("missing a required field"));
+    }
+
+    Ok(preset)
+}
+
+/// Whether `path` is named like a preset: `.json`, or `.bin` for the binary
+/// format.
+pub fn is_preset(path: &Path) -> bool {
+    matches!(
+        path.extension().and_then(OsStr::to_str),
+        Some("json" | "bin")
+    )
+}
+
+fn is_binary(path: &Path) -> bool {
+    path.extension() == Some(OsStr::new("bin"))
+}
+
+// The first `n` bytes and the rest, or an error if there aren't enough.
+fn split(bytes: &[u8], n: usize) -> Result<(&[u8], &[u8]), PresetError> {
+    if bytes.len() < n {
+        return Err(PresetError::Binary("truncated"));
+    }
+
+    Ok(bytes.split_at(n))
+}
+
+fn float(payload: &[u8]) -> Result<f32, PresetError> {
+    let bytes = payload
+        .try_into()
+        .map_err(|_| PresetError::Binary("a float field isn't 4 bytes"))?;
+
+    Ok(f32::from_le_bytes(bytes))
+}
+
+fn byte(payload: &[u8]) -> Result<u8, PresetError> {
+    match payload {
+        &[byte] => Ok(byte),
+        _ => Err(PresetError::Binary("a byte field isn't 1 byte")),
+    }
+}
+
+fn param_from_code(code: u8) -> Result<ParamId, PresetError> {
+    ParamId::ALL
+        .get(usize::from(code))
+        .copied()
+        .ok_or(PresetError::Binary("unknown parameter"))
+}
+
+fn mode_from_code(code: u8) -> Result<CcMode, PresetError> {
+    match code {
+        0 => Ok(CcMode::Absolute),
+        1 => Ok(CcMode::Relative),
+        _ => Err(PresetError::Binary("unknown MIDI mode")),
+    }
+}
+
+fn shape_from_code(code: u8) -> Result<Shape, PresetError> {
+    match code {
+        0 => Ok(Shape::Sine),
+        1 => Ok(Shape::Triangle),
+        2 => Ok(Shape::Square),
+        3 => Ok(Shape::Saw),
+        _ => Err(PresetError::Binary("unknown LFO shape")),
+    }
+}
+
+#[derive(Debug)]
+pub enum PresetError {
+    Io(io::Error),
+    Json(serde_json::Error),
+    // A binary preset whose header can't be read, and why.
+    Binary(&'static str),
+    // A binary preset whose fields can't be decoded.
+    Bincode(bincode::Error),
+    // The preset was saved by a newer version of the app.
+    UnsupportedVersion(u32),
+    // A parameter's normal isn't a number, or the LFO's rate isn't above 0,
+    // so neither can be clamped into range.
+    OutOfRange,
+}
+
+impl fmt::Display for PresetError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            PresetError::Io(error) => write!(f, "{error}"),
+            PresetError::Json(error) => write!(f, "invalid preset: {error}"),
+            PresetError::Binary(reason) => write!(f, "invalid binary preset: {reason}"),
+            PresetError::Bincode(error) => write!(f, "invalid binary preset: {error}"),
+            PresetError::UnsupportedVersion(version) => {
+                write!(f, "unsupported preset version {version}")
+            }
+            PresetError::OutOfRange => write!(f, "a value can't be brought into range"),
+        }
+    }
+}
+
+impl std::error::Error for PresetError {}
+
+impl From<io::Error> for PresetError {
+    fn from(error: io::Error) -> Self {
+        PresetError::Io(error)
+    }
+}
+
+impl From<serde_json::Error> for PresetError {
+    fn from(error: serde_json::Error) -> Self {
+        PresetError::Json(error)
+    }
+}
+
+impl From<bincode::Error> for PresetError {
+    fn from(error: bincode::Error) -> Self {
+        PresetError::Bincode(error)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tests::{temp_dir, test_preset};
+
+    #[test]
+    fn binary_round_trips_exactly() {
+        let preset = test_preset();
+        let decoded = Preset::decode(&preset.encode()).unwrap();
+
+        assert_eq!(decoded, preset);
+        assert_eq!(decoded.v_slider.to_bits(), preset.v_slider.to_bits());
+    }
+
+    #[test]
+    fn binary_versions_are_checked() {
+        let bytes = test_preset().encode();
+        let with_version = |version: u32| {
+            let mut bytes = bytes.clone();
+            bytes[4..8].copy_from_slice(&version.to_le_bytes());
+            bytes
+        };
+
+        assert!(matches!(
+            Preset::decode(&with_version(PRESET_VERSION + 1)),
+            Err(PresetError::UnsupportedVersion(version)) if version == PRESET_VERSION + 1
+        ));
+        assert!(matches!(
+            Preset::decode(&bytes[..bytes.len() - 4]),
+            Err(PresetError::Bincode(_))
+        ));
+        assert!(matches!(
+            Preset::decode(b"JSON{}"),
+            Err(PresetError::Binary(_))
+        ));
+    }
+
+    #[test]
+    fn encoding_is_the_same_however_the_maps_were_built() {
+        let bindings = (0..32).map(|cc| (cc, ParamId::ALL[usize::from(cc) % 11]));
+        let forward = Preset {
+            midi_bindings: Some(bindings.clone().collect()),
+            ..test_preset()
+        };
+        let backward = Preset {
+            midi_bindings: Some(bindings.rev().collect()),
+            ..test_preset()
+        };
+
+        assert_eq!(forward.encode(), backward.encode());
+    }
+
+    #[test]
+    fn version_10_records_still_decode() {
+        let mut bytes = MAGIC.to_vec();
+        bytes.extend(10_u32.to_le_bytes());
+        let mut record = |tag: u8, payload: &[u8]| {
+            bytes.push(tag);
+            bytes.extend((payload.len() as u16).to_le_bytes());
+            bytes.extend(payload);
+        };
+        record(TAG_H_SLIDER, &0.1_f32.to_le_bytes());
+        record(TAG_V_SLIDER, &0.2_f32.to_le_bytes());
+        record(TAG_KNOB, &0.3_f32.to_le_bytes());
+        record(TAG_XY_PAD_X, &0.4_f32.to_le_bytes());
+        record(TAG_XY_PAD_Y, &0.5_f32.to_le_bytes());
+        record(TAG_SLIDER_VALUE, &0.6_f32.to_le_bytes());
+        record(TAG_BUTTON_ID, &[3]);
+        // KnobFreq and Relative, by their codes.
+        record(TAG_MIDI_BINDINGS, &[1, 2]);
+        record(TAG_MIDI_MODES, &[1, 1]);
+        record(TAG_LFO_SHAPE, &[2]);
+        record(TAG_BAND_LOW, &0.25_f32.to_le_bytes());
+        // A field from some later version.
+        record(200, &[0xAB, 0xCD]);
+
+        let decoded = Preset::decode(&bytes).unwrap();
+        assert_eq!(decoded.version, 10);
+        assert_eq!(decoded.knob, 0.3);
+        assert_eq!(decoded.button_id, 3);
+        assert_eq!(
+            decoded.midi_bindings,
+            Some(HashMap::from([(1, ParamId::KnobFreq)]))
+        );
+        assert_eq!(decoded.midi_modes, HashMap::from([(1, CcMode::Relative)]));
+        assert_eq!(decoded.lfo_shape, Shape::Square);
+        assert_eq!(decoded.band_low, Some(0.25));
+        // Missing, so defaulted like JSON.
+        assert_eq!(decoded.ramp, default_ramp());
+        assert_eq!(decoded.lfo_rate, lfo::DEFAULT_RATE);
+        assert_eq!(decoded.band_high, None);
+        assert_eq!(decoded.smoothing, None);
+        assert_eq!(decoded.migrate().version, PRESET_VERSION);
+
+        // Cut off before the button, which every version has.
+        let button_at = 8 + 6 * 7;
+        assert_eq!(bytes[button_at], TAG_BUTTON_ID);
+        assert!(Preset::decode(&bytes[..button_at]).is_err());
+    }
+
+    #[test]
+    fn version_1_records_drop_bindings_when_loaded() {
+        let dir = temp_dir("preset_v1");
+        let path = dir.join("old.bin");
+        let mut bytes = MAGIC.to_vec();
+        bytes.extend(1_u32.to_le_bytes());
+        for tag in [
+            TAG_H_SLIDER,
+            TAG_V_SLIDER,
+            TAG_KNOB,
+            TAG_XY_PAD_X,
+            TAG_XY_PAD_Y,
+            TAG_SLIDER_VALUE,
+        ] {
+            bytes.extend([tag, 4, 0]);
+            bytes.extend(0.5_f32.to_le_bytes());
+        }
+        bytes.extend([TAG_BUTTON_ID, 1, 0, 0]);
+        bytes.extend([TAG_MIDI_BINDINGS, 2, 0, 1, 2]);
+        fs::write(&path, bytes).unwrap();
+
+        let loaded = Preset::load(&path).unwrap();
+        assert_eq!(loaded.version, PRESET_VERSION);
+        assert_eq!(loaded.knob, 0.5);
+        assert_eq!(loaded.midi_bindings, None);
+    }
+
+    #[test]
+    fn save_replaces_the_whole_file() {
+        let dir = temp_dir("preset");
+        let path = dir.join("preset.json");
+        fs::write(&path, "an older, longer file that's no preset at all").unwrap();
+
+        test_preset().save(&path).unwrap();
+        let loaded = Preset::load(&path);
+        let files = fs::read_dir(&dir).unwrap().count();
+
+        assert_eq!(loaded.unwrap(), test_preset());
+        // Nothing's left beside it.
+        assert_eq!(files, 1);
+    }
+
+    #[test]
+    fn normals_that_are_not_numbers_are_rejected() {
+        assert!(test_preset().validate().is_ok());
+        // Clamped when applied instead.
+        let wide = Preset {
+            knob: 1.5,
+            xy_pads: vec![(0.5, -0.1)],
+            ..test_preset()
+        };
+        assert!(wide.validate().is_ok());
+
+        for broken in [
+            Preset {
+                v_slider: f32::NAN,
+                ..test_preset()
+            },
+            Preset {
+                xy_pads: vec![(0.5, f32::INFINITY)],
+                ..test_preset()
+            },
+            Preset {
+                lfo_rate: 0.0,
+                ..test_preset()
+            },
+        ] {
+            assert!(matches!(broken.validate(), Err(PresetError::OutOfRange)));
+        }
+    }
+
+    #[test]
+    fn version_1_drops_bindings() {
+        let mut old = test_preset();
+        old.version = 1;
+        let migrated = old.migrate();
+
+        assert_eq!(migrated.version, PRESET_VERSION);
+        assert_eq!(migrated.midi_bindings, None);
+        assert!(migrated.midi_modes.is_empty());
+    }
+}
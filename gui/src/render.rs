@@ -0,0 +1,239 @@
+//! Draw one frame of the app to a PNG without opening a window, for
+//! documentation.
+
+use iced::{Application, Color, Element, Size};
+use iced_native::renderer::Style;
+use iced_native::user_interface::{Cache, UserInterface};
+use iced_native::Point;
+use iced_wgpu::wgpu;
+use iced_wgpu::{Backend, Renderer, Settings, Viewport};
+
+use std::fmt;
+use std::fs::File;
+use std::io::{self, BufWriter};
+use std::num::NonZeroU32;
+use std::path::Path;
+
+use crate::{App, Message};
+
+// Rows of pixels in the buffer copied back from the GPU start this many
+// bytes apart, so a row is padded out to a multiple of it.
+const ROW_ALIGNMENT: usize = wgpu::COPY_BYTES_PER_ROW_ALIGNMENT as usize;
+
+// Matches the frame the window draws, so the PNG looks as the window does.
+const FORMAT: wgpu::TextureFormat = wgpu::TextureFormat::Rgba8UnormSrgb;
+
+#[derive(Debug)]
+pub enum RenderError {
+    // No GPU, or software fallback, could be found to draw with.
+    NoAdapter,
+    Device(wgpu::RequestDeviceError),
+    // The drawn frame couldn't be read back from the GPU.
+    Readback(wgpu::BufferAsyncError),
+    Io(io::Error),
+    Png(png::EncodingError),
+}
+
+impl fmt::Display for RenderError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            RenderError::NoAdapter => write!(f, "no graphics adapter to render with"),
+            RenderError::Device(error) => write!(f, "couldn't open the graphics device: {error}"),
+            RenderError::Readback(error) => write!(f, "couldn't read the frame back: {error}"),
+            RenderError::Io(error) => write!(f, "{error}"),
+            RenderError::Png(error) => write!(f, "couldn't encode the PNG: {error}"),
+        }
+    }
+}
+
+impl std::error::Error for RenderError {}
+
+impl From<io::Error> for RenderError {
+    fn from(error: io::Error) -> Self {
+        RenderError::Io(error)
+    }
+}
+
+impl From<png::EncodingError> for RenderError {
+    fn from(error: png::EncodingError) -> Self {
+        RenderError::Png(error)
+    }
+}
+
+/// Lays `app` out in a window `width` by `height` logical pixels, draws it
+/// once with nothing hovered or focused, and writes the frame to `path` as
+/// a PNG.
+pub fn render(app: &mut App, width: u32, height: u32, path: &Path) -> Result<(), RenderError> {
+    let _ = app.update(Message::WindowResized(width, height));
+
+    let instance = wgpu::Instance::new(wgpu::Backends::all());
+    let adapter =
+        iced::futures::executor::block_on(instance.request_adapter(&wgpu::RequestAdapterOptions {
+            power_preference: wgpu::PowerPreference::default(),
+            compatible_surface: None,
+            force_fallback_adapter: false,
+        }))
+        .ok_or(RenderError::NoAdapter)?;
+    let (device, queue) = iced::futures::executor::block_on(adapter.request_device(
+        &wgpu::DeviceDescriptor {
+            label: Some("paris_green render device"),
+            features: wgpu::Features::empty(),
+            limits: wgpu::Limits::downlevel_defaults(),
+        },
+        None,
+    ))
+    .map_err(RenderError::Device)?;
+
+    let theme = app.theme();
+    let mut renderer = Renderer::new(Backend::new(&device, Settings::default(), FORMAT));
+    let element: Element<'_, Message> = app.view();
+    let mut ui = UserInterface::build(
+        element,
+        Size::new(width as f32, height as f32),
+        Cache::default(),
+        &mut renderer,
+    );
+    let style = Style {
+        text_color: theme.palette().text,
+    };
+    // Off the window, so nothing draws hovered.
+    ui.draw(&mut renderer, &theme, &style, Point::new(-1.0, -1.0));
+
+    let extent = wgpu::Extent3d {
+        width,
+        height,
+        depth_or_array_layers: 1,
+    };
+    let texture = device.create_texture(&wgpu::TextureDescriptor {
+        label: Some("paris_green render target"),
+        size: extent,
+        mip_level_count: 1,
+        sample_count: 1,
+        dimension: wgpu::TextureDimension::D2,
+        format: FORMAT,
+        usage: wgpu::TextureUsages::RENDER_ATTACHMENT | wgpu::TextureUsages::COPY_SRC,
+    });
+    let view = texture.create_view(&wgpu::TextureViewDescriptor::default());
+    let padded_row = padded_row(width as usize * 4);
+    let buffer = device.create_buffer(&wgpu::BufferDescriptor {
+        label: Some("paris_green render readback"),
+        size: (padded_row * height as usize) as u64,
+        usage: wgpu::BufferUsages::COPY_DST | wgpu::BufferUsages::MAP_READ,
+        mapped_at_creation: false,
+    });
+
+    let mut encoder = device.create_command_encoder(&wgpu::CommandEncoderDescriptor {
+        label: Some("paris_green render encoder"),
+    });
+    clear(&mut encoder, &view, theme.palette().background);
+    let mut staging_belt = wgpu::util::StagingBelt::new(10 * 1024);
+    let viewport = Viewport::with_physical_size(Size::new(width, height), 1.0);
+    renderer.with_primitives(|backend, primitives| {
+        backend.present::<&str>(
+            &device,
+            &mut staging_belt,
+            &mut encoder,
+            &view,
+            primitives,
+            &viewport,
+            &[],
+        );
+    });
+    encoder.copy_texture_to_buffer(
+        wgpu::ImageCopyTexture {
+            texture: &texture,
+            mip_level: 0,
+            origin: wgpu::Origin3d::ZERO,
+            aspect: wgpu::TextureAspect::All,
+        },
+        wgpu::ImageCopyBuffer {
+            buffer: &buffer,
+            layout: wgpu::ImageDataLayout {
+                offset: 0,
+                bytes_per_row: NonZeroU32::new(padded_row as u32),
+                rows_per_image: None,
+            },
+        },
+        extent,
+    );
+    staging_belt.finish();
+    queue.submit(Some(encoder.finish()));
+
+    let (sender, receiver) = std::sync::mpsc::channel();
+    let slice = buffer.slice(..);
+    slice.map_async(wgpu::MapMode::Read, move |result| {
+        let _ = sender.send(result);
+    });
+    device.poll(wgpu::Maintain::Wait);
+    receiver
+        .recv()
+        .expect("the readback callback runs before polling returns")
+        .map_err(RenderError::Readback)?;
+    let pixels = unpadded(&slice.get_mapped_range(), width as usize * 4, padded_row);
+
+    let mut encoder = png::Encoder::new(BufWriter::new(File::create(path)?), width, height);
+    encoder.set_color(png::ColorType::Rgba);
+    encoder.set_depth(png::BitDepth::Eight);
+    encoder.write_header()?.write_image_data(&pixels)?;
+
+    Ok(())
+}
+
+// Fills the frame with the theme's background, as the window's is before
+// anything's drawn on it.
+fn clear(encoder: &mut wgpu::CommandEncoder, view: &wgpu::TextureView, background: Color) {
+    let [r, g, b, a] = background.into_linear();
+
+    let _ = encoder.begin_render_pass(&wgpu::RenderPassDescriptor {
+        label: Some("paris_green render clear"),
+        color_attachments: &[Some(wgpu::RenderPassColorAttachment {
+            view,
+            resolve_target: None,
+            ops: wgpu::Operations {
+                load: wgpu::LoadOp::Clear(wgpu::Color {
+                    r: f64::from(r),
+                    g: f64::from(g),
+                    b: f64::from(b),
+                    a: f64::from(a),
+                }),
+                store: true,
+            },
+        })],
+        depth_stencil_attachment: None,
+    });
+}
+
+// How many bytes a row of `row` bytes takes in the readback buffer.
+fn padded_row(row: usize) -> usize {
+    row.div_ceil(ROW_ALIGNMENT) * ROW_ALIGNMENT
+}
+
+// The pixels in `data`, each row `row` bytes long but `padded_row` apart,
+// packed together.
+fn unpadded(data: &[u8], row: usize, padded_row: usize) -> Vec<u8> {
+    data.chunks(padded_row)
+        .flat_map(|padded| &padded[..row])
+        .copied()
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn rows_are_padded_then_packed_again() {
+        assert_eq!(padded_row(256), 256);
+        assert_eq!(padded_row(4), 256);
+        assert_eq!(padded_row(1024 * 4 + 4), 1024 * 4 + 256);
+
+        let mut data = vec![0; 2 * ROW_ALIGNMENT];
+        data[..8].copy_from_slice(&[1, 2, 3, 4, 5, 6, 7, 8]);
+        data[ROW_ALIGNMENT..ROW_ALIGNMENT + 8].copy_from_slice(&[9; 8]);
+
+        assert_eq!(
+            unpadded(&data, 8, ROW_ALIGNMENT),
+            [1, 2, 3, 4, 5, 6, 7, 8, 9, 9, 9, 9, 9, 9, 9, 9]
+        );
+    }
+}
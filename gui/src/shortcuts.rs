@@ -0,0 +1,155 @@
+//! Every keyboard shortcut, in one table that both the key handling and the
+//! help overlay read, so the help can't drift from what the keys do.
+
+use iced::keyboard::{KeyCode, Modifiers};
+
+use crate::{Message, NUDGE_PAGE};
+
+pub struct Shortcut {
+    pub key: KeyCode,
+    // The modifiers that must be held. Others may be held too.
+    pub modifiers: Modifiers,
+    // How the help shows the keys.
+    pub label: &'static str,
+    pub action: &'static str,
+    pub message: Message,
+}
+
+/// Every shortcut, in the order they're matched and listed. A shortcut
+/// needing more modifiers comes before one on the same key needing fewer,
+/// or the fewer would always match first.
+pub const SHORTCUTS: &[Shortcut] = &[
+    Shortcut {
+        key: KeyCode::Z,
+        modifiers: Modifiers::COMMAND.union(Modifiers::SHIFT),
+        label: "Ctrl+Shift+Z",
+        action: "Redo",
+        message: Message::Redo,
+    },
+    Shortcut {
+        key: KeyCode::Z,
+        modifiers: Modifiers::COMMAND,
+        label: "Ctrl+Z",
+        action: "Undo",
+        message: Message::Undo,
+    },
+    Shortcut {
+        key: KeyCode::Y,
+        modifiers: Modifiers::COMMAND,
+        label: "Ctrl+Y",
+        action: "Redo",
+        message: Message::Redo,
+    },
+    Shortcut {
+        key: KeyCode::Tab,
+        modifiers: Modifiers::SHIFT,
+        label: "Shift+Tab",
+        action: "Focus the previous widget",
+        message: Message::FocusPrevious,
+    },
+    Shortcut {
+        key: KeyCode::Tab,
+        modifiers: Modifiers::empty(),
+        label: "Tab",
+        action: "Focus the next widget",
+        message: Message::FocusNext,
+    },
+    Shortcut {
+        key: KeyCode::Right,
+        modifiers: Modifiers::empty(),
+        label: "Right",
+        action: "Step the focused widget up",
+        message: Message::Nudge(1),
+    },
+    Shortcut {
+        key: KeyCode::Left,
+        modifiers: Modifiers::empty(),
+        label: "Left",
+        action: "Step the focused widget down",
+        message: Message::Nudge(-1),
+    },
+    Shortcut {
+        key: KeyCode::Up,
+        modifiers: Modifiers::empty(),
+        label: "Up",
+        action: "Step the focused widget's vertical value up",
+        message: Message::NudgeVertical(1),
+    },
+    Shortcut {
+        key: KeyCode::Down,
+        modifiers: Modifiers::empty(),
+        label: "Down",
+        action: "Step the focused widget's vertical value down",
+        message: Message::NudgeVertical(-1),
+    },
+    Shortcut {
+        key: KeyCode::PageUp,
+        modifiers: Modifiers::empty(),
+        label: "Page Up",
+        action: "Step the focused widget up by a page",
+        message: Message::NudgeVertical(NUDGE_PAGE),
+    },
+    Shortcut {
+        key: KeyCode::PageDown,
+        modifiers: Modifiers::empty(),
+        label: "Page Down",
+        action: "Step the focused widget down by a page",
+        message: Message::NudgeVertical(-NUDGE_PAGE),
+    },
+    Shortcut {
+        key: KeyCode::N,
+        modifiers: Modifiers::empty(),
+        label: "N",
+        action: "Type a note for the frequency knob",
+        message: Message::FocusNoteInput,
+    },
+    // Shift+/ is ? on most layouts.
+    Shortcut {
+        key: KeyCode::Slash,
+        modifiers: Modifiers::SHIFT,
+        label: "?",
+        action: "Show or hide this help",
+        message: Message::ToggleHelp,
+    },
+    Shortcut {
+        key: KeyCode::Escape,
+        modifiers: Modifiers::empty(),
+        label: "Esc",
+        action: "Close this help",
+        message: Message::CloseHelp,
+    },
+];
+
+/// The message for a key pressed with `modifiers` held, if it's a shortcut.
+pub fn message(key: KeyCode, modifiers: Modifiers) -> Option<Message> {
+    SHORTCUTS
+        .iter()
+        .find(|shortcut| shortcut.key == key && modifiers.contains(shortcut.modifiers))
+        .map(|shortcut| shortcut.message.clone())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn every_shortcut_is_reachable() {
+        for shortcut in SHORTCUTS {
+            let message = message(shortcut.key, shortcut.modifiers);
+
+            assert_eq!(
+                format!("{message:?}"),
+                format!("{:?}", Some(&shortcut.message)),
+                "{} is shadowed",
+                shortcut.label
+            );
+        }
+    }
+
+    #[test]
+    fn extra_modifiers_still_match() {
+        let message = message(KeyCode::Right, Modifiers::CTRL);
+
+        assert!(matches!(message, Some(Message::Nudge(1))));
+    }
+}
@@ -0,0 +1,60 @@
+//! Take parameter values an embedding program sends from another thread,
+//! such as a DSP thread, polling for them once a frame.
+
+use iced::subscription::{self, Subscription};
+
+use std::sync::mpsc::{Receiver, TryRecvError};
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+use crate::param::ParamId;
+
+// How long to wait before polling again once the channel's empty.
+const FRAME: Duration = Duration::from_millis(16);
+
+/// Where an embedding program sends values in from: each a parameter and
+/// its new value, mapped as [`AppBuilder::on_change`] reports it.
+///
+/// [`AppBuilder::on_change`]: crate::AppBuilder::on_change
+pub type Source = Receiver<(ParamId, f32)>;
+
+#[derive(Debug, Clone)]
+pub enum Event {
+    Param(ParamId, f32),
+    // Every sender has been dropped, so nothing more can arrive.
+    Closed,
+}
+
+/// Emits each value sent on `source`. It never blocks: what's waiting is
+/// emitted straight away, and an empty channel is polled again a frame
+/// later.
+pub fn subscription(source: Arc<Mutex<Source>>) -> Subscription<Event> {
+    struct External;
+
+    subscription::unfold(
+        std::any::TypeId::of::<External>(),
+        State::Polling(source),
+        |state| async move {
+            match state {
+                State::Polling(source) => {
+                    let received = source.lock().unwrap().try_recv();
+
+                    match received {
+                        Ok((id, value)) => (Some(Event::Param(id, value)), State::Polling(source)),
+                        Err(TryRecvError::Empty) => {
+                            tokio::time::sleep(FRAME).await;
+                            (None, State::Polling(source))
+                        }
+                        Err(TryRecvError::Disconnected) => (Some(Event::Closed), State::Finished),
+                    }
+                }
+                State::Finished => iced::futures::future::pending().await,
+            }
+        },
+    )
+}
+
+enum State {
+    Polling(Arc<Mutex<Source>>),
+    Finished,
+}
@@ -0,0 +1,88 @@
+//! Count how each parameter has been used this session.
+
+use std::collections::HashMap;
+
+use crate::param::ParamId;
+
+/// How often a parameter changed, and the lowest and highest values it
+/// reached, in its range's values rather than normals.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Usage {
+    pub changes: u32,
+    pub min: f32,
+    pub max: f32,
+}
+
+/// Every parameter's [`Usage`] since the session started or was last reset.
+/// A parameter that hasn't changed has none.
+#[derive(Debug, Default)]
+pub struct Stats {
+    usage: HashMap<ParamId, Usage>,
+}
+
+impl Stats {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Counts a change to `id`, which now has `value`.
+    pub fn record(&mut self, id: ParamId, value: f32) {
+        self.usage
+            .entry(id)
+            .and_modify(|usage| {
+                usage.changes += 1;
+                usage.min = usage.min.min(value);
+                usage.max = usage.max.max(value);
+            })
+            .or_insert(Usage {
+                changes: 1,
+                min: value,
+                max: value,
+            });
+    }
+
+    pub fn get(&self, id: ParamId) -> Option<Usage> {
+        self.usage.get(&id).copied()
+    }
+
+    /// The parameters that have changed, with their usage, in the order of
+    /// [`ParamId::ALL`].
+    pub fn iter(&self) -> impl Iterator<Item = (ParamId, Usage)> + '_ {
+        ParamId::ALL
+            .into_iter()
+            .filter_map(|id| Some((id, self.get(id)?)))
+    }
+
+    pub fn reset(&mut self) {
+        self.usage.clear();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn tracks_the_count_and_extremes() {
+        let mut stats = Stats::new();
+
+        stats.record(ParamId::KnobFreq, 440.0);
+        stats.record(ParamId::KnobFreq, 110.0);
+        stats.record(ParamId::KnobFreq, 220.0);
+        stats.record(ParamId::HSliderInt, 3.0);
+
+        assert_eq!(
+            stats.get(ParamId::KnobFreq),
+            Some(Usage {
+                changes: 3,
+                min: 110.0,
+                max: 440.0,
+            })
+        );
+        let ids: Vec<_> = stats.iter().map(|(id, _)| id).collect();
+        assert_eq!(ids, [ParamId::HSliderInt, ParamId::KnobFreq]);
+
+        stats.reset();
+        assert_eq!(stats.get(ParamId::KnobFreq), None);
+    }
+}
@@ -0,0 +1,578 @@
+//! Style sheets for the iced_audio widgets, on top of their defaults.
+//!
+//! The iced_audio defaults are drawn for a light background. [`Themed`]
+//! starts from them and, under the dark theme, shades every color so the
+//! widgets read well on a dark background. Tick marks instead take their
+//! colors and sizes from a [`TickStyle`], one tier at a time.
+
+use iced::widget::container;
+use iced::{Color, Theme};
+use iced_audio::knob::LineCap;
+use iced_audio::style::{
+    h_slider, knob, mod_range_input, ramp, text_marks, theme, tick_marks, v_slider, xy_pad,
+};
+
+/// The default look of every parameter widget, following the app's theme.
+///
+/// Knobs also get an arc around them showing their modulation range. Tick
+/// marks on the sliders and knobs are drawn in `ticks`, or the theme's own
+/// [`TickStyle`] when that's `None`.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct Themed {
+    pub ticks: Option<TickStyle>,
+}
+
+impl Themed {
+    fn tick_marks(&self, theme: &Theme) -> tick_marks::Appearance {
+        self.ticks
+            .unwrap_or_else(|| TickStyle::for_theme(theme))
+            .appearance()
+    }
+}
+
+/// How one tier of tick marks is drawn: a line `length` long and `width`
+/// wide, in logical pixels.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct TickTier {
+    pub color: Color,
+    pub length: f32,
+    pub width: f32,
+}
+
+/// The color and size of each tier of tick marks, the same on every slider
+/// and knob.
+///
+/// ```
+/// use iced::{Color, Theme};
+/// use paris_green_gui::TickStyle;
+///
+/// let mut ticks = TickStyle::for_theme(&Theme::Dark);
+/// ticks.tier_1.color = Color::from_rgb(0.9, 0.6, 0.1);
+/// ticks.tier_1.length = 6.0;
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct TickStyle {
+    pub tier_1: TickTier,
+    pub tier_2: TickTier,
+    pub tier_3: TickTier,
+}
+
+impl TickStyle {
+    /// The theme's palette: tier one in its text color, tier two in its
+    /// primary color, and tier three in a faded text color, each shorter
+    /// than the last as the iced_audio defaults are.
+    pub fn for_theme(theme: &Theme) -> Self {
+        let palette = theme.palette();
+
+        TickStyle {
+            tier_1: TickTier {
+                color: palette.text,
+                length: 4.0,
+                width: 2.0,
+            },
+            tier_2: TickTier {
+                color: palette.primary,
+                length: 3.0,
+                width: 2.0,
+            },
+            tier_3: TickTier {
+                color: Color {
+                    a: 0.5,
+                    ..palette.text
+                },
+                length: 2.0,
+                width: 1.0,
+            },
+        }
+    }
+
+    pub fn appearance(&self) -> tick_marks::Appearance {
+        let line = |tier: TickTier| tick_marks::Shape::Line {
+            length: tier.length,
+            width: tier.width,
+            color: tier.color,
+        };
+
+        tick_marks::Appearance {
+            tier_1: line(self.tier_1),
+            tier_2: line(self.tier_2),
+            tier_3: line(self.tier_3),
+        }
+    }
+}
+
+// Light colors turn dark and dark ones light, keeping some contrast with
+// the dark theme's background.
+fn shade(theme: &Theme, color: Color) -> Color {
+    match theme {
+        Theme::Dark => Color {
+            r: 1.0 - color.r * 0.85,
+            g: 1.0 - color.g * 0.85,
+            b: 1.0 - color.b * 0.85,
+            a: color.a,
+        },
+        _ => color,
+    }
+}
+
+fn shade_text_marks(theme: &Theme, mut style: text_marks::Appearance) -> text_marks::Appearance {
+    style.color = shade(theme, style.color);
+    style
+}
+
+impl h_slider::StyleSheet for Themed {
+    type Style = Theme;
+
+    fn active(&self, theme: &Theme) -> h_slider::Appearance {
+        shade_h_slider(
+            theme,
+            h_slider::StyleSheet::active(theme, &theme::HSlider::Default),
+        )
+    }
+
+    fn hovered(&self, theme: &Theme) -> h_slider::Appearance {
+        shade_h_slider(
+            theme,
+            h_slider::StyleSheet::hovered(theme, &theme::HSlider::Default),
+        )
+    }
+
+    fn dragging(&self, theme: &Theme) -> h_slider::Appearance {
+        shade_h_slider(
+            theme,
+            h_slider::StyleSheet::dragging(theme, &theme::HSlider::Default),
+        )
+    }
+
+    fn tick_marks_appearance(&self, theme: &Theme) -> Option<h_slider::TickMarksAppearance> {
+        h_slider::StyleSheet::tick_marks_appearance(theme, &theme::HSlider::Default).map(
+            |mut appearance| {
+                appearance.style = self.tick_marks(theme);
+                appearance
+            },
+        )
+    }
+
+    fn text_marks_appearance(&self, theme: &Theme) -> Option<h_slider::TextMarksAppearance> {
+        h_slider::StyleSheet::text_marks_appearance(theme, &theme::HSlider::Default).map(
+            |mut appearance| {
+                appearance.style = shade_text_marks(theme, appearance.style);
+                appearance
+            },
+        )
+    }
+}
+
+fn shade_h_slider(theme: &Theme, appearance: h_slider::Appearance) -> h_slider::Appearance {
+    match appearance {
+        h_slider::Appearance::Classic(mut classic) => {
+            let (top, bottom) = classic.rail.rail_colors;
+            classic.rail.rail_colors = (shade(theme, top), shade(theme, bottom));
+            classic.handle.color = shade(theme, classic.handle.color);
+            classic.handle.notch_color = shade(theme, classic.handle.notch_color);
+            classic.handle.border_color = shade(theme, classic.handle.border_color);
+            h_slider::Appearance::Classic(classic)
+        }
+        appearance => appearance,
+    }
+}
+
+/// How wide the horizontal slider's handle is. The value travels along the
+/// track between the handle's two end positions.
+pub fn h_slider_handle(theme: &Theme) -> f32 {
+    let width = match h_slider::StyleSheet::active(&Themed::default(), theme) {
+        h_slider::Appearance::Texture(texture) => texture.handle_width,
+        h_slider::Appearance::Classic(classic) => classic.handle.width,
+        h_slider::Appearance::Rect(rect) => rect.handle_width,
+        h_slider::Appearance::RectBipolar(rect) => rect.handle_width,
+    };
+
+    f32::from(width)
+}
+
+impl v_slider::StyleSheet for Themed {
+    type Style = Theme;
+
+    fn active(&self, theme: &Theme) -> v_slider::Appearance {
+        shade_v_slider(
+            theme,
+            v_slider::StyleSheet::active(theme, &theme::VSlider::Default),
+        )
+    }
+
+    fn hovered(&self, theme: &Theme) -> v_slider::Appearance {
+        shade_v_slider(
+            theme,
+            v_slider::StyleSheet::hovered(theme, &theme::VSlider::Default),
+        )
+    }
+
+    fn dragging(&self, theme: &Theme) -> v_slider::Appearance {
+        shade_v_slider(
+            theme,
+            v_slider::StyleSheet::dragging(theme, &theme::VSlider::Default),
+        )
+    }
+
+    fn tick_marks_appearance(&self, theme: &Theme) -> Option<v_slider::TickMarksAppearance> {
+        v_slider::StyleSheet::tick_marks_appearance(theme, &theme::VSlider::Default).map(
+            |mut appearance| {
+                appearance.style = self.tick_marks(theme);
+                appearance
+            },
+        )
+    }
+
+    fn text_marks_appearance(&self, theme: &Theme) -> Option<v_slider::TextMarksAppearance> {
+        v_slider::StyleSheet::text_marks_appearance(theme, &theme::VSlider::Default).map(
+            |mut appearance| {
+                appearance.style = shade_text_marks(theme, appearance.style);
+                appearance
+            },
+        )
+    }
+}
+
+fn shade_v_slider(theme: &Theme, appearance: v_slider::Appearance) -> v_slider::Appearance {
+    match appearance {
+        v_slider::Appearance::Classic(mut classic) => {
+            let (top, bottom) = classic.rail.rail_colors;
+            classic.rail.rail_colors = (shade(theme, top), shade(theme, bottom));
+            classic.handle.color = shade(theme, classic.handle.color);
+            classic.handle.notch_color = shade(theme, classic.handle.notch_color);
+            classic.handle.border_color = shade(theme, classic.handle.border_color);
+            v_slider::Appearance::Classic(classic)
+        }
+        appearance => appearance,
+    }
+}
+
+/// How tall the vertical slider's handle is, as for [`h_slider_handle`].
+pub fn v_slider_handle(theme: &Theme) -> f32 {
+    let height = match v_slider::StyleSheet::active(&Themed::default(), theme) {
+        v_slider::Appearance::Texture(texture) => texture.handle_height,
+        v_slider::Appearance::Classic(classic) => classic.handle.height,
+        v_slider::Appearance::Rect(rect) => rect.handle_height,
+        v_slider::Appearance::RectBipolar(rect) => rect.handle_height,
+    };
+
+    f32::from(height)
+}
+
+impl knob::StyleSheet for Themed {
+    type Style = Theme;
+
+    fn active(&self, theme: &Theme) -> knob::Appearance {
+        shade_knob(
+            theme,
+            knob::StyleSheet::active(theme, &theme::Knob::Default),
+        )
+    }
+
+    fn hovered(&self, theme: &Theme) -> knob::Appearance {
+        shade_knob(
+            theme,
+            knob::StyleSheet::hovered(theme, &theme::Knob::Default),
+        )
+    }
+
+    fn dragging(&self, theme: &Theme) -> knob::Appearance {
+        shade_knob(
+            theme,
+            knob::StyleSheet::dragging(theme, &theme::Knob::Default),
+        )
+    }
+
+    fn tick_marks_appearance(&self, theme: &Theme) -> Option<knob::TickMarksAppearance> {
+        knob::StyleSheet::tick_marks_appearance(theme, &theme::Knob::Default).map(
+            |mut appearance| {
+                appearance.style = self.tick_marks(theme);
+                appearance
+            },
+        )
+    }
+
+    fn text_marks_appearance(&self, theme: &Theme) -> Option<knob::TextMarksAppearance> {
+        knob::StyleSheet::text_marks_appearance(theme, &theme::Knob::Default).map(
+            |mut appearance| {
+                appearance.style = shade_text_marks(theme, appearance.style);
+                appearance
+            },
+        )
+    }
+
+    fn mod_range_arc_appearance(&self, theme: &Theme) -> Option<knob::ModRangeArcAppearance> {
+        Some(knob::ModRangeArcAppearance {
+            width: 3.0,
+            offset: 1.5,
+            empty_color: Some(shade(theme, Color::from_rgba(0.0, 0.0, 0.0, 0.1))),
+            // Positive modulation in green, negative in orange.
+            filled_color: Color::from_rgb(0.36, 0.72, 0.36),
+            filled_inverse_color: Color::from_rgb(0.92, 0.55, 0.2),
+            cap: LineCap::Butt,
+        })
+    }
+}
+
+fn shade_notch(theme: &Theme, notch: knob::NotchShape) -> knob::NotchShape {
+    match notch {
+        knob::NotchShape::Circle(mut circle) => {
+            circle.color = shade(theme, circle.color);
+            circle.border_color = shade(theme, circle.border_color);
+            knob::NotchShape::Circle(circle)
+        }
+        knob::NotchShape::Line(mut line) => {
+            line.color = shade(theme, line.color);
+            knob::NotchShape::Line(line)
+        }
+        notch => notch,
+    }
+}
+
+fn shade_knob(theme: &Theme, appearance: knob::Appearance) -> knob::Appearance {
+    match appearance {
+        knob::Appearance::Circle(mut circle) => {
+            circle.color = shade(theme, circle.color);
+            circle.border_color = shade(theme, circle.border_color);
+            circle.notch = shade_notch(theme, circle.notch);
+            knob::Appearance::Circle(circle)
+        }
+        appearance => appearance,
+    }
+}
+
+impl mod_range_input::StyleSheet for Themed {
+    type Style = Theme;
+
+    fn active(&self, theme: &Theme) -> mod_range_input::Appearance {
+        let appearance = mod_range_input::StyleSheet::active(theme, &theme::ModRangeInput::Default);
+        shade_mod_range_input(theme, appearance)
+    }
+
+    fn hovered(&self, theme: &Theme) -> mod_range_input::Appearance {
+        let appearance =
+            mod_range_input::StyleSheet::hovered(theme, &theme::ModRangeInput::Default);
+        shade_mod_range_input(theme, appearance)
+    }
+
+    fn dragging(&self, theme: &Theme) -> mod_range_input::Appearance {
+        let appearance =
+            mod_range_input::StyleSheet::dragging(theme, &theme::ModRangeInput::Default);
+        shade_mod_range_input(theme, appearance)
+    }
+}
+
+fn shade_mod_range_input(
+    theme: &Theme,
+    appearance: mod_range_input::Appearance,
+) -> mod_range_input::Appearance {
+    match appearance {
+        mod_range_input::Appearance::Circle(mut circle) => {
+            circle.color = shade(theme, circle.color);
+            circle.border_color = shade(theme, circle.border_color);
+            mod_range_input::Appearance::Circle(circle)
+        }
+        mod_range_input::Appearance::Square(mut square) => {
+            square.color = shade(theme, square.color);
+            square.border_color = shade(theme, square.border_color);
+            mod_range_input::Appearance::Square(square)
+        }
+        appearance => appearance,
+    }
+}
+
+impl xy_pad::StyleSheet for Themed {
+    type Style = Theme;
+
+    fn active(&self, theme: &Theme) -> xy_pad::Appearance {
+        shade_xy_pad(
+            theme,
+            xy_pad::StyleSheet::active(theme, &theme::XYPad::Default),
+        )
+    }
+
+    fn hovered(&self, theme: &Theme) -> xy_pad::Appearance {
+        shade_xy_pad(
+            theme,
+            xy_pad::StyleSheet::hovered(theme, &theme::XYPad::Default),
+        )
+    }
+
+    fn dragging(&self, theme: &Theme) -> xy_pad::Appearance {
+        shade_xy_pad(
+            theme,
+            xy_pad::StyleSheet::dragging(theme, &theme::XYPad::Default),
+        )
+    }
+}
+
+fn shade_xy_pad(theme: &Theme, mut appearance: xy_pad::Appearance) -> xy_pad::Appearance {
+    appearance.h_rail_color = shade(theme, appearance.h_rail_color);
+    appearance.v_rail_color = shade(theme, appearance.v_rail_color);
+    appearance.back_color = shade(theme, appearance.back_color);
+    appearance.border_color = shade(theme, appearance.border_color);
+    appearance.center_line_color = shade(theme, appearance.center_line_color);
+    appearance.handle = match appearance.handle {
+        xy_pad::HandleShape::Circle(mut circle) => {
+            circle.color = shade(theme, circle.color);
+            circle.border_color = shade(theme, circle.border_color);
+            xy_pad::HandleShape::Circle(circle)
+        }
+        xy_pad::HandleShape::Square(mut square) => {
+            square.color = shade(theme, square.color);
+            square.border_color = shade(theme, square.border_color);
+            xy_pad::HandleShape::Square(square)
+        }
+    };
+
+    appearance
+}
+
+impl ramp::StyleSheet for Themed {
+    type Style = Theme;
+
+    fn active(&self, theme: &Theme) -> ramp::Appearance {
+        shade_ramp(
+            theme,
+            ramp::StyleSheet::active(theme, &theme::Ramp::Default),
+        )
+    }
+
+    fn hovered(&self, theme: &Theme) -> ramp::Appearance {
+        shade_ramp(
+            theme,
+            ramp::StyleSheet::hovered(theme, &theme::Ramp::Default),
+        )
+    }
+
+    fn dragging(&self, theme: &Theme) -> ramp::Appearance {
+        shade_ramp(
+            theme,
+            ramp::StyleSheet::dragging(theme, &theme::Ramp::Default),
+        )
+    }
+}
+
+fn shade_ramp(theme: &Theme, mut appearance: ramp::Appearance) -> ramp::Appearance {
+    appearance.back_color = shade(theme, appearance.back_color);
+    appearance.back_border_color = shade(theme, appearance.back_border_color);
+    appearance.line_center_color = shade(theme, appearance.line_center_color);
+    appearance.line_up_color = shade(theme, appearance.line_up_color);
+    appearance.line_down_color = shade(theme, appearance.line_down_color);
+    appearance
+}
+
+/// A frame around the parameter widget that has keyboard focus.
+pub struct Focused;
+
+impl container::StyleSheet for Focused {
+    type Style = Theme;
+
+    fn appearance(&self, theme: &Theme) -> container::Appearance {
+        container::Appearance {
+            border_radius: 4.0,
+            border_width: 2.0,
+            border_color: theme.palette().primary,
+            ..Default::default()
+        }
+    }
+}
+
+/// A heavier frame around the parameter widget last moved, in solo mode.
+pub struct Solo;
+
+impl container::StyleSheet for Solo {
+    type Style = Theme;
+
+    fn appearance(&self, theme: &Theme) -> container::Appearance {
+        container::Appearance {
+            border_radius: 4.0,
+            border_width: 3.0,
+            border_color: theme.palette().primary,
+            ..Default::default()
+        }
+    }
+}
+
+/// A faint frame around a parameter widget moved since the preset was saved
+/// or loaded.
+pub struct Drifted;
+
+impl container::StyleSheet for Drifted {
+    type Style = Theme;
+
+    fn appearance(&self, theme: &Theme) -> container::Appearance {
+        container::Appearance {
+            border_radius: 4.0,
+            border_width: 1.0,
+            border_color: Color {
+                a: 0.5,
+                ..theme.palette().primary
+            },
+            ..Default::default()
+        }
+    }
+}
+
+/// A brief flash behind the horizontal slider as it crosses a step.
+pub struct Detent;
+
+impl container::StyleSheet for Detent {
+    type Style = Theme;
+
+    fn appearance(&self, theme: &Theme) -> container::Appearance {
+        let mut highlight = theme.palette().primary;
+        highlight.a = 0.2;
+
+        container::Appearance {
+            background: Some(highlight.into()),
+            border_radius: 4.0,
+            ..Default::default()
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn widget_ticks(themed: &Themed, theme: &Theme) -> [tick_marks::Appearance; 3] {
+        [
+            h_slider::StyleSheet::tick_marks_appearance(themed, theme)
+                .unwrap()
+                .style,
+            v_slider::StyleSheet::tick_marks_appearance(themed, theme)
+                .unwrap()
+                .style,
+            knob::StyleSheet::tick_marks_appearance(themed, theme)
+                .unwrap()
+                .style,
+        ]
+    }
+
+    #[test]
+    fn every_widget_draws_ticks_alike() {
+        for theme in [Theme::Light, Theme::Dark] {
+            let expected = TickStyle::for_theme(&theme).appearance();
+
+            assert_eq!(widget_ticks(&Themed::default(), &theme), [expected; 3]);
+        }
+
+        let mut ticks = TickStyle::for_theme(&Theme::Light);
+        ticks.tier_1.color = Color::from_rgb(1.0, 0.0, 0.0);
+        ticks.tier_3.length = 8.0;
+        let themed = Themed { ticks: Some(ticks) };
+
+        // Set tiers win over the theme's, whichever theme it is.
+        assert_eq!(widget_ticks(&themed, &Theme::Dark), [ticks.appearance(); 3]);
+    }
+
+    #[test]
+    fn each_tier_has_its_own_color() {
+        let ticks = TickStyle::for_theme(&Theme::Dark);
+
+        assert_ne!(ticks.tier_1.color, ticks.tier_2.color);
+        assert_ne!(ticks.tier_1.color, ticks.tier_3.color);
+        assert_ne!(ticks.tier_2.color, ticks.tier_3.color);
+    }
+}
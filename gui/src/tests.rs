@@ -0,0 +1,1451 @@
+//! Runs sequences of messages through `App::update`, as the widgets and
+//! controllers would send them, and checks the parameters they leave.
+
+use super::*;
+
+use std::cell::RefCell;
+use std::rc::Rc;
+
+fn app() -> App {
+    App::with_config(Config::default(), Prefs::default())
+}
+
+fn send(app: &mut App, messages: impl IntoIterator<Item = Message>) {
+    for message in messages {
+        let _ = app.update(message);
+    }
+}
+
+// Every change an app has reported to an embedding program, in order.
+type Changes = Rc<RefCell<Vec<(ParamId, f32)>>>;
+
+// An app that records every change it reports to an embedding program.
+fn recording_app() -> (App, Changes) {
+    let changes = Rc::new(RefCell::new(Vec::new()));
+    let mut app = app();
+    let heard = Rc::clone(&changes);
+    app.on_change = Some(Box::new(move |id, value| {
+        heard.borrow_mut().push((id, value))
+    }));
+
+    (app, changes)
+}
+
+// A directory of its own under the system's temp directory for a test's
+// files, removed again when it's dropped, even if the test fails.
+pub(crate) fn temp_dir(name: &str) -> TempDir {
+    let path = std::env::temp_dir().join(format!("paris_green_{name}_{}", std::process::id()));
+    fs::create_dir_all(&path).unwrap();
+
+    TempDir(path)
+}
+
+pub(crate) struct TempDir(PathBuf);
+
+impl std::ops::Deref for TempDir {
+    type Target = Path;
+
+    fn deref(&self) -> &Path {
+        &self.0
+    }
+}
+
+impl AsRef<Path> for TempDir {
+    fn as_ref(&self) -> &Path {
+        &self.0
+    }
+}
+
+impl Drop for TempDir {
+    fn drop(&mut self) {
+        let _ = fs::remove_dir_all(&self.0);
+    }
+}
+
+// A preset with every field set to something other than its default, for
+// building variations on with struct update syntax.
+pub(crate) fn test_preset() -> Preset {
+    Preset {
+        version: PRESET_VERSION,
+        // Values with no short decimal form, and one next to zero.
+        h_slider: 0.1,
+        v_slider: 1.0 / 3.0,
+        knob: f32::MIN_POSITIVE,
+        xy_pad_x: 0.7,
+        xy_pad_y: 0.5,
+        slider_value: 0.025,
+        button_id: 128,
+        midi_bindings: Some(HashMap::from([
+            (1, ParamId::KnobFreq),
+            (7, ParamId::VSliderDB),
+        ])),
+        ramp: 0.9,
+        knob_mod: 0.25,
+        amplitude_taper: true,
+        midi_modes: HashMap::from([(1, CcMode::Relative)]),
+        phase_invert: true,
+        xy_pads: vec![(0.7, 0.5), (0.1, 0.9)],
+        xy_pad_tab: 0,
+        lfo_rate: 0.3,
+        lfo_depth: 0.45,
+        lfo_shape: Shape::Square,
+        band_low: Some(0.2),
+        band_high: Some(0.6),
+        smoothing: Some(0.15),
+    }
+}
+
+fn normal(value: f32) -> Normal {
+    Normal::from_clipped(value)
+}
+
+fn assert_close(actual: f32, expected: f32) {
+    assert!(
+        (actual - expected).abs() < 1e-3,
+        "{actual} is not close to {expected}"
+    );
+}
+
+#[test]
+fn h_slider_snaps_to_the_nearest_step() {
+    let mut app = app();
+
+    // The default range is 0 to 10, so 0.33 falls between 3 and 4.
+    send(&mut app, [Message::HSliderInt(normal(0.33))]);
+
+    let param = app.param(ParamId::HSliderInt);
+    assert_eq!(param.value(), 3.0);
+    assert_close(param.normal_param.value.as_f32(), 0.3);
+    assert_eq!(app.log.latest(), Some(param.label().as_str()));
+}
+
+#[test]
+fn int_stepper_stops_at_the_bounds() {
+    let mut app = app();
+
+    send(
+        &mut app,
+        [Message::HSliderInt(normal(0.9)), Message::IntStep(1)],
+    );
+    assert_eq!(app.param(ParamId::HSliderInt).value(), 10.0);
+    assert!(app.int_stepped(1).is_none());
+
+    send(&mut app, [Message::IntStep(1), Message::IntStep(-3)]);
+    assert_eq!(app.param(ParamId::HSliderInt).value(), 7.0);
+
+    send(&mut app, [Message::HSliderInt(Normal::MIN)]);
+    assert!(app.int_stepped(-1).is_none());
+    assert!(app.int_stepped(1).is_some());
+}
+
+#[test]
+fn octave_knob_follows_the_frequency_knob() {
+    let mut app = app();
+
+    send(&mut app, [Message::KnobFreq(normal(0.5))]);
+    let knob = app.param(ParamId::KnobFreq).value();
+    assert_close(app.param(ParamId::KnobOctave).value(), knob * OCTAVE_RATIO);
+
+    // Moving the octave knob moves the frequency knob instead.
+    let octave = app.param(ParamId::KnobOctave).range.map_to_normal(2000.0);
+    send(&mut app, [Message::KnobOctave(octave)]);
+    assert_close(app.param(ParamId::KnobFreq).value(), 1000.0);
+}
+
+#[test]
+fn gain_stops_at_its_limit() {
+    let mut app = app();
+
+    send(&mut app, [Message::VSliderDB(Normal::MAX)]);
+
+    assert_close(app.param(ParamId::VSliderDB).value(), GAIN_LIMIT_DB);
+}
+
+#[test]
+fn xy_pad_is_held_inside_the_circle() {
+    let mut app = app();
+
+    send(
+        &mut app,
+        [
+            Message::ToggleXYCircle,
+            Message::XYPadFloat(0, Normal::MAX, Normal::MAX),
+        ],
+    );
+
+    let x = app.param(ParamId::XYPadX).value();
+    let y = app.param(ParamId::XYPadY).value();
+    assert_close(x.hypot(y), 1.0);
+    assert_close(x, y);
+}
+
+#[test]
+fn bipolar_drags_stick_at_center() {
+    let mut app = app();
+
+    send(
+        &mut app,
+        [
+            Message::KnobModChanged(normal(0.8)),
+            Message::KnobModChanged(normal(0.51)),
+        ],
+    );
+    assert_eq!(app.param(ParamId::KnobMod).value(), 0.0);
+    assert_eq!(app.log.latest(), Some("mod depth centered"));
+
+    // Moving on past the zone lets go.
+    send(&mut app, [Message::KnobModChanged(normal(0.4))]);
+    assert_close(app.param(ParamId::KnobMod).value(), -0.2);
+
+    send(
+        &mut app,
+        [
+            Message::XYPadFloat(0, normal(0.2), normal(0.9)),
+            Message::XYPadFloat(0, normal(0.49), normal(0.505)),
+        ],
+    );
+    assert_eq!(app.param(ParamId::XYPadX).value(), 0.0);
+    assert_eq!(app.param(ParamId::XYPadY).value(), 0.0);
+    assert_eq!(app.log.latest(), Some("x and y centered"));
+}
+
+#[test]
+fn xy_pad_snaps_to_its_grid() {
+    let mut app = app();
+
+    send(
+        &mut app,
+        [
+            Message::ToggleXYSnap,
+            Message::XYPadFloat(0, normal(0.8), normal(0.3)),
+        ],
+    );
+    assert_close(app.param(ParamId::XYPadX).value(), 0.5);
+    assert_close(app.param(ParamId::XYPadY).value(), -0.5);
+    assert_eq!(app.param(ParamId::XYPadX).formatted(), "0.50");
+
+    send(
+        &mut app,
+        [
+            Message::XYGridSize(4),
+            Message::XYPadFloat(0, normal(0.8), normal(0.4)),
+        ],
+    );
+    assert_close(app.param(ParamId::XYPadX).value(), 0.5);
+    assert_close(app.param(ParamId::XYPadY).value(), 0.0);
+
+    send(
+        &mut app,
+        [
+            Message::ToggleXYSnap,
+            Message::XYPadFloat(0, normal(0.8), normal(0.3)),
+        ],
+    );
+    assert_close(app.param(ParamId::XYPadX).value(), 0.6);
+}
+
+#[test]
+fn xy_pad_x_pans_the_output() {
+    let mut app = app();
+    assert_eq!(app.audio_shared.pan(), 0.0);
+
+    send(
+        &mut app,
+        [Message::XYPadFloat(0, normal(0.185), normal(0.8))],
+    );
+    assert_close(app.audio_shared.pan(), -0.63);
+    assert!(app.log.latest().unwrap().ends_with("Pan L63"));
+
+    send(&mut app, [Message::XYPadFloat(0, Normal::MAX, normal(0.8))]);
+    assert_eq!(app.audio_shared.pan(), 1.0);
+    assert!(app.log.latest().unwrap().ends_with("Pan R100"));
+}
+
+#[test]
+fn solo_highlights_the_last_moved_until_idle() {
+    let mut app = app();
+    send(&mut app, [Message::KnobFreq(normal(0.7))]);
+    assert_eq!(app.soloed, None);
+
+    send(
+        &mut app,
+        [Message::ToggleSolo, Message::VSliderDB(normal(0.4))],
+    );
+    assert!(app.is_soloed(Focus::VSlider));
+    assert!(!app.is_soloed(Focus::Knob));
+
+    // Typing alone doesn't move it, submitting does.
+    send(
+        &mut app,
+        [Message::ValueEdited(ParamId::HSliderInt, "4".into())],
+    );
+    assert!(app.is_soloed(Focus::VSlider));
+    send(&mut app, [Message::ValueSubmitted(ParamId::HSliderInt)]);
+    assert!(app.is_soloed(Focus::HSlider));
+
+    send(&mut app, [Message::Tick(Instant::now())]);
+    assert!(app.is_soloed(Focus::HSlider));
+    let idle = Duration::from_millis(app.prefs.solo_idle);
+    send(&mut app, [Message::Tick(Instant::now() + idle)]);
+    assert_eq!(app.soloed, None);
+}
+
+#[test]
+fn the_band_is_saved_with_presets() {
+    let mut app = app();
+    send(
+        &mut app,
+        [
+            Message::RangeHigh(Normal::MAX),
+            Message::RangeLow(normal(0.9)),
+        ],
+    );
+    let preset = app.preset();
+    let (low, high) = (app.normal(ParamId::BandLow), app.normal(ParamId::BandHigh));
+
+    send(&mut app, [Message::MenuReset(Focus::Band)]);
+    app.apply_preset(&preset);
+    assert_eq!(app.normal(ParamId::BandLow), low);
+    assert_eq!(app.normal(ParamId::BandHigh), high);
+
+    // Morphing there moves the whole band up past where its top was.
+    app.prefs.morph_time = 100;
+    send(&mut app, [Message::MenuReset(Focus::Band)]);
+    app.apply_preset(&preset);
+    send(
+        &mut app,
+        [Message::Tick(Instant::now() + Duration::from_secs(1))],
+    );
+    assert_eq!(app.normal(ParamId::BandLow), low);
+    assert_eq!(app.normal(ParamId::BandHigh), high);
+
+    app.saved = Some(preset);
+    send(&mut app, [Message::RangeLow(normal(0.8))]);
+    assert_eq!(app.drifted(), vec![ParamId::BandLow]);
+}
+
+#[test]
+fn smoothing_is_saved_with_presets_and_compare_slots() {
+    let mut app = app();
+    send(&mut app, [Message::Smoothing(normal(0.7))]);
+    let preset = app.preset();
+
+    send(&mut app, [Message::Reset(ParamId::Smoothing)]);
+    app.apply_preset(&preset);
+    assert_eq!(app.normal(ParamId::Smoothing), 0.7);
+
+    // B starts as a copy of A, so moving it there and swapping back
+    // brings A's smoothing back with it.
+    send(
+        &mut app,
+        [
+            Message::SwapCompare,
+            Message::Smoothing(normal(0.2)),
+            Message::SwapCompare,
+        ],
+    );
+    assert_eq!(app.normal(ParamId::Smoothing), 0.7);
+}
+
+#[test]
+fn band_ends_hold_each_other_back() {
+    let mut app = app();
+    let high = app.param(ParamId::BandHigh).normal_param.value;
+
+    send(&mut app, [Message::RangeLow(Normal::MAX)]);
+    assert_eq!(app.param(ParamId::BandLow).normal_param.value, high);
+    assert_close(app.param(ParamId::BandLow).value(), 2000.0);
+
+    send(&mut app, [Message::RangeHigh(Normal::MIN)]);
+    assert_close(app.param(ParamId::BandHigh).value(), 2000.0);
+
+    send(&mut app, [Message::RangeHigh(Normal::MAX)]);
+    assert_eq!(
+        app.log.latest(),
+        Some("band low: 2.00 kHz, band high: 20.5 kHz, width 18.5 kHz")
+    );
+
+    // Resetting both from above the defaults lands on both defaults.
+    send(
+        &mut app,
+        [
+            Message::RangeLow(Normal::MAX),
+            Message::MenuReset(Focus::Band),
+        ],
+    );
+    assert_close(app.param(ParamId::BandLow).value(), 200.0);
+    assert_close(app.param(ParamId::BandHigh).value(), 2000.0);
+}
+
+#[test]
+fn drag_undoes_in_one_step() {
+    let mut app = app();
+    let before = app.param(ParamId::RampCurve).normal_param.value;
+
+    send(
+        &mut app,
+        [
+            Message::Grabbed(ParamId::RampCurve),
+            Message::RampChanged(normal(0.6)),
+            Message::RampChanged(normal(0.7)),
+            Message::Released(ParamId::RampCurve),
+            Message::Undo,
+        ],
+    );
+    assert_eq!(app.param(ParamId::RampCurve).normal_param.value, before);
+
+    send(&mut app, [Message::Redo]);
+    assert_close(
+        app.param(ParamId::RampCurve).normal_param.value.as_f32(),
+        0.7,
+    );
+}
+
+#[test]
+fn typed_values_are_parsed_through_the_range() {
+    let mut app = app();
+
+    send(
+        &mut app,
+        [
+            Message::ValueEdited(ParamId::KnobFreq, "440".into()),
+            Message::ValueSubmitted(ParamId::KnobFreq),
+            Message::ValueEdited(ParamId::Smoothing, "fast".into()),
+            Message::ValueSubmitted(ParamId::Smoothing),
+        ],
+    );
+
+    assert_close(app.param(ParamId::KnobFreq).value(), 440.0);
+    assert_eq!(app.log.latest(), Some("Invalid value: \"fast\""));
+}
+
+#[test]
+fn midi_applies_the_last_value_on_the_next_tick() {
+    let mut app = app();
+    let before = app.param(ParamId::KnobFreq).normal_param.value;
+
+    // CC 1 is bound to the frequency knob by default.
+    send(
+        &mut app,
+        [
+            Message::MidiCc(1, 0.into()),
+            Message::MidiCc(1, 64.into()),
+            Message::MidiCc(1, 127.into()),
+        ],
+    );
+    assert_eq!(app.param(ParamId::KnobFreq).normal_param.value, before);
+
+    send(&mut app, [Message::Tick(Instant::now())]);
+    assert_eq!(app.param(ParamId::KnobFreq).normal_param.value, Normal::MAX);
+}
+
+#[test]
+fn note_snap_lands_on_a_note() {
+    let mut app = app();
+
+    send(
+        &mut app,
+        [
+            Message::ToggleNoteSnap,
+            Message::ValueEdited(ParamId::KnobFreq, "445".into()),
+            Message::ValueSubmitted(ParamId::KnobFreq),
+        ],
+    );
+
+    assert_close(app.param(ParamId::KnobFreq).value(), 440.0);
+}
+
+#[test]
+fn builder_sets_a_single_range() {
+    let builder = App::builder().param(ParamId::KnobFreq, ParamConfig::new(20.0, 2000.0, 220.0));
+    let config = builder.config.unwrap();
+    assert_eq!(config.v_slider, Config::default().v_slider);
+
+    let app = App::with_config(config, Prefs::default());
+    assert_close(app.param(ParamId::KnobFreq).value(), 220.0);
+    let top = app
+        .param(ParamId::KnobFreq)
+        .range
+        .unmap_to_value(Normal::MAX);
+    assert_close(top, 2000.0);
+}
+
+#[test]
+fn embedder_hears_every_change() {
+    let (mut app, changes) = recording_app();
+
+    send(
+        &mut app,
+        [
+            Message::HSliderInt(normal(0.3)),
+            Message::Undo,
+            Message::KnobFreq(Normal::MAX),
+        ],
+    );
+
+    let changes = changes.borrow();
+    assert_eq!(changes[0], (ParamId::HSliderInt, 3.0));
+    assert_eq!(changes[1], (ParamId::HSliderInt, 5.0));
+    assert!(changes.contains(&(ParamId::KnobFreq, 20480.0)));
+    assert!(changes.iter().any(|&(id, _)| id == ParamId::KnobOctave));
+}
+
+#[test]
+fn phase_invert_negates_the_frequency_sent() {
+    let (mut app, changes) = recording_app();
+
+    send(
+        &mut app,
+        [Message::KnobFreq(Normal::MAX), Message::TogglePhaseInvert],
+    );
+    assert!(app.audio_shared.invert());
+    assert!(app.log.latest().unwrap().ends_with(", inverted"));
+    assert_eq!(
+        changes.borrow().last(),
+        Some(&(ParamId::KnobFreq, -20480.0))
+    );
+    // The knob itself still reads positive.
+    assert_eq!(app.param(ParamId::KnobFreq).value(), 20480.0);
+
+    // Randomizing leaves it alone, and presets carry it.
+    send(&mut app, [Message::Randomize]);
+    assert!(app.phase_invert);
+    let preset = app.preset();
+    send(&mut app, [Message::TogglePhaseInvert]);
+    assert!(!app.audio_shared.invert());
+    app.apply_preset(&preset);
+    assert!(app.phase_invert);
+    assert!(app.audio_shared.invert());
+}
+
+#[test]
+fn exporting_nothing_says_so() {
+    let mut app = app();
+
+    send(&mut app, [Message::ExportAutomation]);
+
+    assert_eq!(app.log.latest(), Some("Nothing recorded to export"));
+}
+
+#[test]
+fn note_names_set_the_frequency() {
+    let mut app = app();
+
+    send(
+        &mut app,
+        [Message::NoteEdited("A5".into()), Message::NoteSubmitted],
+    );
+    assert_close(app.param(ParamId::KnobFreq).value(), 880.0);
+    assert_eq!(app.log.latest(), Some("A5: 880.0 Hz"));
+
+    // Past the top of the range, the knob stops there.
+    send(
+        &mut app,
+        [Message::NoteEdited("C11".into()), Message::NoteSubmitted],
+    );
+    assert_eq!(app.param(ParamId::KnobFreq).normal_param.value, Normal::MAX);
+    assert_eq!(
+        app.log.latest(),
+        Some("C11 is out of range, set to 20.5 kHz")
+    );
+
+    send(
+        &mut app,
+        [Message::NoteEdited("H2".into()), Message::NoteSubmitted],
+    );
+    assert_eq!(app.log.latest(), Some("Invalid note: \"H2\""));
+}
+
+#[test]
+fn midi_notes_play_the_frequency_knob() {
+    let mut app = app();
+
+    send(&mut app, [Message::MidiNote(69)]);
+    assert_close(app.param(ParamId::KnobFreq).value(), 440.0);
+    assert_eq!(app.log.latest(), Some("A4: 440.0 Hz"));
+
+    // Tuned to the reference pitch.
+    send(
+        &mut app,
+        [
+            Message::ReferenceEdited("432".into()),
+            Message::ReferenceSubmitted,
+            Message::MidiNote(81),
+        ],
+    );
+    assert_close(app.param(ParamId::KnobFreq).value(), 864.0);
+
+    // A locked knob ignores the keyboard.
+    send(
+        &mut app,
+        [Message::ToggleLock(Focus::Knob), Message::MidiNote(57)],
+    );
+    assert_close(app.param(ParamId::KnobFreq).value(), 864.0);
+}
+
+#[test]
+fn the_lfo_moves_what_is_sent_but_not_the_widget() {
+    let (mut app, changes) = recording_app();
+    let start = Instant::now();
+
+    send(
+        &mut app,
+        [
+            Message::ToggleLfo(Focus::XYPad),
+            Message::Tick(start),
+            // A quarter of a cycle at 1 Hz, to the top of the wave.
+            Message::Tick(start + Duration::from_millis(250)),
+        ],
+    );
+    assert_eq!(app.param(ParamId::XYPadX).normal_param.value, normal(0.5));
+    assert_close(
+        app.live_param(ParamId::XYPadX).normal_param.value.as_f32(),
+        0.6,
+    );
+    assert_close(
+        app.live_param(ParamId::XYPadY).normal_param.value.as_f32(),
+        0.6,
+    );
+    assert_eq!(
+        changes.borrow().last(),
+        Some(&(ParamId::XYPadY, app.live_param(ParamId::XYPadY).value()))
+    );
+
+    // Swung past the end, it holds there.
+    send(
+        &mut app,
+        [
+            Message::LfoDepthEdited("1".into()),
+            Message::LfoDepthSubmitted,
+        ],
+    );
+    assert_eq!(
+        app.live_param(ParamId::XYPadX).normal_param.value,
+        Normal::MAX
+    );
+
+    // Stopped, what's sent is where the widget is again.
+    send(&mut app, [Message::ToggleLfo(Focus::XYPad)]);
+    assert_eq!(app.log.latest(), Some("Stopped modulating x"));
+    assert_eq!(
+        changes.borrow().last(),
+        Some(&(ParamId::XYPadY, app.param(ParamId::XYPadY).value()))
+    );
+    assert!(app.modulated().is_empty());
+}
+
+#[test]
+fn lfo_settings_are_saved_with_presets() {
+    let mut app = app();
+    send(
+        &mut app,
+        [
+            Message::ToggleLfo(Focus::Ramp),
+            Message::LfoRate(Normal::MAX),
+            Message::LfoShape(Shape::Saw),
+            Message::LfoDepthEdited("0.3".into()),
+            Message::LfoDepthSubmitted,
+        ],
+    );
+    assert_close(app.lfo.rate(), 20.0);
+    let preset = app.preset();
+
+    send(
+        &mut app,
+        [
+            Message::LfoRateEdited("0.5".into()),
+            Message::LfoRateSubmitted,
+            Message::LfoShape(Shape::Triangle),
+        ],
+    );
+    assert_eq!(app.log.latest(), Some("LFO shape Triangle"));
+    app.apply_preset(&preset);
+
+    assert_close(app.lfo.rate(), 20.0);
+    assert_eq!(app.lfo.shape(), Shape::Saw);
+    assert_eq!(
+        app.param(ParamId::RampCurve).mod_source,
+        Some(ModSource::Lfo { depth: 0.3 })
+    );
+}
+
+#[test]
+fn an_external_source_sets_params_each_frame() {
+    let mut app = app();
+    let before = app.param(ParamId::KnobFreq).value();
+
+    // The subscription turns what's sent into messages, which wait for the
+    // next frame, the latest for each parameter winning.
+    send(
+        &mut app,
+        [
+            Message::ExternalParam(ParamId::KnobFreq, 220.0),
+            Message::ExternalParam(ParamId::KnobFreq, 330.0),
+        ],
+    );
+    assert_close(app.param(ParamId::KnobFreq).value(), before);
+    send(&mut app, [Message::Tick(Instant::now())]);
+    assert_close(app.param(ParamId::KnobFreq).value(), 330.0);
+
+    // A locked parameter ignores it, as it does OSC.
+    send(
+        &mut app,
+        [
+            Message::ToggleLock(Focus::Knob),
+            Message::ExternalParam(ParamId::KnobFreq, 110.0),
+            Message::Tick(Instant::now()),
+        ],
+    );
+    assert_close(app.param(ParamId::KnobFreq).value(), 330.0);
+
+    // Nor does anything take a value that isn't a number.
+    send(
+        &mut app,
+        [
+            Message::ToggleLock(Focus::Knob),
+            Message::ExternalParam(ParamId::KnobFreq, f32::NAN),
+            Message::Tick(Instant::now()),
+        ],
+    );
+    assert_close(app.param(ParamId::KnobFreq).value(), 330.0);
+    assert_eq!(
+        app.log.latest(),
+        Some("Ignored an external KnobFreq of NaN")
+    );
+
+    let (_, receiver) = std::sync::mpsc::channel();
+    app.external = Some(Arc::new(Mutex::new(receiver)));
+    send(&mut app, [Message::ExternalClosed]);
+    assert!(app.external.is_none());
+}
+
+#[test]
+fn disabled_params_ignore_input() {
+    let mut app = app();
+    let depth = app.param(ParamId::KnobMod).normal_param.value;
+    app.midi_bindings
+        .insert(20, Binding::absolute(ParamId::KnobMod));
+
+    send(
+        &mut app,
+        [
+            Message::ToggleModulation,
+            Message::KnobModChanged(normal(0.9)),
+            Message::MenuReset(Focus::ModRange),
+            Message::MidiCc(20, 127.into()),
+            Message::Tick(Instant::now()),
+        ],
+    );
+    assert_eq!(app.param(ParamId::KnobMod).normal_param.value, depth);
+
+    // Tab goes from the knob straight past the depth to the XY pad.
+    app.focused = Some(Focus::Knob);
+    send(&mut app, [Message::FocusNext]);
+    assert_eq!(app.focused, Some(Focus::XYPad));
+
+    send(
+        &mut app,
+        [
+            Message::ToggleModulation,
+            Message::KnobModChanged(normal(0.9)),
+        ],
+    );
+    assert_close(app.param(ParamId::KnobMod).value(), 0.8);
+}
+
+#[test]
+fn fine_mode_makes_every_drag_fine() {
+    let mut app = app();
+    assert_eq!(app.fine_drag_keys(), FINE_DRAG_KEYS);
+
+    send(&mut app, [Message::ToggleFineMode]);
+
+    // No keys at all counts as always held.
+    assert!(keyboard::Modifiers::empty().contains(app.fine_drag_keys()));
+    assert!(FINE_DRAG_KEYS.contains(app.fine_drag_keys()));
+}
+
+#[test]
+fn preset_morphs_to_exactly_its_values() {
+    let mut app = app();
+    app.prefs.morph_time = 1000;
+
+    let before = app.preset();
+    let mut preset = app.preset();
+    preset.ramp = 1.0;
+    preset.xy_pad_x = 0.8;
+    app.apply_preset(&preset);
+
+    // Partway through, the ramp is on its way up.
+    let started = Instant::now();
+    app.step_morph(started + Duration::from_millis(500));
+    let ramp = app.param(ParamId::RampCurve).normal_param.value.as_f32();
+    assert!(ramp > before.ramp && ramp < 1.0, "{ramp} is not partway");
+
+    // Well past the end, it has landed on the preset and stopped.
+    app.step_morph(started + Duration::from_secs(5));
+    assert_eq!(
+        app.param(ParamId::RampCurve).normal_param.value,
+        normal(1.0)
+    );
+    assert_eq!(app.param(ParamId::XYPadX).normal_param.value, normal(0.8));
+    assert!(app.morph.is_none());
+
+    // The whole load undoes in one go.
+    send(&mut app, [Message::Undo]);
+    assert_eq!(app.preset().ramp, before.ramp);
+    assert_eq!(app.preset().xy_pad_x, before.xy_pad_x);
+}
+
+#[test]
+fn lost_audio_device_is_reported_once() {
+    let mut app = app();
+    let knob = app.param(ParamId::KnobFreq).normal_param.value;
+
+    app.audio_shared.lose_device();
+    send(&mut app, [Message::Tick(Instant::now())]);
+    assert!(app.device_lost);
+    assert_eq!(
+        app.log.latest(),
+        Some("Audio device lost: press Reconnect to use the default device")
+    );
+
+    // Later ticks don't repeat the warning, and nothing else changes.
+    send(&mut app, [Message::KnobFreq(normal(0.2))]);
+    send(&mut app, [Message::Tick(Instant::now())]);
+    assert_ne!(
+        app.log.latest(),
+        Some("Audio device lost: press Reconnect to use the default device")
+    );
+    assert_ne!(app.param(ParamId::KnobFreq).normal_param.value, knob);
+}
+
+#[test]
+fn locked_params_only_take_typed_values() {
+    let mut app = app();
+    let gain = app.param(ParamId::VSliderDB).normal_param.value;
+    app.midi_bindings
+        .insert(7, Binding::absolute(ParamId::VSliderDB));
+
+    send(
+        &mut app,
+        [
+            Message::ToggleLock(Focus::VSlider),
+            Message::VSliderDB(normal(0.9)),
+            Message::Reset(ParamId::VSliderDB),
+            Message::MidiCc(7, 127.into()),
+            Message::Tick(Instant::now()),
+            Message::Randomize,
+        ],
+    );
+    assert_eq!(app.param(ParamId::VSliderDB).normal_param.value, gain);
+
+    send(
+        &mut app,
+        [
+            Message::ValueEdited(ParamId::VSliderDB, "-6".into()),
+            Message::ValueSubmitted(ParamId::VSliderDB),
+        ],
+    );
+    assert_close(app.param(ParamId::VSliderDB).value(), -6.0);
+}
+
+#[test]
+fn relative_cc_steps_from_the_current_value() {
+    let mut app = app();
+    app.midi_bindings
+        .insert(20, Binding::absolute(ParamId::HSliderInt));
+    app.last_touched = Some(ParamId::HSliderInt);
+    send(&mut app, [Message::ToggleCcMode]);
+    assert_eq!(app.midi_bindings[&20].mode, CcMode::Relative);
+
+    let start = app.param(ParamId::HSliderInt).value();
+    let mut control = midi::Control::default();
+    for value in [1, 1, 1] {
+        control.push(value);
+    }
+
+    // Two messages in one frame add up, rather than the last one winning.
+    send(
+        &mut app,
+        [
+            Message::MidiCc(20, control),
+            Message::MidiCc(20, 127.into()),
+            Message::Tick(Instant::now()),
+        ],
+    );
+    assert_eq!(app.param(ParamId::HSliderInt).value(), start + 2.0);
+
+    // A jump is dropped rather than applied.
+    send(
+        &mut app,
+        [
+            Message::MidiCc(20, 90.into()),
+            Message::Tick(Instant::now()),
+        ],
+    );
+    assert_eq!(app.param(ParamId::HSliderInt).value(), start + 2.0);
+}
+
+#[test]
+fn help_toggles_and_closes() {
+    let mut app = app();
+
+    send(&mut app, [Message::ToggleHelp]);
+    assert!(app.help_open);
+    send(&mut app, [Message::CloseHelp]);
+    assert!(!app.help_open);
+
+    send(&mut app, [Message::ToggleHelp, Message::ToggleHelp]);
+    assert!(!app.help_open);
+}
+
+#[test]
+fn compact_mode_only_changes_the_view() {
+    let mut app = app();
+    let before = app.preset();
+    let Length::Fixed(full) = app.knob_length() else {
+        panic!("knobs have a fixed size");
+    };
+
+    send(&mut app, [Message::ToggleCompact]);
+
+    let Length::Fixed(compact) = app.knob_length() else {
+        panic!("knobs have a fixed size");
+    };
+    assert!(compact < full);
+    assert_eq!(app.preset(), before);
+}
+
+#[test]
+fn hover_previews_without_setting() {
+    let mut app = app();
+    let before = app.param(ParamId::HSliderInt).normal_param;
+
+    // The horizontal slider snaps, so the preview shows the step it'd land on.
+    send(
+        &mut app,
+        [Message::Hover(ParamId::HSliderInt, Some(normal(0.33)))],
+    );
+    assert_eq!(app.hovered(ParamId::HSliderInt), Some(normal(0.3)));
+    assert_eq!(app.hover_preview(ParamId::HSliderInt).unwrap(), "→ 3");
+    assert_eq!(app.hovered(ParamId::VSliderDB), None);
+    assert_eq!(app.param(ParamId::HSliderInt).normal_param, before);
+
+    send(&mut app, [Message::Hover(ParamId::HSliderInt, None)]);
+    assert_eq!(app.hover_preview(ParamId::HSliderInt), None);
+}
+
+#[test]
+fn raw_normals_follow_the_values() {
+    let mut app = app();
+
+    send(&mut app, [Message::HSliderInt(normal(0.3))]);
+    assert!(!app.log.latest().unwrap().contains('['));
+
+    send(
+        &mut app,
+        [Message::ToggleShowRaw, Message::HSliderInt(normal(0.4))],
+    );
+    assert!(app.log.latest().unwrap().ends_with(" [0.400000]"));
+
+    send(
+        &mut app,
+        [Message::XYPadFloat(0, normal(0.25), normal(0.75))],
+    );
+    assert!(app.log.latest().unwrap().ends_with(" [0.250000, 0.750000]"));
+}
+
+#[test]
+fn xy_deadzone_centers_then_stretches_the_pad() {
+    let mut app = app();
+
+    send(
+        &mut app,
+        [
+            Message::XYDeadzoneEdited("0.5".into()),
+            Message::XYDeadzoneSubmitted,
+            Message::XYPadFloat(0, normal(0.7), normal(0.125)),
+        ],
+    );
+    // x is inside the deadzone; y is half way from its edge to the bottom.
+    assert_eq!(app.normal(ParamId::XYPadX), 0.5);
+    assert_eq!(app.normal(ParamId::XYPadY), 0.25);
+    assert_eq!(app.param(ParamId::XYPadY).value(), -0.5);
+
+    send(
+        &mut app,
+        [
+            Message::XYDeadzoneEdited("1".into()),
+            Message::XYDeadzoneSubmitted,
+        ],
+    );
+    assert_eq!(app.xy_deadzone, 0.5);
+    assert!(app.log.latest().unwrap().starts_with("Invalid XY deadzone"));
+}
+
+#[test]
+fn xy_smoothing_eases_then_settles_on_the_pointer() {
+    let mut app = app();
+    let start = Instant::now();
+
+    send(
+        &mut app,
+        [
+            Message::XYSmoothingEdited("0.5".into()),
+            Message::XYSmoothingSubmitted,
+            Message::Grabbed(ParamId::XYPadX),
+            Message::XYPadFloat(0, normal(0.9), normal(0.1)),
+        ],
+    );
+    // Half way from the center, rather than all the way.
+    assert_eq!(app.normal(ParamId::XYPadX), 0.7);
+    assert_eq!(app.normal(ParamId::XYPadY), 0.3);
+
+    // With the pointer still, the pad keeps closing in until it's exactly
+    // where the pointer is.
+    for i in 1..=20 {
+        send(&mut app, [Message::Tick(start + FRAME_INTERVAL * i)]);
+    }
+    assert_eq!(app.normal(ParamId::XYPadX), 0.9);
+    assert_eq!(app.normal(ParamId::XYPadY), 0.1);
+    assert!(app.xy_target.is_none());
+
+    // Letting go lands on the pointer straight away.
+    send(
+        &mut app,
+        [
+            Message::XYPadFloat(0, normal(0.1), normal(0.9)),
+            Message::Released(ParamId::XYPadX),
+        ],
+    );
+    assert_eq!(app.normal(ParamId::XYPadX), 0.1);
+    assert_eq!(app.normal(ParamId::XYPadY), 0.9);
+}
+
+#[test]
+fn code_holds_the_current_values_as_defaults() {
+    let mut app = app();
+
+    send(
+        &mut app,
+        [
+            Message::HSliderInt(normal(0.7)),
+            Message::ToggleGainTaper,
+            Message::VSliderDB(Normal::MIN),
+        ],
+    );
+    let code = app.defaults_code();
+
+    assert!(code.contains("    h_slider: ParamConfig::new(0.0, 10.0, 7.0),\n"));
+    // Silence in the taper is the bottom of the dB range.
+    assert!(code.contains("    v_slider: ParamConfig::new(-12.0, 12.0, -12.0),\n"));
+    assert!(code.contains("    knob: ParamConfig::new(20.0, 20480.0, 1000.0),\n"));
+}
+
+#[test]
+fn knob_travel_sets_the_pixels_per_turn() {
+    let prefs = Prefs {
+        knob_travel: 520.0,
+        ..Prefs::default()
+    };
+    let mut app = App::with_config(Config::default(), prefs);
+    assert_eq!(app.knob_scalar(), 1.0 / 520.0);
+
+    // A knob has to move some distance to turn.
+    for text in ["0", "-10", "inf", "far"] {
+        send(
+            &mut app,
+            [
+                Message::KnobTravelEdited(text.into()),
+                Message::KnobTravelSubmitted,
+            ],
+        );
+        assert_eq!(app.prefs.knob_travel, 520.0);
+        assert!(app.log.latest().unwrap().starts_with("Invalid knob travel"));
+    }
+}
+
+#[test]
+fn xy_pad_tabs_keep_their_own_positions() {
+    let mut app = app();
+
+    send(
+        &mut app,
+        [
+            Message::XYPadFloat(0, normal(0.2), normal(0.3)),
+            Message::SelectXYPad(2),
+        ],
+    );
+    // A fresh pad starts at the defaults.
+    assert_eq!(app.normal(ParamId::XYPadX), 0.5);
+    assert_eq!(app.normal(ParamId::XYPadY), 0.5);
+
+    send(
+        &mut app,
+        [
+            Message::XYPadFloat(2, normal(0.8), normal(0.9)),
+            // Left over from the pad that was showing.
+            Message::XYPadFloat(0, normal(0.25), normal(0.35)),
+        ],
+    );
+    assert_eq!(app.normal(ParamId::XYPadX), 0.8);
+
+    // Every pad goes in the preset, and comes back out of it.
+    let preset = app.preset();
+    assert_eq!(preset.xy_pads[0], (0.25, 0.35));
+    assert_eq!(preset.xy_pads[2], (0.8, 0.9));
+    assert_eq!(preset.xy_pad_tab, 2);
+
+    send(&mut app, [Message::SelectXYPad(0)]);
+    assert_eq!(app.normal(ParamId::XYPadX), 0.25);
+    assert_eq!(app.normal(ParamId::XYPadY), 0.35);
+
+    app.apply_preset(&preset);
+    assert_eq!(app.xy_pad_tab, 2);
+    assert_eq!(app.normal(ParamId::XYPadX), 0.8);
+    assert_eq!(app.xy_pads()[0], (normal(0.25), normal(0.35)));
+}
+
+#[test]
+fn idle_changes_are_auto_saved_and_offered_back() {
+    let dir = temp_dir("scratch");
+    let scratch = dir.join("scratch.json");
+    let mut app = app();
+    app.scratch_path = Some(scratch.clone());
+
+    send(&mut app, [Message::HSliderInt(normal(0.7))]);
+    let edited = app.edited.unwrap();
+    send(&mut app, [Message::Tick(edited + AUTOSAVE_IDLE / 2)]);
+    assert!(!scratch.exists());
+    send(&mut app, [Message::Tick(edited + AUTOSAVE_IDLE)]);
+    assert!(app.edited.is_none());
+
+    // With no preset saved after it, it's offered on the next launch.
+    let offered = newer_scratch(&scratch, &dir.join("preset.json"));
+    let mut next = self::app();
+    next.scratch = offered;
+    send(&mut next, [Message::RestoreScratch]);
+    assert_eq!(next.param(ParamId::HSliderInt).value(), 7.0);
+    assert!(next.scratch.is_none());
+
+    // A preset saved since supersedes it.
+    app.preset().save(&dir.join("preset.json")).unwrap();
+    let superseded = newer_scratch(&scratch, &dir.join("preset.json"));
+    assert!(superseded.is_none());
+}
+
+#[test]
+fn gain_floor_is_negative_infinity() {
+    let range = Range::LogDB(db_range(&Config::default()));
+
+    // The very bottom is silence, and silence maps back to it.
+    assert_eq!(range.unmap_to_value(Normal::MIN), f32::NEG_INFINITY);
+    assert_eq!(range.map_to_normal(f32::NEG_INFINITY), Normal::MIN);
+    assert_eq!(format_value(&range, Normal::MIN, None), "-inf dB");
+    assert_eq!(range.format(f32::NEG_INFINITY, None), "-inf");
+
+    // Anywhere above it is the range's minimum or more, shown as a number.
+    for above in [f32::MIN_POSITIVE, 1e-6, 0.001] {
+        let normal = normal(above);
+        let value = range.unmap_to_value(normal);
+        assert!(value.is_finite() && value >= -12.0, "{value} at {above}");
+
+        let text = format_value(&range, normal, None);
+        assert!(!text.contains("inf"), "{text} at {above}");
+        assert_eq!(text, "-12.0 dB");
+        assert!(!range.format(value, Some(2)).contains("inf"));
+    }
+
+    // The slider shows the same at its floor.
+    let mut app = app();
+    send(&mut app, [Message::VSliderDB(Normal::MIN)]);
+    assert_eq!(app.param(ParamId::VSliderDB).value_text(), "-inf");
+}
+
+#[test]
+fn a_gain_range_from_0_db_starts_audible() {
+    let config = Config {
+        v_slider: ParamConfig::new(0.0, 12.0, 0.0),
+        ..Config::default()
+    };
+    let app = App::with_config(config, Prefs::default());
+
+    // 0 dB is the default and the bottom, and it's unity gain, not silence.
+    let param = app.param(ParamId::VSliderDB);
+    assert_eq!(param.normal_param.value, Normal::MIN);
+    assert_eq!(param.value(), 0.0);
+    assert!(param.label().ends_with("0.0 dB"), "{}", param.label());
+}
+
+#[test]
+fn idle_status_clears_when_on() {
+    let prefs = Prefs {
+        status_clears: true,
+        status_timeout: 1000,
+        ..Prefs::default()
+    };
+    let mut app = App::with_config(Config::default(), prefs);
+
+    send(&mut app, [Message::HSliderInt(normal(0.7))]);
+    let since = app.status_since.unwrap();
+    send(
+        &mut app,
+        [Message::Tick(since + Duration::from_millis(500))],
+    );
+    assert_eq!(app.status_line(), "HSliderInt: 7");
+    send(
+        &mut app,
+        [Message::Tick(since + Duration::from_millis(1000))],
+    );
+    assert_eq!(app.status_line(), IDLE_STATUS);
+    // Only what's shown clears, not the log.
+    assert_eq!(app.log.latest(), Some("HSliderInt: 7"));
+
+    send(&mut app, [Message::HSliderInt(normal(0.3))]);
+    assert_eq!(app.status_line(), "HSliderInt: 3");
+
+    // Off, the status stays however long it's left.
+    app.prefs.status_clears = false;
+    let since = app.status_since.unwrap();
+    send(&mut app, [Message::Tick(since + Duration::from_secs(60))]);
+    assert_eq!(app.status_line(), "HSliderInt: 3");
+}
+
+#[test]
+fn dropped_presets_load_and_bad_ones_are_reported() {
+    let dir = temp_dir("drop");
+    let mut app = app();
+
+    let mut preset = app.preset();
+    preset.h_slider = 0.7;
+    preset.save(&dir.join("good.bin")).unwrap();
+    // A rate of 0 can't be clamped to one that runs.
+    preset.lfo_rate = 0.0;
+    fs::write(
+        dir.join("bad.json"),
+        serde_json::to_string(&preset).unwrap(),
+    )
+    .unwrap();
+    preset.version = PRESET_VERSION + 1;
+    preset.lfo_rate = lfo::DEFAULT_RATE;
+    preset.h_slider = 0.2;
+    preset.save(&dir.join("newer.json")).unwrap();
+
+    send(
+        &mut app,
+        [
+            Message::FileHovered(dir.join("good.bin")),
+            Message::FileDropped(dir.join("good.bin")),
+        ],
+    );
+    assert!(app.file_hovered.is_none());
+    assert_eq!(app.param(ParamId::HSliderInt).value(), 7.0);
+
+    send(&mut app, [Message::FileDropped(dir.join("bad.json"))]);
+    assert_eq!(
+        app.log.latest(),
+        Some("Couldn't load preset: a value can't be brought into range")
+    );
+    send(&mut app, [Message::FileDropped(dir.join("newer.json"))]);
+    let newer = format!("unsupported preset version {}", PRESET_VERSION + 1);
+    assert!(app.log.latest().unwrap().ends_with(&newer));
+    send(&mut app, [Message::FileDropped(dir.join("notes.txt"))]);
+    assert!(app
+        .log
+        .latest()
+        .unwrap()
+        .ends_with("presets end in .json or .bin"));
+
+    // None of them touched the parameters.
+    assert_eq!(app.param(ParamId::HSliderInt).value(), 7.0);
+}
+
+#[test]
+fn freq_span_keeps_the_frequency_inside_it() {
+    let mut app = app();
+    let knob = app.param(ParamId::KnobFreq).range.map_to_normal(440.0);
+    send(&mut app, [Message::KnobFreq(knob)]);
+
+    // 440 Hz is inside the bass, so it stays.
+    send(&mut app, [Message::FreqSpan(FreqSpan(20.0, 2000.0))]);
+    assert_close(app.param(ParamId::KnobFreq).value(), 440.0);
+    assert_eq!(
+        app.log.latest(),
+        Some("Frequency span 20.00 Hz to 2.00 kHz")
+    );
+    // The octave knob shares the range, and still follows an octave up.
+    assert_close(
+        app.param(ParamId::KnobOctave)
+            .range
+            .unmap_to_value(Normal::MAX),
+        2000.0,
+    );
+    assert_close(app.param(ParamId::KnobOctave).value(), 880.0);
+    // Only the marks inside the span are left: 100 and 1000 Hz.
+    assert_eq!(app.knob_marks_sparse.len(), 2);
+
+    // It isn't inside a sub control's, so it goes to the top.
+    send(&mut app, [Message::FreqSpan(FreqSpan(20.0, 200.0))]);
+    assert_close(app.param(ParamId::KnobFreq).value(), 200.0);
+    assert!(app
+        .log
+        .latest()
+        .unwrap()
+        .ends_with("moving the frequency to 200.0 Hz"));
+}
+
+#[test]
+fn drift_counts_changes_since_the_last_save() {
+    let dir = temp_dir("drift");
+    let mut app = app();
+    app.preset_path = dir.join("preset.json");
+
+    // Nothing saved or loaded yet, so nothing to drift from.
+    send(&mut app, [Message::KnobFreq(normal(0.7))]);
+    assert!(app.drifted().is_empty());
+    assert_eq!(app.drift_label(), "");
+
+    send(&mut app, [Message::SavePreset]);
+    assert_eq!(app.drift_label(), "No changes since saved");
+
+    send(
+        &mut app,
+        [
+            Message::KnobFreq(normal(0.2)),
+            Message::HSliderInt(normal(0.9)),
+        ],
+    );
+    assert_eq!(app.drifted(), vec![ParamId::HSliderInt, ParamId::KnobFreq]);
+    assert_eq!(app.drift_label(), "2 changes since saved");
+
+    // Moving back to the saved value is no longer a change.
+    send(&mut app, [Message::KnobFreq(normal(0.7))]);
+    assert_eq!(app.drifted(), vec![ParamId::HSliderInt]);
+
+    send(&mut app, [Message::SavePreset]);
+    assert!(app.drifted().is_empty());
+
+    send(
+        &mut app,
+        [Message::HSliderInt(normal(0.1)), Message::LoadPreset],
+    );
+    assert!(app.drifted().is_empty());
+}
+
+#[test]
+fn out_of_range_presets_are_clamped_with_a_warning() {
+    let dir = temp_dir("clamp");
+    let mut app = app();
+    app.preset_path = dir.join("preset.json");
+
+    // Saved under a wider range: the knob past its end, and the gain at the
+    // top of the dB range, past the limit below it.
+    let mut preset = app.preset();
+    preset.knob = 1.5;
+    preset.v_slider = 1.0;
+    preset.lfo_rate = 100.0;
+    preset.save(&app.preset_path).unwrap();
+    send(&mut app, [Message::LoadPreset]);
+
+    assert_eq!(app.normal(ParamId::KnobFreq), 1.0);
+    assert_close(app.param(ParamId::VSliderDB).value(), GAIN_LIMIT_DB);
+    assert_close(app.lfo.rate(), lfo::RATES.max());
+    assert!(app
+        .log
+        .latest()
+        .unwrap()
+        .ends_with("; clamped into range: VSliderDB, KnobFreq, LFO rate"));
+
+    // One within range loads without a warning.
+    assert!(app.clamped(&app.preset()).is_empty());
+}
+
+#[test]
+fn stats_count_each_parameters_changes_and_extremes() {
+    let mut app = app();
+
+    send(
+        &mut app,
+        [
+            Message::HSliderInt(normal(0.5)),
+            Message::HSliderInt(normal(1.0)),
+            Message::HSliderInt(normal(0.2)),
+            Message::Reset(ParamId::RampCurve),
+        ],
+    );
+    let usage = app.stats.get(ParamId::HSliderInt).unwrap();
+    assert_eq!(usage.changes, 3);
+    assert_eq!(usage.min, 2.0);
+    assert_eq!(usage.max, 10.0);
+    assert_eq!(app.stats.get(ParamId::RampCurve).unwrap().changes, 1);
+    assert_eq!(app.stats.get(ParamId::VSliderDB), None);
+
+    send(&mut app, [Message::ToggleStats, Message::ResetStats]);
+    assert!(app.stats_open);
+    assert_eq!(app.stats.iter().count(), 0);
+}
+
+#[test]
+fn knob_drags_snap_to_nearby_detents() {
+    let mut app = app();
+
+    send(
+        &mut app,
+        [
+            Message::KnobDetentsEdited("0.75, 0.25".into()),
+            Message::KnobDetentsSubmitted,
+            Message::KnobMagnetismEdited("0.05".into()),
+            Message::KnobMagnetismSubmitted,
+            Message::KnobFreq(normal(0.28)),
+        ],
+    );
+    assert_eq!(app.knob_detents, [normal(0.25), normal(0.75)]);
+    assert_eq!(app.normal(ParamId::KnobFreq), 0.25);
+    assert!(app.log.latest().unwrap().ends_with("(detent)"));
+
+    // Away from any detent, the knob moves freely.
+    send(&mut app, [Message::KnobFreq(normal(0.4))]);
+    assert_eq!(app.normal(ParamId::KnobFreq), 0.4);
+    assert!(!app.log.latest().unwrap().ends_with("(detent)"));
+
+    send(
+        &mut app,
+        [
+            Message::KnobDetentsEdited("0.5, 2".into()),
+            Message::KnobDetentsSubmitted,
+        ],
+    );
+    assert_eq!(app.knob_detents.len(), 2);
+    assert!(app.log.latest().unwrap().starts_with("Invalid detents"));
+}
+
+#[test]
+fn a_launch_preset_is_where_the_session_starts() {
+    let dir = temp_dir("launch");
+    let path = dir.join("demo.json");
+    let mut preset = app().preset();
+    preset.knob = 0.8;
+    preset.slider_value = 0.3;
+    preset.save(&path).unwrap();
+
+    let mut app = app();
+    app.prefs.morph_time = 500;
+    let (_, preset) = App::builder().load_preset(&path).unwrap().preset.unwrap();
+    app.start_from_preset(&path, &preset);
+
+    // Applied straight away, with nothing to undo.
+    assert_eq!(app.normal(ParamId::KnobFreq), 0.8);
+    assert_eq!(app.slider_value, 0.3);
+    assert!(app.morph.is_none());
+    send(&mut app, [Message::Undo]);
+    assert_eq!(app.normal(ParamId::KnobFreq), 0.8);
+    assert!(app.drifted().is_empty());
+    assert!(app.edited.is_none());
+
+    fs::write(&path, "{").unwrap();
+    assert!(App::builder().load_preset(&path).is_err());
+    assert!(App::builder()
+        .load_preset(dir.join("missing.json"))
+        .is_err());
+}
@@ -0,0 +1,142 @@
+//! Limit how often each parameter's value goes out to other programs.
+
+use std::collections::HashMap;
+use std::time::{Duration, Instant};
+
+use crate::param::ParamId;
+
+/// Lets at most one value per parameter out every `interval`, holding back
+/// the rest.
+///
+/// A drag can move a parameter hundreds of times a second, more than a
+/// receiver on the network wants. A value offered too soon after the last
+/// one sent replaces whatever was held for its parameter, so the one
+/// eventually sent is always the latest: none is ever lost for good.
+#[derive(Debug)]
+pub struct Throttle {
+    interval: Duration,
+    // When each parameter last had a value let out.
+    sent: HashMap<ParamId, Instant>,
+    // The latest value held back for each parameter.
+    held: HashMap<ParamId, f32>,
+}
+
+impl Throttle {
+    pub fn new(interval: Duration) -> Self {
+        Throttle {
+            interval,
+            sent: HashMap::new(),
+            held: HashMap::new(),
+        }
+    }
+
+    /// Changes the interval. Values already held go out on the new one.
+    pub fn set_interval(&mut self, interval: Duration) {
+        self.interval = interval;
+    }
+
+    /// Offers `id`'s new value, returning it if it should go out now, or
+    /// holding it back until `due` or `flush` lets it out.
+    pub fn offer(&mut self, id: ParamId, value: f32, now: Instant) -> Option<f32> {
+        if self.is_due(id, now) {
+            self.held.remove(&id);
+            self.sent.insert(id, now);
+            Some(value)
+        } else {
+            self.held.insert(id, value);
+            None
+        }
+    }
+
+    /// Takes the held values whose interval has passed since their
+    /// parameter's last went out.
+    pub fn due(&mut self, now: Instant) -> Vec<(ParamId, f32)> {
+        let due: Vec<_> = self
+            .held
+            .iter()
+            .filter(|&(&id, _)| self.is_due(id, now))
+            .map(|(&id, &value)| (id, value))
+            .collect();
+
+        for &(id, _) in &due {
+            self.held.remove(&id);
+            self.sent.insert(id, now);
+        }
+        due
+    }
+
+    /// Takes every held value, due or not, as at the end of a drag when the
+    /// final value mustn't wait.
+    pub fn flush(&mut self, now: Instant) -> Vec<(ParamId, f32)> {
+        let held: Vec<_> = self.held.drain().collect();
+
+        for &(id, _) in &held {
+            self.sent.insert(id, now);
+        }
+        held
+    }
+
+    /// Whether any value is waiting to go out.
+    pub fn is_holding(&self) -> bool {
+        !self.held.is_empty()
+    }
+
+    fn is_due(&self, id: ParamId, now: Instant) -> bool {
+        self.sent
+            .get(&id)
+            .is_none_or(|&sent| now.duration_since(sent) >= self.interval)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const INTERVAL: Duration = Duration::from_millis(20);
+
+    #[test]
+    fn holds_values_within_the_interval() {
+        let mut throttle = Throttle::new(INTERVAL);
+        let start = Instant::now();
+
+        assert_eq!(throttle.offer(ParamId::KnobFreq, 1.0, start), Some(1.0));
+        assert_eq!(throttle.offer(ParamId::KnobFreq, 2.0, start), None);
+        // Other parameters have their own interval.
+        assert_eq!(throttle.offer(ParamId::VSliderDB, 3.0, start), Some(3.0));
+        assert_eq!(throttle.offer(ParamId::KnobFreq, 4.0, start), None);
+        assert!(throttle.is_holding());
+
+        // Only the latest held value goes out, once its interval is up.
+        assert!(throttle.due(start + INTERVAL / 2).is_empty());
+        assert_eq!(
+            throttle.due(start + INTERVAL),
+            vec![(ParamId::KnobFreq, 4.0)]
+        );
+        assert!(!throttle.is_holding());
+        assert_eq!(
+            throttle.offer(ParamId::KnobFreq, 5.0, start + INTERVAL),
+            None
+        );
+    }
+
+    #[test]
+    fn flush_lets_everything_out() {
+        let mut throttle = Throttle::new(INTERVAL);
+        let start = Instant::now();
+
+        throttle.offer(ParamId::KnobFreq, 1.0, start);
+        throttle.offer(ParamId::KnobFreq, 2.0, start);
+
+        assert_eq!(throttle.flush(start), vec![(ParamId::KnobFreq, 2.0)]);
+        assert!(throttle.flush(start).is_empty());
+    }
+
+    #[test]
+    fn no_interval_lets_every_value_out() {
+        let mut throttle = Throttle::new(Duration::ZERO);
+        let start = Instant::now();
+
+        assert_eq!(throttle.offer(ParamId::KnobFreq, 1.0, start), Some(1.0));
+        assert_eq!(throttle.offer(ParamId::KnobFreq, 2.0, start), Some(2.0));
+    }
+}
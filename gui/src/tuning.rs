@@ -0,0 +1,156 @@
+//! Equal-tempered notes, for snapping and labelling the frequency knob.
+
+/// The MIDI note number the reference pitch is tuned to, A4.
+const REFERENCE_NOTE: i32 = 69;
+
+const NAMES: [&str; 12] = [
+    "C", "C#", "D", "D#", "E", "F", "F#", "G", "G#", "A", "A#", "B",
+];
+
+/// The nearest 12-TET note to `frequency`, as a MIDI note number, with A4
+/// tuned to `reference`.
+pub fn nearest_note(frequency: f32, reference: f32) -> i32 {
+    let semitones = 12.0 * (frequency / reference).log2();
+    REFERENCE_NOTE + semitones.round() as i32
+}
+
+/// The frequency of a MIDI note number with A4 tuned to `reference`.
+pub fn note_frequency(note: i32, reference: f32) -> f32 {
+    reference * 2.0_f32.powf((note - REFERENCE_NOTE) as f32 / 12.0)
+}
+
+/// The nearest note to `frequency` and how far off it is, in cents from -50
+/// to +50, with A4 tuned to `reference`. `None` for frequencies that have no
+/// pitch, zero and below.
+pub fn pitch(frequency: f32, reference: f32) -> Option<(i32, f32)> {
+    if !(frequency > 0.0 && frequency.is_finite()) {
+        return None;
+    }
+
+    // Rounding the whole semitone count, rather than the position within an
+    // octave, keeps notes just below C in the octave beneath it.
+    let semitones = 12.0 * f64::from(frequency / reference).log2();
+    let nearest = semitones.round();
+    let cents = (semitones - nearest) * 100.0;
+
+    Some((REFERENCE_NOTE + nearest as i32, cents as f32))
+}
+
+/// The nearest note to `frequency` and its deviation, like "A4 +12c".
+pub fn pitch_label(frequency: f32, reference: f32) -> String {
+    match pitch(frequency, reference) {
+        // Whole cents, so a hair flat doesn't read as "-0c".
+        Some((note, cents)) => format!("{} {:+}c", note_name(note), cents.round() as i32),
+        None => "no pitch".into(),
+    }
+}
+
+/// The name of a MIDI note number, like "A4" or "C#5". Middle C is C4.
+pub fn note_name(note: i32) -> String {
+    let octave = note.div_euclid(12) - 1;
+    format!("{}{}", NAMES[note.rem_euclid(12) as usize], octave)
+}
+
+/// The MIDI note number of a name like "C#4", "Bb2" or "e-1": a letter, any
+/// number of sharps or flats, and an octave, where middle C is C4. `None`
+/// if it isn't one, or its octave is too far out to number.
+pub fn parse_note(name: &str) -> Option<i32> {
+    let name = name.trim();
+    let mut chars = name.chars();
+
+    let class = match chars.next()?.to_ascii_uppercase() {
+        'C' => 0,
+        'D' => 2,
+        'E' => 4,
+        'F' => 5,
+        'G' => 7,
+        'A' => 9,
+        'B' => 11,
+        _ => return None,
+    };
+
+    let rest = chars.as_str();
+    let octave_at = rest.find(|c| c != '#' && c != 'b').unwrap_or(rest.len());
+    let (accidentals, octave) = rest.split_at(octave_at);
+    let shift: i32 = accidentals
+        .chars()
+        .map(|c| if c == '#' { 1 } else { -1 })
+        .sum();
+    let octave: i32 = octave.parse().ok()?;
+
+    let note = octave
+        .checked_add(1)?
+        .checked_mul(12)?
+        .checked_add(class + shift)?;
+    // `note_frequency` measures it from the reference.
+    note.checked_sub(REFERENCE_NOTE)?;
+
+    Some(note)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn snaps_to_nearest_note() {
+        assert_eq!(nearest_note(440.0, 440.0), 69);
+        assert_eq!(nearest_note(450.0, 440.0), 69);
+        assert_eq!(nearest_note(261.63, 440.0), 60);
+        assert_eq!(nearest_note(432.0, 432.0), 69);
+    }
+
+    #[test]
+    fn note_names() {
+        assert_eq!(note_name(69), "A4");
+        assert_eq!(note_name(73), "C#5");
+        assert_eq!(note_name(60), "C4");
+        assert_eq!(note_name(0), "C-1");
+    }
+
+    #[test]
+    fn pitch_labels() {
+        assert_eq!(pitch_label(440.0, 440.0), "A4 +0c");
+        assert_eq!(pitch_label(445.0, 440.0), "A4 +20c");
+        assert_eq!(pitch_label(430.0, 440.0), "A4 -40c");
+        assert_eq!(pitch_label(0.0, 440.0), "no pitch");
+    }
+
+    #[test]
+    fn pitch_near_octave_boundary() {
+        // A few cents under C4 is still C4, but far enough under is the top
+        // of the octave below.
+        let c4 = note_frequency(60, 440.0);
+        assert_eq!(pitch_label(c4 * 0.998, 440.0), "C4 -3c");
+        assert_eq!(pitch_label(c4 * 0.97, 440.0), "B3 +47c");
+    }
+
+    #[test]
+    fn pitch_at_extremes() {
+        assert_eq!(pitch_label(20480.0, 440.0), "D#10 +49c");
+        assert_eq!(pitch_label(1.0, 440.0), "C-4 -38c");
+    }
+
+    #[test]
+    fn parses_note_names() {
+        assert_eq!(parse_note("A4"), Some(69));
+        assert_eq!(parse_note(" c#5 "), Some(73));
+        assert_eq!(parse_note("Db5"), Some(73));
+        assert_eq!(parse_note("B#3"), Some(60));
+        assert_eq!(parse_note("C-1"), Some(0));
+
+        assert_eq!(parse_note("H4"), None);
+        assert_eq!(parse_note("C"), None);
+        assert_eq!(parse_note("C#x"), None);
+        // Octaves so far out the note number would overflow.
+        assert_eq!(parse_note("C999999999"), None);
+        assert_eq!(parse_note("C-178956970"), None);
+        assert_eq!(parse_note(""), None);
+    }
+
+    #[test]
+    fn octave_doubles_frequency() {
+        assert!((note_frequency(81, 440.0) - 880.0).abs() < 1e-3);
+        assert!((note_frequency(69, 432.0) - 432.0).abs() < 1e-3);
+    }
+}
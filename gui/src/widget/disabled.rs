@@ -0,0 +1,40 @@
+//! Grey out a widget that doesn't apply right now.
+
+use iced_native::{renderer, Color, Rectangle, Theme};
+
+use super::inert::{Inert, Overlay};
+
+// How much of the background covers a disabled widget.
+const WASH_ALPHA: f32 = 0.6;
+
+/// Draws a widget washed over with the background color and passes it no
+/// input, so it can't be moved, right-clicked or hovered until it's enabled
+/// again.
+///
+/// Unlike [`ReadOnly`](super::ReadOnly) it keeps the wrapped widget's
+/// message type, so the same widget can be built either way.
+pub type Disabled<'a, Message, Renderer> = Inert<'a, Message, Renderer, Wash>;
+
+/// The background color washed over a [`Disabled`] widget.
+pub struct Wash;
+
+impl<Renderer> Overlay<Renderer> for Wash
+where
+    Renderer: renderer::Renderer<Theme = Theme>,
+{
+    fn draw(renderer: &mut Renderer, theme: &Theme, bounds: Rectangle) {
+        let background = theme.palette().background;
+        renderer.fill_quad(
+            renderer::Quad {
+                bounds,
+                border_radius: 0.0.into(),
+                border_width: 0.0,
+                border_color: Color::TRANSPARENT,
+            },
+            Color {
+                a: WASH_ALPHA,
+                ..background
+            },
+        );
+    }
+}
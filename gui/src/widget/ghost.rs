@@ -0,0 +1,198 @@
+//! Show where a slider's handle would go, without moving it.
+
+use iced_audio::Normal;
+use iced_native::widget::tree::{self, Tree};
+use iced_native::{
+    event, layout, mouse, overlay, renderer, Clipboard, Color, Element, Event, Layout, Length,
+    Point, Rectangle, Shell, Theme, Widget,
+};
+
+use crate::param::track_offset;
+
+// How solid the ghost handle is.
+const GHOST_ALPHA: f32 = 0.35;
+
+/// Which way a [`Ghost`]'s slider runs.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Orientation {
+    /// Minimum at the left.
+    Horizontal,
+    /// Minimum at the bottom.
+    Vertical,
+}
+
+/// Draws a faint handle over a slider at a value it isn't set to, such as
+/// the one a click would set. It's otherwise transparent: every event
+/// reaches the slider as it would without the ghost.
+pub struct Ghost<'a, Message, Renderer> {
+    content: Element<'a, Message, Renderer>,
+    orientation: Orientation,
+    // The slider's handle length along the track.
+    handle: f32,
+    normal: Option<Normal>,
+}
+
+impl<'a, Message, Renderer> Ghost<'a, Message, Renderer> {
+    pub fn new(
+        content: impl Into<Element<'a, Message, Renderer>>,
+        orientation: Orientation,
+        handle: f32,
+    ) -> Self {
+        Ghost {
+            content: content.into(),
+            orientation,
+            handle,
+            normal: None,
+        }
+    }
+
+    /// Sets where the ghost handle is drawn, or hides it with `None`.
+    pub fn at(mut self, normal: Option<Normal>) -> Self {
+        self.normal = normal;
+        self
+    }
+}
+
+impl<'a, Message, Renderer> Widget<Message, Renderer> for Ghost<'a, Message, Renderer>
+where
+    Renderer: renderer::Renderer<Theme = Theme>,
+{
+    fn tag(&self) -> tree::Tag {
+        self.content.as_widget().tag()
+    }
+
+    fn state(&self) -> tree::State {
+        self.content.as_widget().state()
+    }
+
+    fn children(&self) -> Vec<Tree> {
+        self.content.as_widget().children()
+    }
+
+    fn diff(&self, tree: &mut Tree) {
+        self.content.as_widget().diff(tree)
+    }
+
+    fn width(&self) -> Length {
+        self.content.as_widget().width()
+    }
+
+    fn height(&self) -> Length {
+        self.content.as_widget().height()
+    }
+
+    fn layout(&self, renderer: &Renderer, limits: &layout::Limits) -> layout::Node {
+        self.content.as_widget().layout(renderer, limits)
+    }
+
+    fn on_event(
+        &mut self,
+        tree: &mut Tree,
+        event: Event,
+        layout: Layout<'_>,
+        cursor_position: Point,
+        renderer: &Renderer,
+        clipboard: &mut dyn Clipboard,
+        shell: &mut Shell<'_, Message>,
+    ) -> event::Status {
+        self.content.as_widget_mut().on_event(
+            tree,
+            event,
+            layout,
+            cursor_position,
+            renderer,
+            clipboard,
+            shell,
+        )
+    }
+
+    fn mouse_interaction(
+        &self,
+        tree: &Tree,
+        layout: Layout<'_>,
+        cursor_position: Point,
+        viewport: &Rectangle,
+        renderer: &Renderer,
+    ) -> mouse::Interaction {
+        self.content.as_widget().mouse_interaction(
+            tree,
+            layout,
+            cursor_position,
+            viewport,
+            renderer,
+        )
+    }
+
+    fn draw(
+        &self,
+        tree: &Tree,
+        renderer: &mut Renderer,
+        theme: &Theme,
+        style: &renderer::Style,
+        layout: Layout<'_>,
+        cursor_position: Point,
+        viewport: &Rectangle,
+    ) {
+        self.content.as_widget().draw(
+            tree,
+            renderer,
+            theme,
+            style,
+            layout,
+            cursor_position,
+            viewport,
+        );
+
+        let Some(normal) = self.normal else {
+            return;
+        };
+
+        let bounds = layout.bounds();
+        let ghost = match self.orientation {
+            Orientation::Horizontal => Rectangle {
+                x: bounds.x + track_offset(normal, bounds.width, self.handle) - self.handle / 2.0,
+                width: self.handle,
+                ..bounds
+            },
+            Orientation::Vertical => Rectangle {
+                y: bounds.y + bounds.height
+                    - track_offset(normal, bounds.height, self.handle)
+                    - self.handle / 2.0,
+                height: self.handle,
+                ..bounds
+            },
+        };
+
+        renderer.fill_quad(
+            renderer::Quad {
+                bounds: ghost,
+                border_radius: 2.0.into(),
+                border_width: 0.0,
+                border_color: Color::TRANSPARENT,
+            },
+            Color {
+                a: GHOST_ALPHA,
+                ..theme.palette().primary
+            },
+        );
+    }
+
+    fn overlay<'b>(
+        &'b mut self,
+        tree: &'b mut Tree,
+        layout: Layout<'_>,
+        renderer: &Renderer,
+    ) -> Option<overlay::Element<'b, Message, Renderer>> {
+        self.content.as_widget_mut().overlay(tree, layout, renderer)
+    }
+}
+
+impl<'a, Message, Renderer> From<Ghost<'a, Message, Renderer>> for Element<'a, Message, Renderer>
+where
+    Message: 'a,
+    Renderer: 'a + renderer::Renderer<Theme = Theme>,
+{
+    fn from(ghost: Ghost<'a, Message, Renderer>) -> Self {
+        Element::new(ghost)
+    }
+}
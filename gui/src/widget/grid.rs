@@ -0,0 +1,177 @@
+//! Draw a grid over a widget, for lining up a position on it.
+
+use iced_native::widget::tree::{self, Tree};
+use iced_native::{
+    event, layout, mouse, overlay, renderer, Clipboard, Color, Element, Event, Layout, Length,
+    Point, Rectangle, Shell, Widget,
+};
+
+// How wide each grid line is, in logical pixels.
+const LINE_WIDTH: f32 = 1.0;
+
+/// Draws evenly spaced lines over a widget, dividing it into `divisions`
+/// across and down. It's otherwise transparent: every event reaches the
+/// widget as it would without the grid.
+pub struct Grid<'a, Message, Renderer> {
+    content: Element<'a, Message, Renderer>,
+    divisions: u16,
+    color: Color,
+}
+
+impl<'a, Message, Renderer> Grid<'a, Message, Renderer> {
+    pub fn new(content: impl Into<Element<'a, Message, Renderer>>, divisions: u16) -> Self {
+        Grid {
+            content: content.into(),
+            divisions,
+            color: Color::BLACK,
+        }
+    }
+
+    pub fn color(mut self, color: Color) -> Self {
+        self.color = color;
+        self
+    }
+}
+
+impl<'a, Message, Renderer> Widget<Message, Renderer> for Grid<'a, Message, Renderer>
+where
+    Renderer: iced_native::Renderer,
+{
+    fn tag(&self) -> tree::Tag {
+        self.content.as_widget().tag()
+    }
+
+    fn state(&self) -> tree::State {
+        self.content.as_widget().state()
+    }
+
+    fn children(&self) -> Vec<Tree> {
+        self.content.as_widget().children()
+    }
+
+    fn diff(&self, tree: &mut Tree) {
+        self.content.as_widget().diff(tree)
+    }
+
+    fn width(&self) -> Length {
+        self.content.as_widget().width()
+    }
+
+    fn height(&self) -> Length {
+        self.content.as_widget().height()
+    }
+
+    fn layout(&self, renderer: &Renderer, limits: &layout::Limits) -> layout::Node {
+        self.content.as_widget().layout(renderer, limits)
+    }
+
+    fn on_event(
+        &mut self,
+        tree: &mut Tree,
+        event: Event,
+        layout: Layout<'_>,
+        cursor_position: Point,
+        renderer: &Renderer,
+        clipboard: &mut dyn Clipboard,
+        shell: &mut Shell<'_, Message>,
+    ) -> event::Status {
+        self.content.as_widget_mut().on_event(
+            tree,
+            event,
+            layout,
+            cursor_position,
+            renderer,
+            clipboard,
+            shell,
+        )
+    }
+
+    fn mouse_interaction(
+        &self,
+        tree: &Tree,
+        layout: Layout<'_>,
+        cursor_position: Point,
+        viewport: &Rectangle,
+        renderer: &Renderer,
+    ) -> mouse::Interaction {
+        self.content.as_widget().mouse_interaction(
+            tree,
+            layout,
+            cursor_position,
+            viewport,
+            renderer,
+        )
+    }
+
+    fn draw(
+        &self,
+        tree: &Tree,
+        renderer: &mut Renderer,
+        theme: &Renderer::Theme,
+        style: &renderer::Style,
+        layout: Layout<'_>,
+        cursor_position: Point,
+        viewport: &Rectangle,
+    ) {
+        self.content.as_widget().draw(
+            tree,
+            renderer,
+            theme,
+            style,
+            layout,
+            cursor_position,
+            viewport,
+        );
+
+        let bounds = layout.bounds();
+        let line = |bounds| renderer::Quad {
+            bounds,
+            border_radius: 0.0.into(),
+            border_width: 0.0,
+            border_color: Color::TRANSPARENT,
+        };
+
+        // The edges are the widget's border, so only the lines between.
+        for i in 1..self.divisions {
+            let t = f32::from(i) / f32::from(self.divisions);
+            let x = bounds.x + bounds.width * t - LINE_WIDTH / 2.0;
+            let y = bounds.y + bounds.height * t - LINE_WIDTH / 2.0;
+
+            renderer.fill_quad(
+                line(Rectangle {
+                    x,
+                    width: LINE_WIDTH,
+                    ..bounds
+                }),
+                self.color,
+            );
+            renderer.fill_quad(
+                line(Rectangle {
+                    y,
+                    height: LINE_WIDTH,
+                    ..bounds
+                }),
+                self.color,
+            );
+        }
+    }
+
+    fn overlay<'b>(
+        &'b mut self,
+        tree: &'b mut Tree,
+        layout: Layout<'_>,
+        renderer: &Renderer,
+    ) -> Option<overlay::Element<'b, Message, Renderer>> {
+        self.content.as_widget_mut().overlay(tree, layout, renderer)
+    }
+}
+
+impl<'a, Message, Renderer> From<Grid<'a, Message, Renderer>> for Element<'a, Message, Renderer>
+where
+    Message: 'a,
+    Renderer: 'a + iced_native::Renderer,
+{
+    fn from(grid: Grid<'a, Message, Renderer>) -> Self {
+        Element::new(grid)
+    }
+}
@@ -0,0 +1,130 @@
+//! Draw a widget without passing it any input, the base of the wrappers
+//! that show a widget without letting it be used.
+
+use iced_native::widget::tree::{self, Tree};
+use iced_native::{
+    event, layout, mouse, renderer, Clipboard, Element, Event, Layout, Length, Point, Rectangle,
+    Shell, Widget,
+};
+
+use std::marker::PhantomData;
+
+/// What an [`Inert`] widget draws over its content.
+pub trait Overlay<Renderer: renderer::Renderer> {
+    /// Draws within `bounds`, the content's, after the content.
+    fn draw(renderer: &mut Renderer, theme: &Renderer::Theme, bounds: Rectangle);
+}
+
+// Nothing at all, for a widget that looks the same whether it's inert or not.
+impl<Renderer: renderer::Renderer> Overlay<Renderer> for () {
+    fn draw(_renderer: &mut Renderer, _theme: &Renderer::Theme, _bounds: Rectangle) {}
+}
+
+/// Draws a widget, then its [`Overlay`] over it, and never passes it input,
+/// so it can't be moved, right-clicked or hovered.
+///
+/// The wrapped widget's messages are never published, so it can sit in a
+/// view of any message type.
+pub struct Inert<'a, Inner, Renderer, O> {
+    content: Element<'a, Inner, Renderer>,
+    overlay: PhantomData<O>,
+}
+
+impl<'a, Inner, Renderer, O> Inert<'a, Inner, Renderer, O> {
+    pub fn new(content: impl Into<Element<'a, Inner, Renderer>>) -> Self {
+        Inert {
+            content: content.into(),
+            overlay: PhantomData,
+        }
+    }
+}
+
+impl<'a, Inner, Message, Renderer, O> Widget<Message, Renderer> for Inert<'a, Inner, Renderer, O>
+where
+    Renderer: renderer::Renderer,
+    O: Overlay<Renderer>,
+{
+    fn tag(&self) -> tree::Tag {
+        self.content.as_widget().tag()
+    }
+
+    fn state(&self) -> tree::State {
+        self.content.as_widget().state()
+    }
+
+    fn children(&self) -> Vec<Tree> {
+        self.content.as_widget().children()
+    }
+
+    fn diff(&self, tree: &mut Tree) {
+        self.content.as_widget().diff(tree)
+    }
+
+    fn width(&self) -> Length {
+        self.content.as_widget().width()
+    }
+
+    fn height(&self) -> Length {
+        self.content.as_widget().height()
+    }
+
+    fn layout(&self, renderer: &Renderer, limits: &layout::Limits) -> layout::Node {
+        self.content.as_widget().layout(renderer, limits)
+    }
+
+    fn on_event(
+        &mut self,
+        _tree: &mut Tree,
+        _event: Event,
+        _layout: Layout<'_>,
+        _cursor_position: Point,
+        _renderer: &Renderer,
+        _clipboard: &mut dyn Clipboard,
+        _shell: &mut Shell<'_, Message>,
+    ) -> event::Status {
+        event::Status::Ignored
+    }
+
+    // No grab cursor, since there's nothing to grab.
+    fn mouse_interaction(
+        &self,
+        _tree: &Tree,
+        _layout: Layout<'_>,
+        _cursor_position: Point,
+        _viewport: &Rectangle,
+        _renderer: &Renderer,
+    ) -> mouse::Interaction {
+        mouse::Interaction::default()
+    }
+
+    fn draw(
+        &self,
+        tree: &Tree,
+        renderer: &mut Renderer,
+        theme: &Renderer::Theme,
+        style: &renderer::Style,
+        layout: Layout<'_>,
+        _cursor_position: Point,
+        viewport: &Rectangle,
+    ) {
+        // Drawn as if the cursor were elsewhere, so it never shows hovered.
+        let away = Point::new(-1.0, -1.0);
+
+        self.content
+            .as_widget()
+            .draw(tree, renderer, theme, style, layout, away, viewport);
+        O::draw(renderer, theme, layout.bounds());
+    }
+}
+
+impl<'a, Inner, Message, Renderer, O> From<Inert<'a, Inner, Renderer, O>>
+    for Element<'a, Message, Renderer>
+where
+    Inner: 'a,
+    Renderer: 'a + renderer::Renderer,
+    O: 'a + Overlay<Renderer>,
+{
+    fn from(inert: Inert<'a, Inner, Renderer, O>) -> Self {
+        Element::new(inert)
+    }
+}
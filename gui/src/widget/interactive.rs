@@ -0,0 +1,416 @@
+//! Wrap a parameter widget to intercept gestures the iced_audio widgets
+//! would otherwise handle on their own.
+
+use iced_native::widget::tree::{self, Tree};
+use iced_native::{
+    event, keyboard, layout, mouse, overlay, renderer, touch, Clipboard, Element, Event, Layout,
+    Length, Point, Rectangle, Shell, Widget,
+};
+use serde::{Deserialize, Serialize};
+
+use std::fmt;
+
+// What a single click produces, from the widget's bounds and the click.
+type OnClick<'a, Message> = Box<dyn Fn(Rectangle, Point) -> Option<Message> + 'a>;
+
+/// Which way a drag moves a widget that only reads vertical drags, like a
+/// knob.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum DragAxis {
+    /// Up raises the value, as the widget does on its own.
+    #[default]
+    Vertical,
+    /// Right raises the value.
+    Horizontal,
+    /// Up and right both raise the value, so a diagonal drag counts twice.
+    Both,
+}
+
+impl DragAxis {
+    pub const ALL: [DragAxis; 3] = [DragAxis::Vertical, DragAxis::Horizontal, DragAxis::Both];
+
+    /// Where to tell the widget the cursor is, for a drag from `anchor` to
+    /// `cursor`. Moving right by some distance reads as moving up by it.
+    pub fn map(self, anchor: Point, cursor: Point) -> Point {
+        let right = cursor.x - anchor.x;
+
+        match self {
+            DragAxis::Vertical => cursor,
+            DragAxis::Horizontal => Point::new(cursor.x, anchor.y - right),
+            DragAxis::Both => Point::new(cursor.x, cursor.y - right),
+        }
+    }
+}
+
+impl fmt::Display for DragAxis {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            DragAxis::Vertical => "Vertical",
+            DragAxis::Horizontal => "Horizontal",
+            DragAxis::Both => "Both",
+        })
+    }
+}
+
+/// A transparent wrapper around a parameter widget.
+///
+/// Events are forwarded to the wrapped widget unless one of the registered
+/// gestures matches, in which case the gesture's messages are published and
+/// the event is captured.
+///
+/// It also lets several widgets be dragged at once on a touchscreen. iced
+/// tracks a single cursor that every finger moves, and the iced_audio widgets
+/// follow any finger's movement while dragged. Each wrapper claims the finger
+/// that pressed on it and only passes on that finger's touches, positioned
+/// where that finger is.
+pub struct Interactive<'a, Message, Renderer> {
+    content: Element<'a, Message, Renderer>,
+    on_double_click: Vec<Message>,
+    on_click: Option<OnClick<'a, Message>>,
+    on_scroll: Option<Box<dyn Fn(f32, keyboard::Modifiers) -> Message + 'a>>,
+    drag_axis: DragAxis,
+    on_hover: Option<OnClick<'a, Message>>,
+    on_leave: Option<Message>,
+}
+
+impl<'a, Message, Renderer> Interactive<'a, Message, Renderer> {
+    pub fn new(content: impl Into<Element<'a, Message, Renderer>>) -> Self {
+        Interactive {
+            content: content.into(),
+            on_double_click: Vec::new(),
+            on_click: None,
+            on_scroll: None,
+            drag_axis: DragAxis::default(),
+            on_hover: None,
+            on_leave: None,
+        }
+    }
+
+    /// Sets the messages published when the widget is double-clicked.
+    ///
+    /// The wrapped widget never sees the second click, so it can't apply its
+    /// own reset behavior on top of the app's.
+    pub fn on_double_click(mut self, messages: impl IntoIterator<Item = Message>) -> Self {
+        self.on_double_click = messages.into_iter().collect();
+        self
+    }
+
+    /// Sets the message produced by a single click, from the widget's bounds
+    /// and where the click landed, if it should produce one.
+    ///
+    /// The wrapped widget still sees the click first, so it's grabbed before
+    /// the message arrives and a drag carries on from wherever it sets.
+    pub fn on_click(mut self, on_click: impl Fn(Rectangle, Point) -> Option<Message> + 'a) -> Self {
+        self.on_click = Some(Box::new(on_click));
+        self
+    }
+
+    /// Sets the message produced when the mouse wheel is scrolled over the
+    /// widget. It receives the number of lines scrolled (positive is up) and
+    /// the modifier keys held at the time.
+    pub fn on_scroll(
+        mut self,
+        on_scroll: impl Fn(f32, keyboard::Modifiers) -> Message + 'a,
+    ) -> Self {
+        self.on_scroll = Some(Box::new(on_scroll));
+        self
+    }
+
+    /// Sets the message produced as the cursor moves over the widget without
+    /// a button held, from the widget's bounds and where the cursor is.
+    pub fn on_hover(mut self, on_hover: impl Fn(Rectangle, Point) -> Option<Message> + 'a) -> Self {
+        self.on_hover = Some(Box::new(on_hover));
+        self
+    }
+
+    /// Sets the message published when the cursor leaves the widget after
+    /// hovering over it, or presses it.
+    pub fn on_leave(mut self, on_leave: Message) -> Self {
+        self.on_leave = Some(on_leave);
+        self
+    }
+
+    /// Sets which way dragging moves the wrapped widget, for one that only
+    /// reads vertical drags. Mouse and touch drags are turned alike.
+    pub fn drag_axis(mut self, drag_axis: DragAxis) -> Self {
+        self.drag_axis = drag_axis;
+        self
+    }
+}
+
+// The local state of an [`Interactive`].
+#[derive(Debug, Default)]
+struct State {
+    last_click: Option<mouse::Click>,
+    modifiers: keyboard::Modifiers,
+    // The finger dragging the widget.
+    finger: Option<touch::Finger>,
+    // Where the current press started, that `drag_axis` turns drags around.
+    anchor: Option<Point>,
+    // Whether the cursor was last over the widget with no button held.
+    hovering: bool,
+}
+
+impl<'a, Message, Renderer> Widget<Message, Renderer> for Interactive<'a, Message, Renderer>
+where
+    Message: Clone,
+    Renderer: iced_native::Renderer,
+{
+    fn tag(&self) -> tree::Tag {
+        tree::Tag::of::<State>()
+    }
+
+    fn state(&self) -> tree::State {
+        tree::State::new(State::default())
+    }
+
+    fn children(&self) -> Vec<Tree> {
+        vec![Tree::new(&self.content)]
+    }
+
+    fn diff(&self, tree: &mut Tree) {
+        tree.diff_children(std::slice::from_ref(&self.content))
+    }
+
+    fn width(&self) -> Length {
+        self.content.as_widget().width()
+    }
+
+    fn height(&self) -> Length {
+        self.content.as_widget().height()
+    }
+
+    fn layout(&self, renderer: &Renderer, limits: &layout::Limits) -> layout::Node {
+        self.content.as_widget().layout(renderer, limits)
+    }
+
+    fn on_event(
+        &mut self,
+        tree: &mut Tree,
+        event: Event,
+        layout: Layout<'_>,
+        cursor_position: Point,
+        renderer: &Renderer,
+        clipboard: &mut dyn Clipboard,
+        shell: &mut Shell<'_, Message>,
+    ) -> event::Status {
+        let state = tree.state.downcast_mut::<State>();
+
+        if let Event::Keyboard(keyboard::Event::ModifiersChanged(modifiers)) = event {
+            state.modifiers = modifiers;
+        }
+
+        // Another finger's touch could move or release the widget, so only
+        // the claiming finger's get through.
+        let cursor_position = match event {
+            Event::Touch(touch::Event::FingerPressed { id, position }) => {
+                if state.finger.is_some() || !layout.bounds().contains(position) {
+                    return event::Status::Ignored;
+                }
+                state.finger = Some(id);
+                position
+            }
+            Event::Touch(
+                touch::Event::FingerMoved { id, position }
+                | touch::Event::FingerLifted { id, position }
+                | touch::Event::FingerLost { id, position },
+            ) => {
+                if state.finger != Some(id) {
+                    return event::Status::Ignored;
+                }
+                if !matches!(event, Event::Touch(touch::Event::FingerMoved { .. })) {
+                    state.finger = None;
+                }
+                position
+            }
+            _ => cursor_position,
+        };
+
+        if let Event::Mouse(mouse::Event::CursorMoved { .. }) = event {
+            let over = state.anchor.is_none() && layout.bounds().contains(cursor_position);
+
+            if over {
+                if let Some(message) = self
+                    .on_hover
+                    .as_ref()
+                    .and_then(|on_hover| on_hover(layout.bounds(), cursor_position))
+                {
+                    shell.publish(message);
+                }
+            } else if state.hovering {
+                if let Some(message) = &self.on_leave {
+                    shell.publish(message.clone());
+                }
+            }
+            state.hovering = over;
+        }
+
+        let cursor_position = match event {
+            Event::Mouse(mouse::Event::ButtonPressed(mouse::Button::Left))
+            | Event::Touch(touch::Event::FingerPressed { .. }) => {
+                if layout.bounds().contains(cursor_position) {
+                    if state.hovering {
+                        if let Some(message) = &self.on_leave {
+                            shell.publish(message.clone());
+                        }
+                        state.hovering = false;
+                    }
+                    state.anchor = Some(cursor_position);
+                }
+                cursor_position
+            }
+            Event::Mouse(mouse::Event::ButtonReleased(mouse::Button::Left))
+            | Event::Touch(touch::Event::FingerLifted { .. } | touch::Event::FingerLost { .. }) => {
+                state.anchor.take().map_or(cursor_position, |anchor| {
+                    self.drag_axis.map(anchor, cursor_position)
+                })
+            }
+            _ => state.anchor.map_or(cursor_position, |anchor| {
+                self.drag_axis.map(anchor, cursor_position)
+            }),
+        };
+
+        if let (Event::Mouse(mouse::Event::WheelScrolled { delta }), Some(on_scroll)) =
+            (event.clone(), &self.on_scroll)
+        {
+            if layout.bounds().contains(cursor_position) {
+                let lines = match delta {
+                    mouse::ScrollDelta::Lines { y, .. } => y,
+                    // Pixel deltas (trackpads) only count as a single line.
+                    mouse::ScrollDelta::Pixels { y, .. } if y != 0.0 => y.signum(),
+                    mouse::ScrollDelta::Pixels { .. } => 0.0,
+                };
+
+                if lines != 0.0 {
+                    shell.publish(on_scroll(lines, state.modifiers));
+                }
+
+                return event::Status::Captured;
+            }
+        }
+
+        if let Event::Mouse(mouse::Event::ButtonPressed(mouse::Button::Left))
+        | Event::Touch(touch::Event::FingerPressed { .. }) = event
+        {
+            if layout.bounds().contains(cursor_position) {
+                let click = mouse::Click::new(cursor_position, state.last_click);
+                state.last_click = Some(click);
+
+                let is_double_click = !matches!(click.kind(), mouse::click::Kind::Single);
+
+                if is_double_click && !self.on_double_click.is_empty() {
+                    for message in &self.on_double_click {
+                        shell.publish(message.clone());
+                    }
+
+                    return event::Status::Captured;
+                }
+
+                if let (false, Some(on_click)) = (is_double_click, &self.on_click) {
+                    let status = self.content.as_widget_mut().on_event(
+                        &mut tree.children[0],
+                        event,
+                        layout,
+                        cursor_position,
+                        renderer,
+                        clipboard,
+                        shell,
+                    );
+
+                    if let Some(message) = on_click(layout.bounds(), cursor_position) {
+                        shell.publish(message);
+                    }
+
+                    return status;
+                }
+            }
+        }
+
+        self.content.as_widget_mut().on_event(
+            &mut tree.children[0],
+            event,
+            layout,
+            cursor_position,
+            renderer,
+            clipboard,
+            shell,
+        )
+    }
+
+    fn mouse_interaction(
+        &self,
+        tree: &Tree,
+        layout: Layout<'_>,
+        cursor_position: Point,
+        viewport: &Rectangle,
+        renderer: &Renderer,
+    ) -> mouse::Interaction {
+        self.content.as_widget().mouse_interaction(
+            &tree.children[0],
+            layout,
+            cursor_position,
+            viewport,
+            renderer,
+        )
+    }
+
+    fn draw(
+        &self,
+        tree: &Tree,
+        renderer: &mut Renderer,
+        theme: &Renderer::Theme,
+        style: &renderer::Style,
+        layout: Layout<'_>,
+        cursor_position: Point,
+        viewport: &Rectangle,
+    ) {
+        self.content.as_widget().draw(
+            &tree.children[0],
+            renderer,
+            theme,
+            style,
+            layout,
+            cursor_position,
+            viewport,
+        )
+    }
+
+    fn overlay<'b>(
+        &'b mut self,
+        tree: &'b mut Tree,
+        layout: Layout<'_>,
+        renderer: &Renderer,
+    ) -> Option<overlay::Element<'b, Message, Renderer>> {
+        self.content
+            .as_widget_mut()
+            .overlay(&mut tree.children[0], layout, renderer)
+    }
+}
+
+impl<'a, Message, Renderer> From<Interactive<'a, Message, Renderer>>
+    for Element<'a, Message, Renderer>
+where
+    Message: 'a + Clone,
+    Renderer: 'a + iced_native::Renderer,
+{
+    fn from(interactive: Interactive<'a, Message, Renderer>) -> Self {
+        Element::new(interactive)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn drags_turn_onto_the_vertical() {
+        let anchor = Point::new(10.0, 50.0);
+        let cursor = Point::new(30.0, 45.0);
+
+        assert_eq!(DragAxis::Vertical.map(anchor, cursor), cursor);
+        // 20 right reads as 20 up, and the 5 up is ignored.
+        assert_eq!(DragAxis::Horizontal.map(anchor, cursor).y, 30.0);
+        assert_eq!(DragAxis::Both.map(anchor, cursor).y, 25.0);
+        assert_eq!(DragAxis::Both.map(anchor, anchor), anchor);
+    }
+}
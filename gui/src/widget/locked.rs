@@ -0,0 +1,63 @@
+//! Mark a widget as locked against accidental changes.
+
+use iced_native::{renderer, Color, Rectangle, Theme};
+
+use super::inert::{Inert, Overlay};
+
+// The padlock's body, and its shackle above it, in logical pixels.
+const BODY_WIDTH: f32 = 9.0;
+const BODY_HEIGHT: f32 = 7.0;
+const SHACKLE_WIDTH: f32 = 6.0;
+const SHACKLE_HEIGHT: f32 = 6.0;
+const SHACKLE_THICKNESS: f32 = 1.5;
+
+/// Draws a widget as it is, with a small padlock in its top right corner,
+/// and passes it no input so it can't be dragged or double-clicked.
+///
+/// Unlike [`Disabled`](super::Disabled) the widget isn't greyed out: a
+/// locked parameter still applies, it just can't be moved by accident.
+pub type Locked<'a, Message, Renderer> = Inert<'a, Message, Renderer, Padlock>;
+
+/// The padlock drawn over a [`Locked`] widget.
+pub struct Padlock;
+
+impl<Renderer> Overlay<Renderer> for Padlock
+where
+    Renderer: renderer::Renderer<Theme = Theme>,
+{
+    fn draw(renderer: &mut Renderer, theme: &Theme, bounds: Rectangle) {
+        let color = theme.palette().text;
+        let body = Rectangle {
+            x: bounds.x + bounds.width - BODY_WIDTH,
+            y: bounds.y + SHACKLE_HEIGHT / 2.0,
+            width: BODY_WIDTH,
+            height: BODY_HEIGHT,
+        };
+        // Only the top half of the shackle shows above the body.
+        let shackle = Rectangle {
+            x: body.center_x() - SHACKLE_WIDTH / 2.0,
+            y: bounds.y,
+            width: SHACKLE_WIDTH,
+            height: SHACKLE_HEIGHT,
+        };
+
+        renderer.fill_quad(
+            renderer::Quad {
+                bounds: shackle,
+                border_radius: (SHACKLE_WIDTH / 2.0).into(),
+                border_width: SHACKLE_THICKNESS,
+                border_color: color,
+            },
+            Color::TRANSPARENT,
+        );
+        renderer.fill_quad(
+            renderer::Quad {
+                bounds: body,
+                border_radius: 1.0.into(),
+                border_width: 0.0,
+                border_color: Color::TRANSPARENT,
+            },
+            color,
+        );
+    }
+}
@@ -0,0 +1,108 @@
+//! A read-only level meter.
+
+use iced_audio::Normal;
+use iced_native::widget::Tree;
+use iced_native::{
+    layout, renderer, Color, Element, Layout, Length, Point, Rectangle, Size, Theme, Widget,
+};
+
+// As wide as the iced_audio sliders' rails.
+const DEFAULT_WIDTH: f32 = 8.0;
+
+/// A vertical bar filled from the bottom up to `level`.
+///
+/// Unlike a slider it has no state and ignores all input, so it can be
+/// redrawn from the audio level every frame without ever being dragged.
+pub struct Meter {
+    level: Normal,
+    width: Length,
+    height: Length,
+}
+
+impl Meter {
+    pub fn new(level: Normal) -> Self {
+        Meter {
+            level,
+            width: Length::Fixed(DEFAULT_WIDTH),
+            height: Length::Fill,
+        }
+    }
+}
+
+impl<Message, Renderer> Widget<Message, Renderer> for Meter
+where
+    Renderer: renderer::Renderer<Theme = Theme>,
+{
+    fn width(&self) -> Length {
+        self.width
+    }
+
+    fn height(&self) -> Length {
+        self.height
+    }
+
+    fn layout(&self, _renderer: &Renderer, limits: &layout::Limits) -> layout::Node {
+        let limits = limits.width(self.width).height(self.height);
+
+        layout::Node::new(limits.resolve(Size::ZERO))
+    }
+
+    fn draw(
+        &self,
+        _tree: &Tree,
+        renderer: &mut Renderer,
+        theme: &Theme,
+        _style: &renderer::Style,
+        layout: Layout<'_>,
+        _cursor_position: Point,
+        _viewport: &Rectangle,
+    ) {
+        let bounds = layout.bounds();
+        let palette = theme.extended_palette();
+
+        renderer.fill_quad(
+            renderer::Quad {
+                bounds,
+                border_radius: 2.0.into(),
+                border_width: 1.0,
+                border_color: palette.background.strong.color,
+            },
+            palette.background.weak.color,
+        );
+
+        let height = bounds.height * self.level.as_f32();
+        if height <= 0.0 {
+            return;
+        }
+
+        // A full meter means the output is clipping.
+        let color: Color = if self.level == Normal::MAX {
+            palette.danger.base.color
+        } else {
+            palette.primary.base.color
+        };
+
+        renderer.fill_quad(
+            renderer::Quad {
+                bounds: Rectangle {
+                    y: bounds.y + bounds.height - height,
+                    height,
+                    ..bounds
+                },
+                border_radius: 2.0.into(),
+                border_width: 0.0,
+                border_color: Color::TRANSPARENT,
+            },
+            color,
+        );
+    }
+}
+
+impl<'a, Message, Renderer> From<Meter> for Element<'a, Message, Renderer>
+where
+    Renderer: renderer::Renderer<Theme = Theme>,
+{
+    fn from(meter: Meter) -> Self {
+        Element::new(meter)
+    }
+}
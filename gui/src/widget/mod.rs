@@ -0,0 +1,25 @@
+//! Custom widgets used alongside the iced_audio ones.
+
+pub mod dimmed;
+pub mod disabled;
+pub mod ghost;
+pub mod grid;
+pub mod inert;
+pub mod interactive;
+pub mod locked;
+pub mod meter;
+pub mod popup;
+pub mod range_slider;
+pub mod read_only;
+
+pub use dimmed::Dimmed;
+pub use disabled::Disabled;
+pub use ghost::{Ghost, Orientation};
+pub use grid::Grid;
+pub use inert::Inert;
+pub use interactive::{DragAxis, Interactive};
+pub use locked::Locked;
+pub use meter::Meter;
+pub use popup::Popup;
+pub use range_slider::{Handle, RangeSlider};
+pub use read_only::ReadOnly;
@@ -0,0 +1,345 @@
+//! Show an element over the rest of the UI, such as a context menu or a
+//! tooltip.
+
+use iced_native::widget::tree::{self, Tree};
+use iced_native::{
+    event, layout, mouse, overlay, renderer, touch, Clipboard, Element, Event, Layout, Length,
+    Point, Rectangle, Shell, Size, Vector, Widget,
+};
+
+/// Wraps a widget with a popup that can be shown over everything else.
+///
+/// The popup opens where `content` was last right-clicked, or follows the
+/// cursor with [`Popup::follow_cursor`], and is shown for as long as the app
+/// passes one to [`Popup::open`]. Closing it is up to the app too, usually
+/// from the [`Popup::on_dismiss`] message.
+pub struct Popup<'a, Message, Renderer> {
+    content: Element<'a, Message, Renderer>,
+    popup: Option<Element<'a, Message, Renderer>>,
+    on_right_click: Option<Message>,
+    on_dismiss: Option<Message>,
+    follow: Option<Vector>,
+}
+
+impl<'a, Message, Renderer> Popup<'a, Message, Renderer> {
+    pub fn new(content: impl Into<Element<'a, Message, Renderer>>) -> Self {
+        Popup {
+            content: content.into(),
+            popup: None,
+            on_right_click: None,
+            on_dismiss: None,
+            follow: None,
+        }
+    }
+
+    /// Shows `popup` over the content.
+    pub fn open(mut self, popup: impl Into<Element<'a, Message, Renderer>>) -> Self {
+        self.popup = Some(popup.into());
+        self
+    }
+
+    /// Sets the message published when the content is right-clicked. The
+    /// wrapped widget never sees the click.
+    pub fn on_right_click(mut self, message: Message) -> Self {
+        self.on_right_click = Some(message);
+        self
+    }
+
+    /// Sets the message published when the user clicks outside the open
+    /// popup. Without one, clicks pass through to the UI beneath.
+    pub fn on_dismiss(mut self, message: Message) -> Self {
+        self.on_dismiss = Some(message);
+        self
+    }
+
+    /// Keeps the popup `offset` from the cursor while it moves anywhere in
+    /// the window, as a tooltip does. Near the window's edge the popup goes
+    /// to the cursor's other side, so it's never under the cursor and
+    /// never hides it from the content.
+    pub fn follow_cursor(mut self, offset: Vector) -> Self {
+        self.follow = Some(offset);
+        self
+    }
+}
+
+// The local state of a [`Popup`].
+#[derive(Debug, Default)]
+struct State {
+    // Where the content was last right-clicked, or where the cursor is when
+    // following it, from the content's top left corner.
+    anchor: Vector,
+}
+
+impl<'a, Message, Renderer> Widget<Message, Renderer> for Popup<'a, Message, Renderer>
+where
+    Message: Clone,
+    Renderer: iced_native::Renderer,
+{
+    fn tag(&self) -> tree::Tag {
+        tree::Tag::of::<State>()
+    }
+
+    fn state(&self) -> tree::State {
+        tree::State::new(State::default())
+    }
+
+    fn children(&self) -> Vec<Tree> {
+        std::iter::once(&self.content)
+            .chain(&self.popup)
+            .map(Tree::new)
+            .collect()
+    }
+
+    fn diff(&self, tree: &mut Tree) {
+        let children: Vec<_> = std::iter::once(&self.content).chain(&self.popup).collect();
+        tree.diff_children(&children);
+    }
+
+    fn width(&self) -> Length {
+        self.content.as_widget().width()
+    }
+
+    fn height(&self) -> Length {
+        self.content.as_widget().height()
+    }
+
+    fn layout(&self, renderer: &Renderer, limits: &layout::Limits) -> layout::Node {
+        self.content.as_widget().layout(renderer, limits)
+    }
+
+    fn on_event(
+        &mut self,
+        tree: &mut Tree,
+        event: Event,
+        layout: Layout<'_>,
+        cursor_position: Point,
+        renderer: &Renderer,
+        clipboard: &mut dyn Clipboard,
+        shell: &mut Shell<'_, Message>,
+    ) -> event::Status {
+        if self.follow.is_some() {
+            let position = match event {
+                Event::Mouse(mouse::Event::CursorMoved { position })
+                | Event::Touch(touch::Event::FingerPressed { position, .. })
+                | Event::Touch(touch::Event::FingerMoved { position, .. }) => Some(position),
+                Event::Mouse(mouse::Event::ButtonPressed(_)) => Some(cursor_position),
+                _ => None,
+            };
+
+            if let Some(position) = position {
+                let state = tree.state.downcast_mut::<State>();
+                state.anchor = position - layout.position();
+            }
+        }
+
+        if let (Event::Mouse(mouse::Event::ButtonPressed(mouse::Button::Right)), Some(message)) =
+            (&event, &self.on_right_click)
+        {
+            let bounds = layout.bounds();
+
+            if bounds.contains(cursor_position) {
+                let state = tree.state.downcast_mut::<State>();
+                state.anchor = cursor_position - bounds.position();
+                shell.publish(message.clone());
+
+                return event::Status::Captured;
+            }
+        }
+
+        self.content.as_widget_mut().on_event(
+            &mut tree.children[0],
+            event,
+            layout,
+            cursor_position,
+            renderer,
+            clipboard,
+            shell,
+        )
+    }
+
+    fn mouse_interaction(
+        &self,
+        tree: &Tree,
+        layout: Layout<'_>,
+        cursor_position: Point,
+        viewport: &Rectangle,
+        renderer: &Renderer,
+    ) -> mouse::Interaction {
+        self.content.as_widget().mouse_interaction(
+            &tree.children[0],
+            layout,
+            cursor_position,
+            viewport,
+            renderer,
+        )
+    }
+
+    fn draw(
+        &self,
+        tree: &Tree,
+        renderer: &mut Renderer,
+        theme: &Renderer::Theme,
+        style: &renderer::Style,
+        layout: Layout<'_>,
+        cursor_position: Point,
+        viewport: &Rectangle,
+    ) {
+        self.content.as_widget().draw(
+            &tree.children[0],
+            renderer,
+            theme,
+            style,
+            layout,
+            cursor_position,
+            viewport,
+        )
+    }
+
+    fn overlay<'b>(
+        &'b mut self,
+        tree: &'b mut Tree,
+        layout: Layout<'_>,
+        renderer: &Renderer,
+    ) -> Option<overlay::Element<'b, Message, Renderer>> {
+        let anchor = tree.state.downcast_ref::<State>().anchor;
+        let (content_tree, popup_tree) = tree.children.split_at_mut(1);
+
+        match (&mut self.popup, popup_tree.first_mut()) {
+            (Some(popup), Some(popup_tree)) => Some(overlay::Element::new(
+                layout.position() + anchor,
+                Box::new(Overlay {
+                    content: popup,
+                    tree: popup_tree,
+                    on_dismiss: &self.on_dismiss,
+                    follow: self.follow,
+                }),
+            )),
+            _ => self
+                .content
+                .as_widget_mut()
+                .overlay(&mut content_tree[0], layout, renderer),
+        }
+    }
+}
+
+struct Overlay<'a, 'b, Message, Renderer> {
+    content: &'b mut Element<'a, Message, Renderer>,
+    tree: &'b mut Tree,
+    on_dismiss: &'b Option<Message>,
+    follow: Option<Vector>,
+}
+
+impl<'a, 'b, Message, Renderer> overlay::Overlay<Message, Renderer>
+    for Overlay<'a, 'b, Message, Renderer>
+where
+    Message: Clone,
+    Renderer: iced_native::Renderer,
+{
+    fn layout(&self, renderer: &Renderer, bounds: Size, position: Point) -> layout::Node {
+        let limits = layout::Limits::new(Size::ZERO, bounds);
+        let mut node = self.content.as_widget().layout(renderer, &limits);
+        let size = node.size();
+
+        let position = match self.follow {
+            // Flip to the cursor's other side rather than slide under it.
+            Some(offset) => {
+                let x = if position.x + offset.x + size.width > bounds.width {
+                    position.x - offset.x - size.width
+                } else {
+                    position.x + offset.x
+                };
+                let y = if position.y + offset.y + size.height > bounds.height {
+                    position.y - offset.y - size.height
+                } else {
+                    position.y + offset.y
+                };
+
+                Point::new(x, y)
+            }
+            None => position,
+        };
+
+        // Keep the whole popup inside the window.
+        node.move_to(Point::new(
+            position.x.min(bounds.width - size.width).max(0.0),
+            position.y.min(bounds.height - size.height).max(0.0),
+        ));
+
+        node
+    }
+
+    fn draw(
+        &self,
+        renderer: &mut Renderer,
+        theme: &Renderer::Theme,
+        style: &renderer::Style,
+        layout: Layout<'_>,
+        cursor_position: Point,
+    ) {
+        self.content.as_widget().draw(
+            self.tree,
+            renderer,
+            theme,
+            style,
+            layout,
+            cursor_position,
+            &layout.bounds(),
+        )
+    }
+
+    fn on_event(
+        &mut self,
+        event: Event,
+        layout: Layout<'_>,
+        cursor_position: Point,
+        renderer: &Renderer,
+        clipboard: &mut dyn Clipboard,
+        shell: &mut Shell<'_, Message>,
+    ) -> event::Status {
+        if let (Event::Mouse(mouse::Event::ButtonPressed(_)), Some(message)) =
+            (&event, self.on_dismiss)
+        {
+            if !layout.bounds().contains(cursor_position) {
+                shell.publish(message.clone());
+
+                return event::Status::Captured;
+            }
+        }
+
+        self.content.as_widget_mut().on_event(
+            self.tree,
+            event,
+            layout,
+            cursor_position,
+            renderer,
+            clipboard,
+            shell,
+        )
+    }
+
+    fn mouse_interaction(
+        &self,
+        layout: Layout<'_>,
+        cursor_position: Point,
+        viewport: &Rectangle,
+        renderer: &Renderer,
+    ) -> mouse::Interaction {
+        self.content.as_widget().mouse_interaction(
+            self.tree,
+            layout,
+            cursor_position,
+            viewport,
+            renderer,
+        )
+    }
+}
+
+impl<'a, Message, Renderer> From<Popup<'a, Message, Renderer>> for Element<'a, Message, Renderer>
+where
+    Message: 'a + Clone,
+    Renderer: 'a + iced_native::Renderer,
+{
+    fn from(popup: Popup<'a, Message, Renderer>) -> Self {
+        Element::new(popup)
+    }
+}
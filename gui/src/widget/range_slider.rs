@@ -0,0 +1,303 @@
+//! A horizontal slider with two handles, setting the bottom and top of a
+//! span.
+
+use iced_audio::Normal;
+use iced_native::widget::tree::{self, Tree};
+use iced_native::{
+    event, layout, mouse, renderer, touch, Clipboard, Color, Element, Event, Layout, Length, Point,
+    Rectangle, Shell, Size, Theme, Widget,
+};
+
+// The handles' size, the rail's thickness, and the widget's height, in
+// logical pixels.
+const HANDLE_WIDTH: f32 = 10.0;
+const HANDLE_HEIGHT: f32 = 20.0;
+const RAIL_HEIGHT: f32 = 2.0;
+const BAR_HEIGHT: f32 = 6.0;
+
+/// One of a [`RangeSlider`]'s two handles.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Handle {
+    Low,
+    High,
+}
+
+/// A slider with a handle for each end of a span, and a bar between them.
+///
+/// Pressing anywhere on it takes the nearer handle to the cursor, and
+/// dragging moves that handle. It reports where a handle was moved to, and
+/// leaves keeping the low handle below the high one to the app, which knows
+/// both values.
+pub struct RangeSlider<'a, Message> {
+    low: Normal,
+    high: Normal,
+    on_change: Box<dyn Fn(Handle, Normal) -> Message + 'a>,
+    on_grab: Option<Box<dyn Fn(Handle) -> Message + 'a>>,
+    on_release: Option<Box<dyn Fn(Handle) -> Message + 'a>>,
+    width: Length,
+}
+
+impl<'a, Message> RangeSlider<'a, Message> {
+    pub fn new(
+        low: Normal,
+        high: Normal,
+        on_change: impl Fn(Handle, Normal) -> Message + 'a,
+    ) -> Self {
+        RangeSlider {
+            low,
+            high,
+            on_change: Box::new(on_change),
+            on_grab: None,
+            on_release: None,
+            width: Length::Fill,
+        }
+    }
+
+    /// Sets the message published when a handle is pressed, before it moves.
+    pub fn on_grab(mut self, on_grab: impl Fn(Handle) -> Message + 'a) -> Self {
+        self.on_grab = Some(Box::new(on_grab));
+        self
+    }
+
+    /// Sets the message published when a handle is let go.
+    pub fn on_release(mut self, on_release: impl Fn(Handle) -> Message + 'a) -> Self {
+        self.on_release = Some(Box::new(on_release));
+        self
+    }
+
+    pub fn width(mut self, width: Length) -> Self {
+        self.width = width;
+        self
+    }
+
+    fn value(&self, handle: Handle) -> Normal {
+        match handle {
+            Handle::Low => self.low,
+            Handle::High => self.high,
+        }
+    }
+}
+
+// The local state of a [`RangeSlider`].
+#[derive(Debug, Default)]
+struct State {
+    // The handle being dragged.
+    dragging: Option<Handle>,
+}
+
+// The x the center of a handle at `normal` sits at. The handles stop half
+// their width inside each end, so they're never cut off.
+fn handle_x(bounds: Rectangle, normal: Normal) -> f32 {
+    bounds.x + HANDLE_WIDTH / 2.0 + normal.as_f32() * (bounds.width - HANDLE_WIDTH)
+}
+
+// The inverse of `handle_x`, clipped to the track.
+fn normal_at(bounds: Rectangle, x: f32) -> Normal {
+    let travel = (bounds.width - HANDLE_WIDTH).max(1.0);
+
+    Normal::from_clipped((x - bounds.x - HANDLE_WIDTH / 2.0) / travel)
+}
+
+impl<'a, Message, Renderer> Widget<Message, Renderer> for RangeSlider<'a, Message>
+where
+    Renderer: renderer::Renderer<Theme = Theme>,
+{
+    fn tag(&self) -> tree::Tag {
+        tree::Tag::of::<State>()
+    }
+
+    fn state(&self) -> tree::State {
+        tree::State::new(State::default())
+    }
+
+    fn width(&self) -> Length {
+        self.width
+    }
+
+    fn height(&self) -> Length {
+        Length::Fixed(HANDLE_HEIGHT)
+    }
+
+    fn layout(&self, _renderer: &Renderer, limits: &layout::Limits) -> layout::Node {
+        let limits = limits
+            .width(self.width)
+            .height(Length::Fixed(HANDLE_HEIGHT));
+
+        layout::Node::new(limits.resolve(Size::ZERO))
+    }
+
+    fn on_event(
+        &mut self,
+        tree: &mut Tree,
+        event: Event,
+        layout: Layout<'_>,
+        cursor_position: Point,
+        _renderer: &Renderer,
+        _clipboard: &mut dyn Clipboard,
+        shell: &mut Shell<'_, Message>,
+    ) -> event::Status {
+        let state = tree.state.downcast_mut::<State>();
+        let bounds = layout.bounds();
+
+        match event {
+            Event::Mouse(mouse::Event::ButtonPressed(mouse::Button::Left))
+            | Event::Touch(touch::Event::FingerPressed { .. }) => {
+                if !bounds.contains(cursor_position) {
+                    return event::Status::Ignored;
+                }
+
+                let low = handle_x(bounds, self.low);
+                let high = handle_x(bounds, self.high);
+                let x = cursor_position.x;
+                // With the handles together, the side pressed on decides.
+                let handle = if (x - low).abs() < (x - high).abs() || (low == high && x < low) {
+                    Handle::Low
+                } else {
+                    Handle::High
+                };
+
+                state.dragging = Some(handle);
+                if let Some(on_grab) = &self.on_grab {
+                    shell.publish(on_grab(handle));
+                }
+
+                let normal = normal_at(bounds, x);
+                if normal != self.value(handle) {
+                    shell.publish((self.on_change)(handle, normal));
+                }
+
+                event::Status::Captured
+            }
+            Event::Mouse(mouse::Event::CursorMoved { .. })
+            | Event::Touch(touch::Event::FingerMoved { .. }) => {
+                let Some(handle) = state.dragging else {
+                    return event::Status::Ignored;
+                };
+
+                let normal = normal_at(bounds, cursor_position.x);
+                if normal != self.value(handle) {
+                    shell.publish((self.on_change)(handle, normal));
+                }
+
+                event::Status::Captured
+            }
+            Event::Mouse(mouse::Event::ButtonReleased(mouse::Button::Left))
+            | Event::Touch(touch::Event::FingerLifted { .. })
+            | Event::Touch(touch::Event::FingerLost { .. }) => {
+                let Some(handle) = state.dragging.take() else {
+                    return event::Status::Ignored;
+                };
+
+                if let Some(on_release) = &self.on_release {
+                    shell.publish(on_release(handle));
+                }
+
+                event::Status::Captured
+            }
+            _ => event::Status::Ignored,
+        }
+    }
+
+    fn mouse_interaction(
+        &self,
+        tree: &Tree,
+        layout: Layout<'_>,
+        cursor_position: Point,
+        _viewport: &Rectangle,
+        _renderer: &Renderer,
+    ) -> mouse::Interaction {
+        let state = tree.state.downcast_ref::<State>();
+
+        if state.dragging.is_some() {
+            mouse::Interaction::Grabbing
+        } else if layout.bounds().contains(cursor_position) {
+            mouse::Interaction::Grab
+        } else {
+            mouse::Interaction::default()
+        }
+    }
+
+    fn draw(
+        &self,
+        _tree: &Tree,
+        renderer: &mut Renderer,
+        theme: &Theme,
+        _style: &renderer::Style,
+        layout: Layout<'_>,
+        _cursor_position: Point,
+        _viewport: &Rectangle,
+    ) {
+        let bounds = layout.bounds();
+        let palette = theme.palette();
+        let center_y = bounds.center_y();
+        let low = handle_x(bounds, self.low);
+        let high = handle_x(bounds, self.high);
+        let quad = |bounds, border_color| renderer::Quad {
+            bounds,
+            border_radius: 2.0.into(),
+            border_width: 1.0,
+            border_color,
+        };
+
+        let rail = Rectangle {
+            x: bounds.x,
+            y: center_y - RAIL_HEIGHT / 2.0,
+            width: bounds.width,
+            height: RAIL_HEIGHT,
+        };
+        let bar = Rectangle {
+            x: low,
+            y: center_y - BAR_HEIGHT / 2.0,
+            width: high - low,
+            height: BAR_HEIGHT,
+        };
+        renderer.fill_quad(
+            quad(rail, Color::TRANSPARENT),
+            Color {
+                a: 0.3,
+                ..palette.text
+            },
+        );
+        renderer.fill_quad(quad(bar, Color::TRANSPARENT), palette.primary);
+
+        for x in [low, high] {
+            let handle = Rectangle {
+                x: x - HANDLE_WIDTH / 2.0,
+                y: bounds.y,
+                width: HANDLE_WIDTH,
+                height: HANDLE_HEIGHT,
+            };
+            renderer.fill_quad(quad(handle, palette.text), palette.background);
+        }
+    }
+}
+
+impl<'a, Message, Renderer> From<RangeSlider<'a, Message>> for Element<'a, Message, Renderer>
+where
+    Message: 'a,
+    Renderer: 'a + renderer::Renderer<Theme = Theme>,
+{
+    fn from(slider: RangeSlider<'a, Message>) -> Self {
+        Element::new(slider)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn positions_round_trip_inside_the_handles() {
+        let bounds = Rectangle {
+            x: 10.0,
+            y: 0.0,
+            width: 110.0,
+            height: HANDLE_HEIGHT,
+        };
+
+        assert_eq!(handle_x(bounds, Normal::MIN), 15.0);
+        assert_eq!(handle_x(bounds, Normal::MAX), 115.0);
+        assert_eq!(normal_at(bounds, 65.0), Normal::CENTER);
+        assert_eq!(normal_at(bounds, 0.0), Normal::MIN);
+    }
+}
@@ -0,0 +1,12 @@
+//! Show a parameter widget as a display that can't be moved.
+
+use super::Inert;
+
+/// Draws a widget without ever passing it input, so it only moves when the
+/// app changes the value it's built from.
+///
+/// The wrapped widget produces `()`, since it never sees an event to publish
+/// a message from, which lets it sit in a view of any message type. It isn't
+/// one of the app's [`Focus`](crate::focus::Focus) targets either, so
+/// keyboard focus skips it.
+pub type ReadOnly<'a, Renderer> = Inert<'a, (), Renderer, ()>;